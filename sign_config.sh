@@ -0,0 +1,30 @@
+#!/bin/bash
+
+# Sign config/config_remote.toml with the project ed25519 private key, its public key is
+# embedded in src/config/remote_config.rs (CONFIG_PUBLIC_KEYS).
+# usage: ./sign_config.sh <private_key.pem>
+#
+# Key rotation, cvm only accepts configs signed with a key it was built with:
+#   1. openssl genpkey -algorithm ed25519 -out cvm_config_key.pem, kept by the maintainers outside the repo
+#   2. print its public key with ./sign_config.sh --public cvm_config_key.pem and add it to CONFIG_PUBLIC_KEYS
+#   3. release cvm and wait for the users to update, the config is still signed with the old key
+#   4. sign the config with the new key and push config_remote.toml and config_remote.toml.sig together
+#   5. remove the old key from CONFIG_PUBLIC_KEYS in the next release
+# A leaked key is removed from CONFIG_PUBLIC_KEYS right away, the releases that still trust it must be updated.
+
+if [ "$1" == "--public" ]; then
+	openssl pkey -in "$2" -pubout -outform DER | tail -c 32 | base64
+	exit $?
+fi
+
+KEY=$1
+CONFIG="config/config_remote.toml"
+
+if [ -z "$KEY" ]; then
+	echo "usage: ./sign_config.sh <private_key.pem>"
+	echo "       ./sign_config.sh --public <private_key.pem>"
+	exit 1
+fi
+
+openssl pkeyutl -sign -inkey "$KEY" -rawin -in "$CONFIG" | base64 -w 0 > "$CONFIG.sig"
+echo "signature written to $CONFIG.sig"
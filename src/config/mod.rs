@@ -1,2 +1,4 @@
 pub mod remote_config;
-pub mod state_config;
\ No newline at end of file
+pub mod state_config;
+pub mod settings_config;
+pub mod state_migration;
\ No newline at end of file
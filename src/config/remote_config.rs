@@ -1,44 +1,102 @@
 use serde::{Deserialize};
+use std::cmp::Ordering;
 use std::fs;
 use std::path::Path;
-use crate::{Message, url_build};
+use ring::signature;
+use crate::{Message, MessageData, url_build};
+use crate::message::message::MessageKind;
 use crate::utils::download_manager::download_in_path;
 use crate::utils::folders::Folder;
+use crate::utils::version_utils::{compare_versions, LATEST, verify_version};
 
 const CONFIG_URL: &str = "https://raw.githubusercontent.com/orelvis15/cvm/master/config/config_remote.toml";
+const SIGNATURE_URL: &str = "https://raw.githubusercontent.com/orelvis15/cvm/master/config/config_remote.toml.sig";
 const FILE_NAME: &str = "config_remote.tom";
-const PROJECT_FOLDER: &str = ".cvm";
+const SIGNATURE_FILE_NAME: &str = "config_remote.tom.sig";
+const DOWNLOAD_PREFIX: &str = "download_";
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Ed25519 public keys (base64) used to verify the detached signature of the remote config.
+/// Only configs signed with one of the matching private keys are accepted, a key rotation
+/// adds the new key here one release before the config is signed with it (see sign_config.sh)
+const CONFIG_PUBLIC_KEYS: [&str; 1] = ["Tn28HoAMPBvaTOZije0etthfl87ecLNq0XR7chzvqm8="];
+
 pub fn get_remote_config() -> Result<RemoteConfig, Message> {
-    let home_dir = Folder::get_home_dir()?;
-    let project_folder = url_build(vec![&home_dir, &PROJECT_FOLDER.to_string()], false);
-    let file_path = format!("{}/{}", project_folder, FILE_NAME);
-    if need_download_config(&file_path)? {
-        download_in_path(&CONFIG_URL.to_string(), project_folder, FILE_NAME.to_string())?;
+    let project_folder = Folder::get_cvm_home()?;
+    let file_path = url_build(vec![&project_folder, &FILE_NAME.to_string()], false);
+    let signature_path = url_build(vec![&project_folder, &SIGNATURE_FILE_NAME.to_string()], false);
+
+    if need_download_config(&file_path, &signature_path) {
+        if let Err(error) = download_verified_config(&project_folder, &file_path, &signature_path) {
+            // keep working with the last known-good config if there is one
+            if read_verified_config(&file_path, &signature_path).is_err() {
+                return Err(error);
+            }
+            Message::ConfigSignature(MessageData {
+                message: format!("Could not update the remote config, using the last verified copy ({})", error.data().message),
+                kind: MessageKind::Warning,
+                ..Default::default()
+            }).print();
+        }
     }
-    Ok(get_file_toml(&file_path)?)
+
+    read_verified_config(&file_path, &signature_path)
 }
 
-fn get_file_toml(file_path: &String) -> Result<RemoteConfig, Message> {
-    let file = fs::read_to_string(file_path)?;
-    let parse_file = toml::from_str(&file)?;
+/// Download the config and its signature next to the current ones and only
+/// replace the current files once the signature has been verified
+fn download_verified_config(project_folder: &String, file_path: &String, signature_path: &String) -> Result<RemoteConfig, Message> {
+    let download_file = download_in_path(&CONFIG_URL.to_string(), project_folder.clone(), format!("{}{}", DOWNLOAD_PREFIX, FILE_NAME))?;
+    let download_signature = download_in_path(&SIGNATURE_URL.to_string(), project_folder.clone(), format!("{}{}", DOWNLOAD_PREFIX, SIGNATURE_FILE_NAME))?;
+
+    let result = read_verified_config(&download_file, &download_signature);
+
+    if result.is_ok() {
+        fs::rename(&download_signature, signature_path)?;
+        fs::rename(&download_file, file_path)?;
+    } else {
+        let _ = fs::remove_file(&download_file);
+        let _ = fs::remove_file(&download_signature);
+    }
+
+    result
+}
+
+fn read_verified_config(file_path: &String, signature_path: &String) -> Result<RemoteConfig, Message> {
+    let file = fs::read(file_path)?;
+    verify_signature(&file, signature_path)?;
+    let parse_file = toml::from_str(&String::from_utf8_lossy(&file))?;
     Ok(parse_file)
 }
 
-fn need_download_config(file_url: &String) -> Result<bool, Message> {
-    let file_path = Path::new(file_url);
-    if !file_path.exists() {
-        return Ok(true);
+fn verify_signature(data: &[u8], signature_path: &String) -> Result<(), Message> {
+    let error = |detail: String| Message::ConfigSignature(MessageData {
+        message: "The remote config is not signed or its signature is not valid".to_string(),
+        stack: vec![detail],
+        ..Default::default()
+    });
+
+    if !Path::new(signature_path).exists() {
+        return Err(error(format!("Signature file {} not found", signature_path)));
     }
 
-    let confg = get_file_toml(file_url)?;
+    let signature_text = fs::read_to_string(signature_path)?;
+    let signature = base64::decode(signature_text.trim()).map_err(|e| error(e.to_string()))?;
 
-    if confg.general.version != VERSION {
-        return Ok(true);
+    for key in CONFIG_PUBLIC_KEYS {
+        let public_key = base64::decode(key).map_err(|e| error(e.to_string()))?;
+        if signature::UnparsedPublicKey::new(&signature::ED25519, public_key).verify(data, &signature).is_ok() {
+            return Ok(());
+        }
     }
+    Err(error(format!("Signature {} does not match the config", signature_path)))
+}
 
-    return Ok(false);
+fn need_download_config(file_path: &String, signature_path: &String) -> bool {
+    match read_verified_config(file_path, signature_path) {
+        Ok(config) => config.general.version != VERSION,
+        Err(_) => true,
+    }
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
@@ -51,7 +109,116 @@ pub struct RemoteConfig {
     pub structure_folder_item: Vec<StructureFolderItem>,
     pub binaries: Binaries,
     pub services_item: Vec<Services>,
-    pub download_install: DownloadInstall
+    pub download_install: DownloadInstall,
+    #[serde(default)]
+    pub db_compatibility: Vec<DbCompatibility>,
+    #[serde(default)]
+    pub component: Vec<Component>,
+    #[serde(default)]
+    pub toolchain: Vec<Toolchain>,
+    #[serde(default)]
+    pub library: Vec<LibraryRecipe>,
+}
+
+/// Native library built from source by [cvm init -b], {prefix} is replaced by the install prefix in the steps and files
+#[derive(Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+#[serde(default)]
+pub struct LibraryRecipe {
+    pub name: String,
+    pub repository: String,
+    /// Commit or tag built, the library is built again when it changes
+    pub commit: String,
+    pub prefix: String,
+    /// Commands run with bash in the checkout, in order
+    pub steps: Vec<String>,
+    /// Files that must exist once the library is installed
+    pub files: Vec<String>,
+}
+
+impl LibraryRecipe {
+    pub fn fill_prefix(self: &Self, text: &String) -> String {
+        text.replace("{prefix}", &self.prefix)
+    }
+
+    pub fn get_files(self: &Self) -> Vec<String> {
+        self.files.iter().map(|file| self.fill_prefix(file)).collect()
+    }
+}
+
+/// ghc and cabal versions needed to build a range of cardano-node versions
+#[derive(Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+#[serde(default)]
+pub struct Toolchain {
+    /// First node version of the range
+    pub from: String,
+    /// First node version out of the range, empty when the range has no end
+    pub to: String,
+    pub ghc: String,
+    pub cabal: String,
+}
+
+/// Toolchain that builds a node version, the init one when the version is not in the matrix or is not a version number
+pub fn get_toolchain(config: &RemoteConfig, version: &String) -> Toolchain {
+    let default = Toolchain {
+        ghc: config.init.haskell_ghc_version.clone(),
+        cabal: config.init.haskell_cabal_version.clone(),
+        ..Default::default()
+    };
+
+    if verify_version(version).is_err() || version == LATEST {
+        return default;
+    }
+
+    config.toolchain.iter()
+        .find(|item| compare_versions(version, &item.from) != Ordering::Less && (item.to.is_empty() || compare_versions(version, &item.to) == Ordering::Less))
+        .cloned()
+        .unwrap_or(default)
+}
+
+/// Tool versioned apart from cardano-node, installed with [cvm install --component <name>]
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Component {
+    pub name: String,
+    pub repository: String,
+    /// Github api url of the latest release, used to resolve the latest version
+    pub release: String,
+    /// Prefix of the git tags, the tag of a version is <tag_prefix><version>
+    pub tag_prefix: String,
+    /// Commands run with bash in the checkout to build it, with the ghcup bin folder in the PATH
+    pub build: Vec<String>,
+    /// Url of the tar.gz release archive, with the {version} and {tag} placeholders. Empty when there is no binary release
+    pub download_url: String,
+    pub binaries: Vec<String>,
+}
+
+pub const NODE_COMPONENT: &str = "cardano-node";
+
+pub fn is_node_component(component: &String) -> bool {
+    component.is_empty() || component == NODE_COMPONENT
+}
+
+pub fn get_component<'a>(config: &'a RemoteConfig, name: &String) -> Result<&'a Component, Message> {
+    match config.component.iter().find(|component| &component.name == name) {
+        Some(component) => Ok(component),
+        None => Err(Message::ParseArg(MessageData {
+            message: format!("The component {} is not supported, the available ones are: {}, {}", name, NODE_COMPONENT,
+                             config.component.iter().map(|component| component.name.clone()).collect::<Vec<String>>().join(", ")),
+            ..Default::default()
+        }))
+    }
+}
+
+/// Change in the chain database format introduced by a cardano-node version
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DbCompatibility {
+    pub version: String,
+    /// Upgrading from an older version to this one replays the ledger from genesis
+    pub ledger_replay: bool,
+    /// A db written by this version or a newer one can be used by older versions
+    pub downgrade_safe: bool,
+    pub note: String,
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
@@ -79,16 +246,6 @@ pub struct Init {
     pub install_ghc_file: String,
     pub ghcup_bin_path: String,
     pub ghcup_pattern_sed: String,
-    pub libsodium_repository: String,
-    pub libsodium_commit: String,
-    pub libsodium_folder: String,
-    pub libsodium_autogen_file: String,
-    pub libsodium_config_file: String,
-    pub libsecp256k1_repository: String,
-    pub libsecp256k1_commit: String,
-    pub libsecp256k1_autogen_file: String,
-    pub libsecp256k1_configure_file: String,
-    pub libsecp256k1_folder: String,
     pub haskell_ghc_version: String,
     pub haskell_cabal_version: String,
 }
@@ -99,6 +256,32 @@ pub struct ConfigFileItem {
     pub name: String,
     pub folder_key: String,
     pub pattern_sed: String,
+    /// Sed script with the {root} placeholder, pattern_sed stays for the versions that don't replace it
+    #[serde(default)]
+    pub root_pattern_sed: String,
+    /// RFC 6902 operations applied to json files after the download, the sed scripts are skipped when there are patches
+    #[serde(default)]
+    pub json_patch: Vec<JsonPatch>,
+}
+
+const NO_PATTERN_SED: &String = &String::new();
+
+impl ConfigFileItem {
+    pub fn get_pattern_sed(self: &Self) -> &String {
+        if !self.json_patch.is_empty() { return NO_PATTERN_SED; }
+        if self.root_pattern_sed.is_empty() { &self.pattern_sed } else { &self.root_pattern_sed }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct JsonPatch {
+    pub op: String,
+    pub path: String,
+    /// Source of move and copy
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
@@ -113,6 +296,12 @@ pub struct Dependencies {
     pub rhel: Vec<String>,
     pub rhel_7: Vec<String>,
     pub rhel_8: Vec<String>,
+    #[serde(default)]
+    pub opensuse: Vec<String>,
+    #[serde(default)]
+    pub arch: Vec<String>,
+    #[serde(default)]
+    pub alpine: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
@@ -133,7 +322,6 @@ pub struct StructureFolderItem {
 
 #[derive(Deserialize, Debug, Clone, Default)]
 pub struct Services {
-    pub url: String,
     pub file: String,
     pub name: String,
 }
\ No newline at end of file
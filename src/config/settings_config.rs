@@ -0,0 +1,113 @@
+#![allow(dead_code, unused_variables)]
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use crate::{Message, url_build};
+
+pub const FILE_NAME: &str = "settings.tom";
+
+/// Read the local settings stored in the cvm home folder.
+/// Missing file or missing keys fall back to the defaults
+pub fn get_settings(cvm_home: &String) -> Result<Settings, Message> {
+    let file_path = url_build(vec![cvm_home, &FILE_NAME.to_string()], false);
+
+    if !Path::new(file_path.as_str()).exists() {
+        return Ok(Settings::default());
+    };
+
+    let file = fs::read_to_string(file_path)?;
+    let parse_file = toml::from_str(&file)?;
+    Ok(parse_file)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Settings {
+    /// Run without root: workspace in the user home, user level systemd units and no sudo
+    pub rootless: bool,
+    /// Folder where the cardano workspace is created, by default /opt or the user home in rootless mode
+    pub workspace: String,
+    /// Folder where cvm keeps its state, config and temporal files, by default ~/.cvm
+    pub cvm_home: String,
+    /// Base folder for the ghcup installation, by default the user home
+    pub ghcup_prefix: String,
+    /// Folder where [cvm db backup] stores the chain database backups, by default {workspace}/cardano/backups
+    pub backup_dir: String,
+    /// Number of backups kept after a new one is created, 0 keeps all of them
+    pub backup_keep: usize,
+    /// Service manager that runs the node: systemd, openrc, runit, supervisord or cvm, detected when empty
+    pub service_manager: String,
+    /// Parameters of the cardano node service, the [service] table
+    pub service: ServiceSettings,
+    /// Automatic upgrades of cardano-node, the [upgrade] table
+    pub upgrade: UpgradeSettings,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ServiceSettings {
+    /// Port of the node, passed to cnode.sh as CNODE_PORT
+    pub port: u16,
+    /// GHC runtime options of cardano-node like "-N4 -A16m", passed with GHCRTS
+    pub rts_options: String,
+    /// systemd MemoryMax like "24G", without limit when empty
+    pub memory_max: String,
+    /// systemd CPUQuota like "200%", without limit when empty
+    pub cpu_quota: String,
+    /// systemd Restart policy
+    pub restart: String,
+    /// Seconds before the node is started again
+    pub restart_sec: u64,
+    pub limit_nofile: u64,
+    /// Seconds the node has to close the database when it is stopped before it is killed
+    pub stop_timeout: u64,
+    /// Seconds [cvm use] waits for the node to open the socket and follow the chain after the restart
+    pub start_timeout: u64,
+    /// Seconds after a restart of a block producer that must be free of scheduled blocks, the restart waits for them
+    pub leader_window: u64,
+    /// Longest wait for the scheduled blocks, the restart is refused when they are later
+    pub leader_wait: u64,
+}
+
+impl Default for ServiceSettings {
+    fn default() -> Self {
+        ServiceSettings {
+            port: 6000,
+            rts_options: "".to_string(),
+            memory_max: "".to_string(),
+            cpu_quota: "".to_string(),
+            restart: "always".to_string(),
+            restart_sec: 60,
+            limit_nofile: 1048576,
+            stop_timeout: 60,
+            start_timeout: 600,
+            leader_window: 600,
+            leader_wait: 1800,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct UpgradeSettings {
+    /// Releases installed by the automatic upgrades: none, patch, minor or major
+    pub policy: String,
+    /// systemd calendar of the maintenance window where the new version is put in use
+    pub window: String,
+    /// systemd calendar of the download or build of the new version, ahead of the window
+    pub prepare: String,
+    /// Build the new versions instead of downloading them
+    pub build: bool,
+}
+
+impl Default for UpgradeSettings {
+    fn default() -> Self {
+        UpgradeSettings {
+            policy: "none".to_string(),
+            window: "Sun *-*-* 02:00:00 UTC".to_string(),
+            prepare: "daily".to_string(),
+            build: false,
+        }
+    }
+}
@@ -1,28 +1,93 @@
 #![allow(dead_code, unused_variables)]
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::fs::File;
-use std::io::Write;
 use std::path::Path;
-use crate::{Message, Success, url_build};
+use toml::Value;
+use toml::value::Table;
+use crate::config::remote_config::is_node_component;
+use crate::config::state_migration::{migrate, SCHEMA_VERSION};
+use crate::{Message, MessageData, Success, url_build};
+use crate::message::message::MessageKind;
 use crate::task::task_type::TaskType;
+use crate::utils::file_utils::{backup_path, write_file_atomic, write_file_atomic_with};
 use crate::utils::folders::Folder;
 
 const FILE_NAME: &str = "state.tom";
-const PROJECT_FOLDER: &str = ".cvm";
+const UPGRADE_HISTORY: usize = 20;
+/// Tables every state has had since the first schema
+const REQUIRED_TABLES: [&str; 2] = ["init", "use"];
 
 pub fn get_state() -> Result<State, Message> {
-    let home_dir = Folder::get_home_dir()?;
-    let file_path = url_build(vec![&home_dir, &PROJECT_FOLDER.to_string(), &FILE_NAME.to_string()], false);
+    let cvm_home = Folder::get_cvm_home()?;
+    let file_path = url_build(vec![&cvm_home, &FILE_NAME.to_string()], false);
 
     if !Path::new(file_path.as_str()).exists() {
         create_state_file()?;
     };
 
+    match read_state_file(&file_path) {
+        Ok(state) => Ok(state),
+        Err(error) => recover_state(&file_path, error),
+    }
+}
+
+fn read_state_file(file_path: &String) -> Result<State, Message> {
     let file = fs::read_to_string(file_path)?;
-    let parse_file = toml::from_str(&file)?;
-    Ok(parse_file)
+    let (state, migrated) = parse_state(&file)?;
+    if migrated {
+        set_state(state.clone())?;
+    }
+    Ok(state)
+}
+
+/// Parse a state of any schema version, upgrading it to the current layout.
+/// Return the state and if it was migrated
+pub fn parse_state(text: &str) -> Result<(State, bool), Message> {
+    let mut table: Table = toml::from_str(text)?;
+    // an empty or truncated file is valid toml, it must not be read as a new state
+    for key in REQUIRED_TABLES {
+        if !matches!(table.get(key), Some(Value::Table(_))) {
+            return Err(Message::StateFile(MessageData {
+                message: format!("The state has no [{}] table, it is damaged or it is not a cvm state", key),
+                ..Default::default()
+            }));
+        }
+    }
+    let migrated = migrate(&mut table)?;
+    let state = Value::Table(table).try_into()?;
+    Ok((state, migrated))
+}
+
+pub fn state_to_string(state: &State) -> String {
+    toml::to_string(state).unwrap()
+}
+
+/// Replace the whole state, used to import a state exported from another server
+pub fn import_state(state: State) -> Result<Success, Message> {
+    set_state(state)
+}
+
+/// The state file is broken (empty or partially written), restore the previous good copy
+fn recover_state(file_path: &String, error: Message) -> Result<State, Message> {
+    let backup = backup_path(file_path);
+
+    if let Ok(state) = read_state_file(&backup) {
+        Message::StateFile(MessageData {
+            message: format!("The state file {} was damaged, the previous copy has been restored", file_path),
+            kind: MessageKind::Warning,
+            ..Default::default()
+        }).print();
+        fs::copy(&backup, file_path)?;
+        return Ok(state);
+    }
+
+    Err(Message::StateFile(MessageData {
+        message: format!("The state file {} is damaged and there is no valid copy to restore it", file_path),
+        stack: vec![error.data().message.clone()],
+        ..Default::default()
+    }))
 }
 
 pub fn reset_init() -> Result<Success, Message>{
@@ -72,27 +137,74 @@ pub fn set_init_network(value: String) -> Result<Success, Message> {
 
 pub fn set_version_use(version: String) -> Result<Success, Message> {
     let mut state = get_state()?;
-    state.r#use = Use { version };
+    state.r#use.version = version;
+    set_state(state)
+}
+
+pub fn set_component_use(component: &String, version: String) -> Result<Success, Message> {
+    if is_node_component(component) {
+        return set_version_use(version);
+    }
+    let mut state = get_state()?;
+    if version.is_empty() {
+        state.r#use.components.remove(component);
+    } else {
+        state.r#use.components.insert(component.clone(), version);
+    }
+    set_state(state)
+}
+
+pub fn set_library_installed(name: &String, commit: &String) -> Result<Success, Message> {
+    let mut state = get_state()?;
+    state.init.libraries.insert(name.clone(), commit.clone());
+    set_state(state)
+}
+
+/// Version of the node that last wrote the chain database
+pub fn set_db_version(version: String) -> Result<Success, Message> {
+    let mut state = get_state()?;
+    state.r#use.db_version = version;
+    set_state(state)
+}
+
+pub fn set_upgrade_pending(version: String) -> Result<Success, Message> {
+    let mut state = get_state()?;
+    state.upgrade.pending = version;
+    set_state(state)
+}
+
+pub fn set_upgrade_scheduled(version: String, at: String) -> Result<Success, Message> {
+    let mut state = get_state()?;
+    state.upgrade.scheduled_version = version;
+    state.upgrade.scheduled_at = at;
+    set_state(state)
+}
+
+/// Record the result of an upgrade, only the last ones are kept
+pub fn add_upgrade_record(record: UpgradeRecord) -> Result<Success, Message> {
+    let mut state = get_state()?;
+    state.upgrade.history.push(record);
+    let extra = state.upgrade.history.len().saturating_sub(UPGRADE_HISTORY);
+    state.upgrade.history.drain(..extra);
     set_state(state)
 }
 
 fn set_state(state: State) -> Result<Success, Message> {
-    let home_dir = Folder::get_home_dir()?;
-    let file_path = url_build(vec![&home_dir, &PROJECT_FOLDER.to_string(), &FILE_NAME.to_string()], false);
-    let mut file = File::options().truncate(true).write(true).open(file_path)?;
-    let toml_str = toml::to_string(&state).unwrap();
-    file.write_all(toml_str.as_bytes())?;
-    Ok(Success {})
+    let cvm_home = Folder::get_cvm_home()?;
+    let file_path = url_build(vec![&cvm_home, &FILE_NAME.to_string()], false);
+    let toml_str = state_to_string(&state);
+    // the previous copy is what recover_state restores, a damaged file must not replace it
+    let keep_backup = fs::read_to_string(&file_path).map(|text| parse_state(&text).is_ok()).unwrap_or(false);
+    write_file_atomic_with(&file_path, toml_str.as_bytes(), keep_backup)
 }
 
 pub fn set_task_complete(task: &TaskType) {
     if let Ok(mut state) = get_state() {
         match task {
             TaskType::InstallDependences => { state.init.install_dependences_task = true }
-            TaskType::InstallGhcup => { state.init.install_hanskell_ghc_task = true }
+            TaskType::InstallGhcup => { state.init.install_haskell_ghc_task = true }
             TaskType::CreateFolderStructure => { state.init.create_folder_structure = true }
             TaskType::DownloadConfigFiles => { state.init.download_config_files_task = true }
-            TaskType::Libsecp256k1 => { state.init.install_libsecp256k1_task = true }
             _ => {}
         }
         let _ = set_state(state);
@@ -103,10 +215,9 @@ pub fn get_task_complete(task: &TaskType) -> bool {
     if let Ok(state) = get_state() {
         return match task {
             TaskType::InstallDependences => { state.init.install_dependences_task }
-            TaskType::InstallGhcup => { state.init.install_hanskell_ghc_task }
+            TaskType::InstallGhcup => { state.init.install_haskell_ghc_task }
             TaskType::CreateFolderStructure => { state.init.create_folder_structure }
             TaskType::DownloadConfigFiles => { state.init.download_config_files_task }
-            TaskType::Libsecp256k1 => { state.init.install_libsecp256k1_task }
             _ => { false }
         };
     }
@@ -114,24 +225,72 @@ pub fn get_task_complete(task: &TaskType) -> bool {
 }
 
 fn create_state_file() -> Result<Success, Message> {
-    let home_dir = Folder::get_home_dir()?;
-    let file_path = url_build(vec![&home_dir, &PROJECT_FOLDER.to_string(), &FILE_NAME.to_string()], false);
+    let cvm_home = Folder::get_cvm_home()?;
+    let file_path = url_build(vec![&cvm_home, &FILE_NAME.to_string()], false);
     let state = State::default();
     let toml_str = toml::to_string(&state).unwrap();
-    let mut file = File::create(Path::new(file_path.as_str()))?;
-    file.write_all(toml_str.as_bytes())?;
-    Ok(Success {})
+    write_file_atomic(&file_path, toml_str.as_bytes())
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct State {
+    #[serde(default)]
+    pub schema_version: u32,
     pub init: Init,
     pub r#use: Use,
+    #[serde(default)]
+    pub upgrade: Upgrade,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            schema_version: SCHEMA_VERSION,
+            init: Init::default(),
+            r#use: Use::default(),
+            upgrade: Upgrade::default(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Use {
     pub version: String,
+    /// Version that last wrote the chain database, empty in states created before it was tracked
+    #[serde(default)]
+    pub db_version: String,
+    /// Version in use of every component other than cardano-node
+    #[serde(default)]
+    pub components: BTreeMap<String, String>,
+}
+
+impl Use {
+    pub fn get_version(&self, component: &String) -> String {
+        if is_node_component(component) {
+            return self.version.clone();
+        }
+        self.components.get(component).cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Upgrade {
+    /// Version installed ahead of the next maintenance window
+    pub pending: String,
+    /// Version and systemd calendar of the upgrade scheduled with [cvm upgrade --at]
+    pub scheduled_version: String,
+    pub scheduled_at: String,
+    /// Last upgrades, the oldest first
+    pub history: Vec<UpgradeRecord>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UpgradeRecord {
+    pub at: String,
+    pub from: String,
+    pub to: String,
+    /// installed, upgraded or the error that stopped the upgrade
+    pub result: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -139,11 +298,13 @@ pub struct Init {
     pub network: String,
     pub success: bool,
     pub install_dependences_task: bool,
-    pub install_hanskell_ghc_task: bool,
+    pub install_haskell_ghc_task: bool,
     pub create_folder_structure: bool,
-    pub install_libsecp256k1_task: bool,
     pub download_config_files_task: bool,
     pub files_item: Vec<ConfigFiles>,
+    /// Commit installed of every native library
+    #[serde(default)]
+    pub libraries: BTreeMap<String, String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
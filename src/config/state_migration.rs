@@ -0,0 +1,66 @@
+#![allow(dead_code, unused_variables)]
+
+use toml::Value;
+use toml::value::Table;
+use crate::{Message, MessageData};
+
+pub const SCHEMA_VERSION: u32 = 2;
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Each migration upgrades the state from the version of its position to the next one,
+/// MIGRATIONS[0] upgrades 0 -> 1, MIGRATIONS[1] upgrades 1 -> 2 ...
+const MIGRATIONS: &[fn(&mut Table)] = &[
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+];
+
+/// Upgrade a state file of any older layout to the current one.
+/// Return true if the state was changed
+pub fn migrate(state: &mut Table) -> Result<bool, Message> {
+    let version = get_schema_version(state)?;
+
+    if version > SCHEMA_VERSION {
+        return Err(Message::StateFile(MessageData {
+            message: format!("The state file was created by a newer version of cvm (schema {}), please update cvm", version),
+            ..Default::default()
+        }));
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(state);
+    }
+
+    state.insert(SCHEMA_VERSION_KEY.to_string(), Value::Integer(SCHEMA_VERSION as i64));
+    Ok(version != SCHEMA_VERSION)
+}
+
+fn get_schema_version(state: &Table) -> Result<u32, Message> {
+    match state.get(SCHEMA_VERSION_KEY) {
+        None => Ok(0),
+        Some(Value::Integer(version)) if *version >= 0 => Ok(*version as u32),
+        Some(value) => Err(Message::StateFile(MessageData {
+            message: format!("Invalid {} in the state file: {}", SCHEMA_VERSION_KEY, value),
+            ..Default::default()
+        }))
+    }
+}
+
+fn rename_key(table: &mut Table, from: &str, to: &str) {
+    if let Some(value) = table.remove(from) {
+        table.insert(to.to_string(), value);
+    }
+}
+
+/// v1: fix the misspelled install_hanskell_ghc_task key
+fn migrate_v0_to_v1(state: &mut Table) {
+    if let Some(Value::Table(init)) = state.get_mut("init") {
+        rename_key(init, "install_hanskell_ghc_task", "install_haskell_ghc_task");
+    }
+}
+
+/// v2: native libraries are tracked by commit in init.libraries, the libsecp256k1 flag doesn't say which one was built
+fn migrate_v1_to_v2(state: &mut Table) {
+    if let Some(Value::Table(init)) = state.get_mut("init") {
+        init.remove("install_libsecp256k1_task");
+    }
+}
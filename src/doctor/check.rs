@@ -0,0 +1,80 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::config::remote_config::RemoteConfig;
+use crate::{Message, MessageData, Success, Term};
+
+pub trait Check {
+
+    /// Short name printed in the report
+    fn name(self: &Self) -> String;
+
+    /// Run the check over the current environment
+    /// * `RemoteConfig` - The config object
+    /// * `Term` - term Configuration
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult;
+
+    /// A failure in a check that is not required is reported as a warning
+    fn is_required(self: &Self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub status: CheckStatus,
+    pub message: String,
+    /// How to fix the problem, empty when the check passes
+    pub hint: String,
+}
+
+impl CheckResult {
+    pub fn pass(message: String) -> Self {
+        CheckResult { status: CheckStatus::Pass, message, hint: "".to_string() }
+    }
+
+    pub fn warn(message: String, hint: String) -> Self {
+        CheckResult { status: CheckStatus::Warn, message, hint }
+    }
+
+    pub fn fail(message: String, hint: String) -> Self {
+        CheckResult { status: CheckStatus::Fail, message, hint }
+    }
+}
+
+/// Run all checks and print the result of each one.
+/// With `only_problems` the passed checks are not printed, used by the preflight gates
+pub fn run_checks(checks: Vec<Box<dyn Check>>, config: &RemoteConfig, term: &mut Term, only_problems: bool) -> Result<Success, Message> {
+    let mut failed = vec![];
+
+    for check in checks {
+        let mut result = check.run(config, term);
+
+        if result.status == CheckStatus::Fail && !check.is_required() {
+            result.status = CheckStatus::Warn;
+        }
+
+        if result.status == CheckStatus::Fail {
+            failed.push(check.name());
+        }
+
+        if !only_problems || result.status != CheckStatus::Pass {
+            term.print_check_result(&check.name(), &result);
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(Message::CheckFailed(MessageData {
+            message: format!("{} check(s) failed: {}, run [cvm doctor] for more details", failed.len(), failed.join(", ")),
+            ..Default::default()
+        }));
+    }
+
+    Ok(Success {})
+}
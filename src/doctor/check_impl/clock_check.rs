@@ -0,0 +1,31 @@
+#![allow(dead_code, unused_variables)]
+
+use std::process::Command;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::Term;
+
+pub struct ClockCheck {}
+
+impl Check for ClockCheck {
+
+    fn name(self: &Self) -> String {
+        "clock".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let hint = "Enable time synchronization with [sudo timedatectl set-ntp true] or install chrony".to_string();
+        let output = Command::new("timedatectl").args(["show", "-p", "NTPSynchronized", "--value"]).output();
+
+        match output {
+            Ok(output) if output.status.success() => {
+                if String::from_utf8_lossy(&output.stdout).trim() == "yes" {
+                    CheckResult::pass("The clock is synchronized".to_string())
+                } else {
+                    CheckResult::warn("The clock is not synchronized, the node can lose blocks".to_string(), hint)
+                }
+            }
+            _ => CheckResult::warn("Could not check the clock synchronization".to_string(), hint)
+        }
+    }
+}
@@ -0,0 +1,33 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::package_manager::{get_dependencies_from_os, get_package_manager};
+use crate::Term;
+
+pub struct DependenciesCheck {}
+
+impl Check for DependenciesCheck {
+
+    fn name(self: &Self) -> String {
+        "dependencies".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let os = os_info::get();
+
+        let (dependencies, package_manager) = match (get_dependencies_from_os(&config.dependencies), get_package_manager()) {
+            (Ok(dependencies), Ok(package_manager)) => (dependencies, package_manager),
+            _ => return CheckResult::fail(format!("{} is not supported", os.os_type()),
+                                          "Install the dependencies manually, see the supported systems in the README".to_string()),
+        };
+
+        let missing = package_manager.missing_packages(&dependencies);
+        if !missing.is_empty() {
+            return CheckResult::warn(format!("{} missing: {}", package_manager.name(), missing.join(", ")),
+                                     "Run [cvm init] to install them".to_string());
+        }
+
+        CheckResult::pass(format!("The dependencies for {} are installed", os.os_type()))
+    }
+}
@@ -0,0 +1,50 @@
+#![allow(dead_code, unused_variables)]
+
+use std::ffi::CString;
+use std::mem::MaybeUninit;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::Term;
+use crate::utils::folders::Folder;
+
+const GB: u64 = 1024 * 1024 * 1024;
+/// The mainnet chain database needs more than 100GB
+const RECOMMENDED_FREE_GB: u64 = 150;
+const MIN_FREE_GB: u64 = 20;
+
+pub struct DiskCheck {}
+
+impl Check for DiskCheck {
+
+    fn name(self: &Self) -> String {
+        "disk".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let workspace = Folder::get_workspaces_dir();
+
+        let free = match free_space(&workspace) {
+            Some(free) => free / GB,
+            None => return CheckResult::warn(format!("Could not read the free space of {}", workspace), "".to_string()),
+        };
+
+        let hint = format!("Free space in {} or choose another workspace with --workspace", workspace);
+        if free < MIN_FREE_GB {
+            CheckResult::fail(format!("Only {} GB free in {}", free, workspace), hint)
+        } else if free < RECOMMENDED_FREE_GB {
+            CheckResult::warn(format!("{} GB free in {}, the chain database needs at least {} GB", free, workspace, RECOMMENDED_FREE_GB), hint)
+        } else {
+            CheckResult::pass(format!("{} GB free in {}", free, workspace))
+        }
+    }
+}
+
+pub fn free_space(path: &String) -> Option<u64> {
+    let path = CString::new(path.as_str()).ok()?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let stat = unsafe { stat.assume_init() };
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
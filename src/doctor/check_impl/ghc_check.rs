@@ -0,0 +1,46 @@
+#![allow(dead_code, unused_variables)]
+
+use std::process::Command;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::{Term, url_build};
+use crate::utils::folders::Folder;
+
+pub struct GhcCheck {
+    /// ghc is only needed to build cardano-node
+    pub required: bool,
+}
+
+impl Check for GhcCheck {
+
+    fn name(self: &Self) -> String {
+        "ghc".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let expected = &config.init.haskell_ghc_version;
+        let hint = format!("Run [cvm init -b] or [ghcup set ghc {}]", expected);
+
+        let ghc = match Folder::get_ghcup_bin_dir(config) {
+            Ok(ghcup_dir) => url_build(vec![&ghcup_dir, &"ghc".to_string()], false),
+            Err(error) => return CheckResult::fail(error.data().message.clone(), hint),
+        };
+
+        match Command::new(&ghc).arg("--numeric-version").output() {
+            Ok(output) if output.status.success() => {
+                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                // any ghc of the version matrix is fine, the build selects the one it needs
+                if &version == expected || config.toolchain.iter().any(|toolchain| toolchain.ghc == version) {
+                    CheckResult::pass(format!("ghc {} is active", version))
+                } else {
+                    CheckResult::fail(format!("ghc {} is active but {} is required", version, expected), hint)
+                }
+            }
+            _ => CheckResult::fail(format!("ghc was not found in {}", ghc), hint)
+        }
+    }
+
+    fn is_required(self: &Self) -> bool {
+        self.required
+    }
+}
@@ -0,0 +1,32 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::{Term, url_build};
+use crate::utils::folders::Folder;
+
+/// The build installs and selects the ghc and cabal of the node version, it only needs ghcup
+pub struct GhcupCheck {}
+
+impl Check for GhcupCheck {
+
+    fn name(self: &Self) -> String {
+        "ghcup".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let hint = "Run [cvm init -b] to install ghcup".to_string();
+
+        let ghcup = match Folder::get_ghcup_bin_dir(config) {
+            Ok(ghcup_dir) => url_build(vec![&ghcup_dir, &"ghcup".to_string()], false),
+            Err(error) => return CheckResult::fail(error.data().message.clone(), hint),
+        };
+
+        if Path::new(&ghcup).exists() {
+            CheckResult::pass(format!("ghcup is installed in {}", ghcup))
+        } else {
+            CheckResult::fail(format!("ghcup was not found in {}", ghcup), hint)
+        }
+    }
+}
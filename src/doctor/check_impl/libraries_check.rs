@@ -0,0 +1,36 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::Term;
+
+pub struct LibrariesCheck {
+    /// The native libraries are only needed to build cardano-node
+    pub required: bool,
+}
+
+impl Check for LibrariesCheck {
+
+    fn name(self: &Self) -> String {
+        "libraries".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let missing: Vec<String> = config.library.iter()
+            .filter(|recipe| !recipe.get_files().iter().all(|file| Path::new(file).exists()))
+            .map(|recipe| recipe.name.clone())
+            .collect();
+
+        if missing.is_empty() {
+            let names: Vec<String> = config.library.iter().map(|recipe| recipe.name.clone()).collect();
+            CheckResult::pass(format!("{} installed", names.join(", ")))
+        } else {
+            CheckResult::fail(format!("{} not installed", missing.join(", ")), "Run [cvm init -b] to prepare the server for building".to_string())
+        }
+    }
+
+    fn is_required(self: &Self) -> bool {
+        self.required
+    }
+}
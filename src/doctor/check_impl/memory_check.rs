@@ -0,0 +1,40 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::Term;
+
+const MEMINFO: &str = "/proc/meminfo";
+const RECOMMENDED_GB: u64 = 16;
+const MIN_GB: u64 = 8;
+
+pub struct MemoryCheck {}
+
+impl Check for MemoryCheck {
+
+    fn name(self: &Self) -> String {
+        "memory".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let total = match total_memory_kb() {
+            Some(total) => total / 1024 / 1024,
+            None => return CheckResult::warn(format!("Could not read {}", MEMINFO), "".to_string()),
+        };
+
+        if total < MIN_GB {
+            CheckResult::fail(format!("{} GB of RAM, cardano-node needs at least {} GB", total, MIN_GB), "Add more memory to the server".to_string())
+        } else if total < RECOMMENDED_GB {
+            CheckResult::warn(format!("{} GB of RAM, {} GB are recommended", total, RECOMMENDED_GB), "Add more memory or swap to the server".to_string())
+        } else {
+            CheckResult::pass(format!("{} GB of RAM", total))
+        }
+    }
+}
+
+fn total_memory_kb() -> Option<u64> {
+    let meminfo = fs::read_to_string(MEMINFO).ok()?;
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
@@ -0,0 +1,57 @@
+use crate::doctor::check::Check;
+use crate::doctor::check_impl::clock_check::ClockCheck;
+use crate::doctor::check_impl::dependencies_check::DependenciesCheck;
+use crate::doctor::check_impl::disk_check::DiskCheck;
+use crate::doctor::check_impl::ghc_check::GhcCheck;
+use crate::doctor::check_impl::libraries_check::LibrariesCheck;
+use crate::doctor::check_impl::memory_check::MemoryCheck;
+use crate::doctor::check_impl::port_check::PortCheck;
+use crate::doctor::check_impl::service_manager_check::ServiceManagerCheck;
+use crate::doctor::check_impl::workspace_check::WorkspaceCheck;
+
+pub mod systemd_check;
+pub mod workspace_check;
+pub mod ghc_check;
+pub mod libraries_check;
+pub mod dependencies_check;
+pub mod disk_check;
+pub mod memory_check;
+pub mod clock_check;
+pub mod port_check;
+pub mod nix_check;
+pub mod ghcup_check;
+pub mod service_manager_check;
+
+/// All the checks run by [cvm doctor]
+pub fn doctor_checks() -> Vec<Box<dyn Check>> {
+    vec![
+        Box::new(ServiceManagerCheck {}),
+        Box::new(WorkspaceCheck {}),
+        Box::new(DependenciesCheck {}),
+        Box::new(GhcCheck { required: false }),
+        Box::new(LibrariesCheck { required: false }),
+        Box::new(DiskCheck {}),
+        Box::new(MemoryCheck {}),
+        Box::new(ClockCheck {}),
+        Box::new(PortCheck::default()),
+    ]
+}
+
+/// Checks that must pass before [cvm init]
+pub fn init_checks() -> Vec<Box<dyn Check>> {
+    vec![
+        Box::new(ServiceManagerCheck {}),
+        Box::new(WorkspaceCheck {}),
+        Box::new(DiskCheck {}),
+    ]
+}
+
+/// Checks that must pass before [cvm install], plus the ones of the build backend when it is compiled
+pub fn install_checks(mut build_checks: Vec<Box<dyn Check>>) -> Vec<Box<dyn Check>> {
+    let mut checks: Vec<Box<dyn Check>> = vec![
+        Box::new(WorkspaceCheck {}),
+        Box::new(DiskCheck {}),
+    ];
+    checks.append(&mut build_checks);
+    checks
+}
@@ -0,0 +1,33 @@
+#![allow(dead_code, unused_variables)]
+
+use std::process::Command;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::Term;
+
+pub struct NixCheck {
+    /// nix is only needed to build with --backend nix
+    pub required: bool,
+}
+
+impl Check for NixCheck {
+
+    fn name(self: &Self) -> String {
+        "nix".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let hint = "Install nix from https://nixos.org/download or build with the cabal backend".to_string();
+
+        match Command::new("nix").arg("--version").output() {
+            Ok(output) if output.status.success() => {
+                CheckResult::pass(format!("{} is installed", String::from_utf8_lossy(&output.stdout).trim()))
+            }
+            _ => CheckResult::fail("nix was not found in the PATH".to_string(), hint)
+        }
+    }
+
+    fn is_required(self: &Self) -> bool {
+        self.required
+    }
+}
@@ -0,0 +1,37 @@
+#![allow(dead_code, unused_variables)]
+
+use std::net::TcpListener;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::Term;
+use crate::config::settings_config::get_settings;
+use crate::utils::folders::Folder;
+
+pub const NODE_PORT: u16 = 6000;
+
+pub struct PortCheck {
+    pub port: u16,
+}
+
+/// The port of the [service] table in the settings file
+impl Default for PortCheck {
+    fn default() -> Self {
+        let port = Folder::get_cvm_home().and_then(|cvm_home| get_settings(&cvm_home)).map(|settings| settings.service.port).unwrap_or(NODE_PORT);
+        PortCheck { port }
+    }
+}
+
+impl Check for PortCheck {
+
+    fn name(self: &Self) -> String {
+        "port".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        match TcpListener::bind(("0.0.0.0", self.port)) {
+            Ok(_) => CheckResult::pass(format!("The port {} is free", self.port)),
+            Err(_) => CheckResult::warn(format!("The port {} is in use", self.port),
+                                        format!("If cardano-node is not running, find the process with [sudo ss -ltnp 'sport = :{}']", self.port)),
+        }
+    }
+}
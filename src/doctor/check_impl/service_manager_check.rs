@@ -0,0 +1,33 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::doctor::check_impl::systemd_check::SystemdCheck;
+use crate::service_manager::{CVM, get_service_manager, SYSTEMD};
+use crate::Term;
+
+/// The service manager that runs the node, set in the settings file or detected
+pub struct ServiceManagerCheck {}
+
+impl Check for ServiceManagerCheck {
+
+    fn name(self: &Self) -> String {
+        "service manager".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let service_manager = match get_service_manager() {
+            Ok(service_manager) => service_manager,
+            Err(error) => return CheckResult::fail(error.data().message.clone(),
+                                                   "Fix service_manager in the settings file or remove it to detect the service manager".to_string()),
+        };
+
+        match service_manager.name().as_str() {
+            SYSTEMD => SystemdCheck {}.run(config, term),
+            CVM => CheckResult::pass("The node runs with the cvm supervisor, start it with [cvm run]".to_string()),
+            name if service_manager.is_available() => CheckResult::pass(format!("{} is running", name)),
+            name => CheckResult::fail(format!("{} is not running on this host", name),
+                                      "Change service_manager in the settings file or remove it to detect the service manager".to_string()),
+        }
+    }
+}
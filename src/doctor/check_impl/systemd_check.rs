@@ -0,0 +1,60 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use std::process::Command;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::Term;
+use crate::service_manager::service_manager_impl::systemd::LINGER_FOLDER;
+use crate::utils::folders::Folder;
+use crate::utils::user::get_current_user;
+
+const SYSTEMD_RUNTIME: &str = "/run/systemd/system";
+
+pub struct SystemdCheck {}
+
+impl Check for SystemdCheck {
+
+    fn name(self: &Self) -> String {
+        "systemd".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        if Command::new("systemctl").arg("--version").output().is_err() {
+            return CheckResult::fail("systemctl was not found".to_string(),
+                                     "cvm runs the node as a systemd service, install systemd".to_string());
+        }
+
+        if !Path::new(SYSTEMD_RUNTIME).exists() {
+            return CheckResult::fail("The system was not booted with systemd".to_string(),
+                                     "Run cvm on a host managed by systemd".to_string());
+        }
+
+        if Folder::is_rootless() {
+            return check_user_manager();
+        }
+
+        CheckResult::pass("systemd is running".to_string())
+    }
+}
+
+/// In rootless mode the node runs under the user manager, which has to be reachable
+/// and kept alive with lingering after the session is closed
+fn check_user_manager() -> CheckResult {
+    let state = Command::new("systemctl").args(["--user", "is-system-running"]).output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+
+    if state.is_empty() || state == "offline" {
+        return CheckResult::fail("The systemd user manager is not reachable".to_string(),
+                                 "Log in with a session of your user or enable lingering with [loginctl enable-linger $USER]".to_string());
+    }
+
+    let linger = get_current_user().map(|user| Path::new(LINGER_FOLDER).join(user).exists()).unwrap_or(false);
+    if !linger {
+        return CheckResult::warn("Lingering is not enabled, the node stops when your last session is closed".to_string(),
+                                 "Run [loginctl enable-linger $USER] or [cvm use] again".to_string());
+    }
+
+    CheckResult::pass(format!("systemd user manager is {}", state))
+}
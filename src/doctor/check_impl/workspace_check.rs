@@ -0,0 +1,39 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::{Check, CheckResult};
+use crate::env::Env;
+use crate::task::task::Task;
+use crate::task::task_impl::commons::permission_task::{PermissionAction, PermissionTask};
+use crate::Term;
+use crate::utils::folders::Folder;
+
+pub struct WorkspaceCheck {}
+
+impl Check for WorkspaceCheck {
+
+    fn name(self: &Self) -> String {
+        "workspace".to_string()
+    }
+
+    fn run(self: &Self, config: &RemoteConfig, term: &mut Term) -> CheckResult {
+        let workspace = Folder::get_workspaces_dir();
+        let root_folder = Folder::get_path(Folder::ROOT, config);
+
+        if !Path::new(&workspace).exists() {
+            return CheckResult::fail(format!("The workspace folder {} does not exist", workspace),
+                                     format!("Create it with [sudo mkdir -p {}] or choose another one with --workspace", workspace));
+        }
+
+        // once initialized the project folder is the one that must be writable
+        let path = if Path::new(&root_folder).exists() { root_folder } else { workspace };
+
+        let task = PermissionTask { input_data: PermissionAction::CheckWrite(vec![path.clone()]) };
+        match task.run(&mut Env::Empty(), config, term) {
+            Ok(_) => CheckResult::pass(format!("{} is writable", path)),
+            Err(error) => CheckResult::fail(error.data().message.clone(),
+                                            format!("Give your user write access with [sudo chown -R $USER {}]", path)),
+        }
+    }
+}
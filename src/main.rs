@@ -10,14 +10,26 @@ use subcommands::subcommands_impl::install::Install;
 use subcommands::subcommands_impl::list::List;
 use subcommands::subcommands_impl::start::Start;
 use subcommands::subcommands_impl::stop::Stop;
-use crate::subcommands::commands_config::CommandsConfig;
+use crate::subcommands::commands_config::Args;
 use crate::subcommands_impl::clean::Clean;
 use crate::subcommands_impl::config::Config;
+use crate::subcommands_impl::db::Db;
+use crate::subcommands_impl::doctor::Doctor;
+use crate::subcommands_impl::toolchain::Toolchain;
 use crate::subcommands_impl::r#use::Use;
 use crate::subcommands_impl::remove::Remove;
+use crate::subcommands_impl::run::Run;
+use crate::subcommands_impl::service::Service;
+use crate::subcommands_impl::logs::Logs;
+use crate::subcommands_impl::upgrade::Upgrade;
+use crate::subcommands_impl::topology::Topology;
+use crate::subcommands_impl::node_config::NodeConfig;
+use crate::subcommands_impl::state::State;
 use crate::subcommands_impl::update::Update;
 use crate::task::task::Success;
 use crate::term::term::Term;
+use crate::utils::folders::{CVM_HOME_ENV, CVM_ROOTLESS_ENV, CVM_WORKSPACE_ENV, Folder, GHCUP_PREFIX_ENV};
+use crate::utils::lock::lock_cvm_home;
 use crate::utils::url_build::url_build;
 use crate::utils::version_utils::get_last_cvm_version;
 
@@ -29,15 +41,35 @@ mod utils;
 mod task_manager;
 mod message;
 mod term;
+mod doctor;
+mod package_manager;
+mod service_manager;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 fn main() {
+    let args = subcommands::commands_config::command_config();
+
+    set_path_args(&args);
+    if let Err(error) = Folder::resolve_paths() {
+        error.print();
+        return;
+    }
+
+    // held until the end of main, [cvm run] and [cvm logs -f] can live for days so they can't block the other commands
+    let lock = if matches!(args.subcommand_name(), Some("run") | Some("logs")) { Ok(None) } else { lock_cvm_home() };
+    let _lock = match lock {
+        Ok(lock) => lock,
+        Err(error) => {
+            error.print();
+            return;
+        }
+    };
+
     if check_and_apply_update(VERSION.to_string()){
         return;
     }
 
-    let args = subcommands::commands_config::command_config();
     let result = match args.subcommand() {
         Some(("init", matches)) => {
             Init::start(matches)
@@ -69,6 +101,36 @@ fn main() {
         Some(("config", matches)) => {
             Config::start(matches)
         }
+        Some(("state", matches)) => {
+            State::start(matches)
+        }
+        Some(("db", matches)) => {
+            Db::start(matches)
+        }
+        Some(("doctor", matches)) => {
+            Doctor::start(matches)
+        }
+        Some(("toolchain", matches)) => {
+            Toolchain::start(matches)
+        }
+        Some(("run", matches)) => {
+            Run::start(matches)
+        }
+        Some(("service", matches)) => {
+            Service::start(matches)
+        }
+        Some(("logs", matches)) => {
+            Logs::start(matches)
+        }
+        Some(("upgrade", matches)) => {
+            Upgrade::start(matches)
+        }
+        Some(("topology", matches)) => {
+            Topology::start(matches)
+        }
+        Some(("node-config", matches)) => {
+            NodeConfig::start(matches)
+        }
         _ => { error_not_found() }
     };
 
@@ -82,6 +144,24 @@ fn main() {
     }
 }
 
+/// The path flags are exported as their environment variables,
+/// so Folder resolves them in one place and they survive a sudo escalation
+fn set_path_args(args: &ArgMatches) {
+    let path_args = vec![
+        (Args::WORKSPACE, CVM_WORKSPACE_ENV),
+        (Args::CVMHOME, CVM_HOME_ENV),
+        (Args::GHCUPPREFIX, GHCUP_PREFIX_ENV),
+    ];
+    for (arg, env_key) in path_args {
+        if let Some(value) = args.get_one::<String>(arg._to_string()) {
+            std::env::set_var(env_key, value);
+        }
+    }
+    if args.contains_id(Args::ROOTLESS._to_string()) {
+        std::env::set_var(CVM_ROOTLESS_ENV, "1");
+    }
+}
+
 fn error_not_found() -> Result<Success, Message> {
     return Err(Message::CommandNotFound(MessageData {
         message: "Command not found".to_string(),
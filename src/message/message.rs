@@ -56,6 +56,15 @@ pub enum Message {
     UpdateConfigFile(MessageData),
     ProjectNotInit(MessageData),
     VersionExist(MessageData),
+    ConfigSignature(MessageData),
+    CheckFailed(MessageData),
+    DbBackup(MessageData),
+    Toolchain(MessageData),
+    ServiceManager(MessageData),
+    Logs(MessageData),
+    Upgrade(MessageData),
+    Topology(MessageData),
+    EditFile(MessageData),
 
     //IO Errors
     FileNotFound(MessageData),
@@ -69,6 +78,8 @@ pub enum Message {
     RemoveFolder(MessageData),
     Copy(MessageData),
     UserNotFound(MessageData),
+    LockFile(MessageData),
+    StateFile(MessageData),
 
     //Permission
     PermissionDenied(MessageData),
@@ -134,6 +145,17 @@ impl Message {
             Message::VersionExist(this) => { &this }
             Message::UserNotFound(this) => { &this }
             Message::NewUpdate(this) => { &this }
+            Message::ConfigSignature(this) => { &this }
+            Message::CheckFailed(this) => { &this }
+            Message::DbBackup(this) => { &this }
+            Message::Toolchain(this) => { &this }
+            Message::ServiceManager(this) => { &this }
+            Message::Logs(this) => { &this }
+            Message::Upgrade(this) => { &this }
+            Message::Topology(this) => { &this }
+            Message::EditFile(this) => { &this }
+            Message::LockFile(this) => { &this }
+            Message::StateFile(this) => { &this }
         }
     }
 
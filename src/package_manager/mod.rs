@@ -0,0 +1,148 @@
+#![allow(dead_code, unused_variables)]
+
+extern crate rs_release;
+
+use std::collections::HashSet;
+use std::process::Command;
+use os_info::Type;
+use crate::config::remote_config::Dependencies;
+use crate::message::message::{Message, MessageData};
+use crate::package_manager::package_manager_impl::apk::Apk;
+use crate::package_manager::package_manager_impl::apt::Apt;
+use crate::package_manager::package_manager_impl::brew::Brew;
+use crate::package_manager::package_manager_impl::dnf::Dnf;
+use crate::package_manager::package_manager_impl::pacman::Pacman;
+use crate::package_manager::package_manager_impl::zypper::Zypper;
+use crate::task::task_impl::commons::run_command_task::RunCommandInputData;
+use crate::task::task_type::TaskType;
+use crate::utils::command_utils::has_command;
+
+pub mod package_manager_impl;
+
+pub trait PackageManager {
+
+    fn name(self: &Self) -> String;
+
+    /// Ask the package database if the package is installed, without changing anything
+    fn is_installed(self: &Self, package: &String) -> bool;
+
+    /// Command that installs the packages without asking for confirmation
+    fn install_command(self: &Self, packages: &[String]) -> RunCommandInputData;
+
+    /// Packages of the list that are not installed
+    fn missing_packages(self: &Self, packages: &[String]) -> Vec<String> {
+        packages.iter().filter(|package| !self.is_installed(package)).cloned().collect()
+    }
+}
+
+/// Distribution family of the running system, it selects the dependency list and the package manager
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum OsFamily {
+    Macos,
+    Ubuntu,
+    Debian,
+    Fedora,
+    CentOS,
+    Rhel,
+    OpenSuse,
+    Arch,
+    Alpine,
+}
+
+pub fn get_os_family() -> Option<OsFamily> {
+    match os_info::get().os_type() {
+        Type::Macos => Some(OsFamily::Macos),
+        Type::Ubuntu | Type::Mint | Type::Pop => Some(OsFamily::Ubuntu),
+        Type::Debian | Type::Raspbian => Some(OsFamily::Debian),
+        Type::Fedora => Some(OsFamily::Fedora),
+        Type::CentOS => Some(OsFamily::CentOS),
+        Type::Redhat | Type::RedHatEnterprise | Type::OracleLinux | Type::Amazon => Some(OsFamily::Rhel),
+        Type::openSUSE | Type::SUSE => Some(OsFamily::OpenSuse),
+        Type::Arch | Type::Manjaro | Type::EndeavourOS => Some(OsFamily::Arch),
+        Type::Alpine => Some(OsFamily::Alpine),
+        // os_info doesn't know Rocky and Alma, they are rhel rebuilds
+        _ => match get_os_release_value("ID").as_str() {
+            "rocky" | "almalinux" => Some(OsFamily::Rhel),
+            _ => None,
+        }
+    }
+}
+
+pub fn get_package_manager() -> Result<Box<dyn PackageManager>, Message> {
+    match get_os_family() {
+        Some(OsFamily::Macos) => Ok(Box::new(Brew {})),
+        Some(OsFamily::Ubuntu) | Some(OsFamily::Debian) => Ok(Box::new(Apt {})),
+        // centos and rhel 7 only have yum
+        Some(OsFamily::Fedora) | Some(OsFamily::CentOS) | Some(OsFamily::Rhel) => Ok(Box::new(Dnf { yum: !has_command("dnf") })),
+        Some(OsFamily::OpenSuse) => Ok(Box::new(Zypper {})),
+        Some(OsFamily::Arch) => Ok(Box::new(Pacman {})),
+        Some(OsFamily::Alpine) => Ok(Box::new(Apk {})),
+        None => Err(unsupported_os()),
+    }
+}
+
+pub fn get_dependencies_from_os(dependencies: &Dependencies) -> Result<Vec<String>, Message> {
+    let major_version = get_os_release_value("VERSION_ID").split('.').next().unwrap_or_default().to_string();
+
+    let mut packages = match get_os_family() {
+        Some(OsFamily::Macos) => dependencies.macos.clone(),
+        Some(OsFamily::Ubuntu) => dependencies.ubuntu.clone(),
+        Some(OsFamily::Debian) => dependencies.debian.clone(),
+        Some(OsFamily::Fedora) => dependencies.fedora.clone(),
+        Some(OsFamily::CentOS) => {
+            let mut packages = dependencies.centos.clone();
+            match major_version.as_str() {
+                "7" => packages.append(&mut dependencies.centos_7.clone()),
+                "8" => packages.append(&mut dependencies.centos_8.clone()),
+                _ => {}
+            }
+            packages
+        }
+        Some(OsFamily::Rhel) => {
+            let mut packages = dependencies.rhel.clone();
+            match major_version.as_str() {
+                "7" => packages.append(&mut dependencies.rhel_7.clone()),
+                "8" => packages.append(&mut dependencies.rhel_8.clone()),
+                _ => {}
+            }
+            packages
+        }
+        Some(OsFamily::OpenSuse) => dependencies.opensuse.clone(),
+        Some(OsFamily::Arch) => dependencies.arch.clone(),
+        Some(OsFamily::Alpine) => dependencies.alpine.clone(),
+        None => return Err(unsupported_os()),
+    };
+
+    // the generic and the release lists can repeat a package anywhere, the first one is kept
+    let mut seen = HashSet::new();
+    packages.retain(|package| seen.insert(package.clone()));
+    Ok(packages)
+}
+
+fn unsupported_os() -> Message {
+    Message::GettingDependences(MessageData {
+        message: format!("{} is not supported, install the dependencies manually", os_info::get().os_type()),
+        task: TaskType::InstallDependences,
+        ..Default::default()
+    })
+}
+
+fn get_os_release_value(key: &str) -> String {
+    match rs_release::get_os_release() {
+        Err(_) => "".to_string(),
+        Ok(os_release) => os_release.get(key).cloned().unwrap_or_default().trim_matches('"').to_string(),
+    }
+}
+
+/// Run a query command and only look at the exit code
+pub fn command_succeeds(command: &str, args: Vec<&str>) -> bool {
+    Command::new(command).args(args).output().map(|output| output.status.success()).unwrap_or(false)
+}
+
+/// Lines printed by a query command, empty when it fails
+pub fn command_lines(command: &str, args: Vec<&str>) -> Vec<String> {
+    match Command::new(command).args(args).output() {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).lines().map(|line| line.trim().to_string()).filter(|line| !line.is_empty()).collect(),
+        _ => vec![],
+    }
+}
@@ -0,0 +1,24 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::package_manager::{command_succeeds, PackageManager};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData};
+
+/// Alpine
+pub struct Apk {}
+
+impl PackageManager for Apk {
+
+    fn name(self: &Self) -> String {
+        "apk".to_string()
+    }
+
+    fn is_installed(self: &Self, package: &String) -> bool {
+        command_succeeds("apk", vec!["info", "-e", package])
+    }
+
+    fn install_command(self: &Self, packages: &[String]) -> RunCommandInputData {
+        let mut args: Vec<String> = vec!["apk".to_string(), "add".to_string()];
+        args.extend_from_slice(packages);
+        RunCommandInputData { command: Cmd::Sudo.as_string(), args, ..Default::default() }
+    }
+}
@@ -0,0 +1,29 @@
+#![allow(dead_code, unused_variables)]
+
+use std::process::Command;
+use crate::package_manager::PackageManager;
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData};
+
+/// Debian, Ubuntu and derivatives
+pub struct Apt {}
+
+impl PackageManager for Apt {
+
+    fn name(self: &Self) -> String {
+        "apt".to_string()
+    }
+
+    fn is_installed(self: &Self, package: &String) -> bool {
+        // removed packages keep an entry with the config-files status
+        match Command::new("dpkg-query").args(["-W", "-f=${Status}", package]).output() {
+            Ok(output) => String::from_utf8_lossy(&output.stdout).contains("install ok installed"),
+            Err(_) => false,
+        }
+    }
+
+    fn install_command(self: &Self, packages: &[String]) -> RunCommandInputData {
+        let mut args: Vec<String> = vec![Cmd::AptGet.as_string(), Cmd::Install.as_string(), "-y".to_string()];
+        args.extend_from_slice(packages);
+        RunCommandInputData { command: Cmd::Sudo.as_string(), args, ..Default::default() }
+    }
+}
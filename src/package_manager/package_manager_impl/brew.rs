@@ -0,0 +1,25 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::package_manager::{command_succeeds, PackageManager};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData};
+
+/// macOS
+pub struct Brew {}
+
+impl PackageManager for Brew {
+
+    fn name(self: &Self) -> String {
+        "brew".to_string()
+    }
+
+    fn is_installed(self: &Self, package: &String) -> bool {
+        command_succeeds("brew", vec!["list", "--versions", package])
+    }
+
+    fn install_command(self: &Self, packages: &[String]) -> RunCommandInputData {
+        let mut args: Vec<String> = vec![Cmd::Install.as_string()];
+        args.extend_from_slice(packages);
+        // brew refuses to run as root
+        RunCommandInputData { command: Cmd::Brew.as_string(), args, ..Default::default() }
+    }
+}
@@ -0,0 +1,28 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::package_manager::{command_succeeds, PackageManager};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData};
+
+/// Fedora, CentOS, RHEL and its rebuilds
+pub struct Dnf {
+    /// Use yum, the only one available in the 7 releases
+    pub yum: bool,
+}
+
+impl PackageManager for Dnf {
+
+    fn name(self: &Self) -> String {
+        if self.yum { Cmd::Yum.as_string() } else { "dnf".to_string() }
+    }
+
+    /// Some dependencies are capabilities like pkgconf-pkg-config instead of package names
+    fn is_installed(self: &Self, package: &String) -> bool {
+        command_succeeds("rpm", vec!["-q", "--whatprovides", package])
+    }
+
+    fn install_command(self: &Self, packages: &[String]) -> RunCommandInputData {
+        let mut args: Vec<String> = vec![self.name(), Cmd::Install.as_string(), "-y".to_string(), "--skip-broken".to_string()];
+        args.extend_from_slice(packages);
+        RunCommandInputData { command: Cmd::Sudo.as_string(), args, ..Default::default() }
+    }
+}
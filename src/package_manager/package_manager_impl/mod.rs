@@ -0,0 +1,6 @@
+pub mod apt;
+pub mod dnf;
+pub mod zypper;
+pub mod pacman;
+pub mod apk;
+pub mod brew;
@@ -0,0 +1,30 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::package_manager::{command_lines, command_succeeds, PackageManager};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData};
+
+/// Arch and derivatives
+pub struct Pacman {}
+
+impl PackageManager for Pacman {
+
+    fn name(self: &Self) -> String {
+        "pacman".to_string()
+    }
+
+    /// A group like base-devel is not a package for pacman -Q, every package of the group has to be installed
+    fn is_installed(self: &Self, package: &String) -> bool {
+        if command_succeeds("pacman", vec!["-Q", package]) {
+            return true;
+        }
+        let group = command_lines("pacman", vec!["-Sgq", package]);
+        let installed = command_lines("pacman", vec!["-Qgq", package]);
+        !group.is_empty() && group.iter().all(|member| installed.contains(member))
+    }
+
+    fn install_command(self: &Self, packages: &[String]) -> RunCommandInputData {
+        let mut args: Vec<String> = vec!["pacman".to_string(), "-S".to_string(), "--needed".to_string(), "--noconfirm".to_string()];
+        args.extend_from_slice(packages);
+        RunCommandInputData { command: Cmd::Sudo.as_string(), args, ..Default::default() }
+    }
+}
@@ -0,0 +1,24 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::package_manager::{command_succeeds, PackageManager};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData};
+
+/// openSUSE and SUSE
+pub struct Zypper {}
+
+impl PackageManager for Zypper {
+
+    fn name(self: &Self) -> String {
+        "zypper".to_string()
+    }
+
+    fn is_installed(self: &Self, package: &String) -> bool {
+        command_succeeds("rpm", vec!["-q", "--whatprovides", package])
+    }
+
+    fn install_command(self: &Self, packages: &[String]) -> RunCommandInputData {
+        let mut args: Vec<String> = vec!["zypper".to_string(), "--non-interactive".to_string(), Cmd::Install.as_string()];
+        args.extend_from_slice(packages);
+        RunCommandInputData { command: Cmd::Sudo.as_string(), args, ..Default::default() }
+    }
+}
@@ -0,0 +1,159 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use serde::Serialize;
+use tinytemplate::{format_unescaped, TinyTemplate};
+use crate::config::remote_config::{RemoteConfig, Services};
+use crate::config::settings_config::get_settings;
+use crate::message::message::{Message, MessageData};
+use crate::service_manager::service_manager_impl::openrc::OpenRc;
+use crate::service_manager::service_manager_impl::runit::Runit;
+use crate::service_manager::service_manager_impl::supervisor::Supervisor;
+use crate::service_manager::service_manager_impl::supervisord::Supervisord;
+use crate::service_manager::service_manager_impl::systemd::Systemd;
+use crate::task::task::{Success, Task};
+use crate::task::task_type::TaskType;
+use crate::utils::folders::Folder;
+use crate::utils::user::{escalate_if_needed, get_current_user};
+
+pub mod service_manager_impl;
+
+pub const SYSTEMD: &str = "systemd";
+pub const OPENRC: &str = "openrc";
+pub const RUNIT: &str = "runit";
+pub const SUPERVISORD: &str = "supervisord";
+/// Built-in supervisor, [cvm run] in the foreground
+pub const CVM: &str = "cvm";
+
+pub trait ServiceManager {
+
+    fn name(self: &Self) -> String;
+
+    /// True when this service manager is running on the host
+    fn is_available(self: &Self) -> bool;
+
+    /// Writing the service definitions and controlling the services needs root
+    fn needs_root(self: &Self) -> bool {
+        true
+    }
+
+    /// Write the definition of the service, the service is not started
+    fn deploy(self: &Self, service: &Services, config: &RemoteConfig) -> Result<Success, Message>;
+
+    /// File with the local changes to the service that survives a new deploy, when the manager supports it
+    fn drop_in_file(self: &Self, service: &Services) -> Result<Option<String>, Message> {
+        Ok(None)
+    }
+
+    /// Tasks that make the service manager load the deployed definitions
+    fn reload_tasks(self: &Self, config: &RemoteConfig) -> Result<Vec<Box<dyn Task>>, Message> {
+        Ok(vec![])
+    }
+
+    /// Start the service, or restart it when it is already running
+    fn start(self: &Self, service: &String) -> Result<Success, Message>;
+
+    fn stop(self: &Self, service: &String) -> Result<Success, Message>;
+
+    fn is_active(self: &Self, service: &String) -> bool;
+}
+
+/// Service manager set in the settings file, or the one running on the host.
+/// Without any of them the node runs under the built-in supervisor
+pub fn get_service_manager() -> Result<Box<dyn ServiceManager>, Message> {
+    let settings = get_settings(&Folder::get_cvm_home()?)?;
+    if !settings.service_manager.is_empty() {
+        return get_service_manager_by_name(&settings.service_manager);
+    }
+
+    let detected: Vec<Box<dyn ServiceManager>> = vec![
+        Box::new(Systemd {}),
+        Box::new(OpenRc {}),
+        Box::new(Runit {}),
+        Box::new(Supervisord {}),
+    ];
+    Ok(detected.into_iter().find(|manager| manager.is_available()).unwrap_or(Box::new(Supervisor {})))
+}
+
+pub fn get_service_manager_by_name(name: &String) -> Result<Box<dyn ServiceManager>, Message> {
+    match name.to_lowercase().as_str() {
+        SYSTEMD => Ok(Box::new(Systemd {})),
+        OPENRC => Ok(Box::new(OpenRc {})),
+        RUNIT => Ok(Box::new(Runit {})),
+        SUPERVISORD => Ok(Box::new(Supervisord {})),
+        CVM => Ok(Box::new(Supervisor {})),
+        _ => Err(Message::ServiceManager(MessageData {
+            message: format!("Unknown service manager {}, use one of: {}, {}, {}, {}, {}", name, SYSTEMD, OPENRC, RUNIT, SUPERVISORD, CVM),
+            task: TaskType::ServicesManager,
+            ..Default::default()
+        }))
+    }
+}
+
+/// Ask for root only when the service manager needs it
+pub fn escalate_for_services() -> Result<(), Message> {
+    if get_service_manager()?.needs_root() {
+        escalate_if_needed()?;
+    }
+    Ok(())
+}
+
+/// True when any of the cardano services is running
+pub fn services_active(config: &RemoteConfig) -> bool {
+    match get_service_manager() {
+        Ok(manager) => config.services_item.iter().any(|service| manager.is_active(&service.name)),
+        Err(_) => false,
+    }
+}
+
+/// Render one of the service templates embedded in cvm with the user, workspace folders and service name
+pub fn render_service_template(template_text: &str, service: &Services, config: &RemoteConfig) -> Result<String, Message> {
+    let mut template = TinyTemplate::new();
+    template.set_default_formatter(&format_unescaped);
+    template.add_template(&service.name, template_text)?;
+    let context = ServiceContext {
+        user: get_current_user()?,
+        root: Folder::get_path(Folder::ROOT, config),
+        logs: Folder::get_path(Folder::LOGS, config),
+        name: service.name.to_string(),
+    };
+    Ok(template.render(&service.name, &context)?)
+}
+
+/// Write the file only when the content changed, with the given permissions
+pub fn write_service_file(path: &String, content: &String, mode: u32) -> Result<Success, Message> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::read_to_string(path).map(|current| current != *content).unwrap_or(true) {
+        fs::write(path, content)?;
+    }
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(Success {})
+}
+
+/// Run a service manager command, a failed exit code is returned as an error with its output
+pub fn run_service_command(command: &str, args: Vec<String>) -> Result<Success, Message> {
+    let output = Command::new(command).args(&args).output()?;
+    if !output.status.success() {
+        return Err(Message::ServiceManager(MessageData {
+            message: format!("{} {} failed", command, args.join(" ")),
+            task: TaskType::ServicesManager,
+            stack: vec![String::from_utf8_lossy(&output.stderr).trim().to_string()],
+            ..Default::default()
+        }));
+    }
+    Ok(Success {})
+}
+
+#[derive(Serialize)]
+struct ServiceContext {
+    user: String,
+    root: String,
+    logs: String,
+    name: String,
+}
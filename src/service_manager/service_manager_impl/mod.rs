@@ -0,0 +1,5 @@
+pub mod systemd;
+pub mod openrc;
+pub mod runit;
+pub mod supervisord;
+pub mod supervisor;
@@ -0,0 +1,53 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use crate::{Success, url_build};
+use crate::config::remote_config::{RemoteConfig, Services};
+use crate::message::message::Message;
+use crate::service_manager::{OPENRC, render_service_template, run_service_command, ServiceManager, write_service_file};
+use crate::utils::command_utils::has_command;
+use crate::task::task::Task;
+use crate::task::task_impl::commons::run_command_task::{RunCommandInputData, RunCommandTask};
+
+const INIT_FOLDER: &str = "/etc/init.d";
+const OPENRC_RUNTIME: &str = "/run/openrc";
+const SCRIPT_TEMPLATE: &str = include_str!("../../../config/openrc/cnode");
+
+/// Alpine, Gentoo and Devuan, the services are init scripts run by openrc-run
+pub struct OpenRc {}
+
+impl ServiceManager for OpenRc {
+
+    fn name(self: &Self) -> String {
+        OPENRC.to_string()
+    }
+
+    fn is_available(self: &Self) -> bool {
+        Path::new(OPENRC_RUNTIME).exists() && has_command("rc-service")
+    }
+
+    fn deploy(self: &Self, service: &Services, config: &RemoteConfig) -> Result<Success, Message> {
+        let script = render_service_template(SCRIPT_TEMPLATE, service, config)?;
+        write_service_file(&url_build(vec![&INIT_FOLDER.to_string(), &service.name], false), &script, 0o755)
+    }
+
+    fn reload_tasks(self: &Self, config: &RemoteConfig) -> Result<Vec<Box<dyn Task>>, Message> {
+        // start the node in the default runlevel on boot, like the systemd unit
+        Ok(config.services_item.iter().map(|service| {
+            let input_data = RunCommandInputData { command: "rc-update".to_string(), args: vec!["add".to_string(), service.name.to_string(), "default".to_string()], current_dir: "".to_string() };
+            Box::new(RunCommandTask { input_data, command_description: format!("Adding {} to the default runlevel", service.name) }) as Box<dyn Task>
+        }).collect())
+    }
+
+    fn start(self: &Self, service: &String) -> Result<Success, Message> {
+        run_service_command("rc-service", vec![service.to_string(), "restart".to_string()])
+    }
+
+    fn stop(self: &Self, service: &String) -> Result<Success, Message> {
+        run_service_command("rc-service", vec![service.to_string(), "stop".to_string()])
+    }
+
+    fn is_active(self: &Self, service: &String) -> bool {
+        run_service_command("rc-service", vec![service.to_string(), "status".to_string()]).is_ok()
+    }
+}
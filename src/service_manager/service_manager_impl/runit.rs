@@ -0,0 +1,71 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::Path;
+use std::process::Command;
+use crate::{Success, url_build};
+use crate::config::remote_config::{RemoteConfig, Services};
+use crate::message::message::Message;
+use crate::service_manager::{render_service_template, run_service_command, RUNIT, ServiceManager, write_service_file};
+use crate::utils::command_utils::has_command;
+
+const SV_FOLDER: &str = "/etc/sv";
+/// Folders scanned by runsvdir, /var/service in Void and /etc/service in the others
+const SERVICE_FOLDERS: [&str; 2] = ["/var/service", "/etc/service"];
+const RUNIT_RUNTIME: &str = "/run/runit";
+const RUN_TEMPLATE: &str = include_str!("../../../config/runit/run");
+const LOG_TEMPLATE: &str = include_str!("../../../config/runit/log");
+
+/// Void, Artix and containers with runit, every service is a folder with a run script
+/// linked in the folder scanned by runsvdir
+pub struct Runit {}
+
+impl ServiceManager for Runit {
+
+    fn name(self: &Self) -> String {
+        RUNIT.to_string()
+    }
+
+    fn is_available(self: &Self) -> bool {
+        has_command("sv") && (Path::new(RUNIT_RUNTIME).exists() || SERVICE_FOLDERS.iter().any(|folder| Path::new(folder).exists()))
+    }
+
+    fn deploy(self: &Self, service: &Services, config: &RemoteConfig) -> Result<Success, Message> {
+        let service_folder = url_build(vec![&SV_FOLDER.to_string(), &service.name], false);
+
+        write_service_file(&url_build(vec![&service_folder, &"run".to_string()], false), &render_service_template(RUN_TEMPLATE, service, config)?, 0o755)?;
+        write_service_file(&url_build(vec![&service_folder, &"log".to_string(), &"run".to_string()], false), &render_service_template(LOG_TEMPLATE, service, config)?, 0o755)?;
+
+        // runsv starts a linked service right away, the down file keeps it stopped until [cvm start]
+        let down_file = url_build(vec![&service_folder, &"down".to_string()], false);
+        if !Path::new(&down_file).exists() {
+            fs::write(&down_file, "")?;
+        }
+
+        let link = url_build(vec![&get_service_folder(), &service.name], false);
+        if fs::symlink_metadata(&link).is_err() {
+            symlink(&service_folder, &link)?;
+        }
+        Ok(Success {})
+    }
+
+    fn start(self: &Self, service: &String) -> Result<Success, Message> {
+        run_service_command("sv", vec!["restart".to_string(), service.to_string()])
+    }
+
+    fn stop(self: &Self, service: &String) -> Result<Success, Message> {
+        run_service_command("sv", vec!["stop".to_string(), service.to_string()])
+    }
+
+    fn is_active(self: &Self, service: &String) -> bool {
+        match Command::new("sv").args(["status", service]).output() {
+            Ok(output) => String::from_utf8_lossy(&output.stdout).starts_with("run:"),
+            Err(_) => false,
+        }
+    }
+}
+
+fn get_service_folder() -> String {
+    SERVICE_FOLDERS.iter().find(|folder| Path::new(folder).exists()).unwrap_or(&SERVICE_FOLDERS[1]).to_string()
+}
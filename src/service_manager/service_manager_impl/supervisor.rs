@@ -0,0 +1,247 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::process::{Child, Command};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use signal_hook::consts::{SIGHUP, SIGINT, SIGQUIT, SIGTERM, SIGUSR1, SIGUSR2};
+use signal_hook::iterator::Signals;
+use crate::{Success, url_build};
+use crate::config::remote_config::{RemoteConfig, Services};
+use crate::config::settings_config::{get_settings, ServiceSettings};
+use crate::message::message::{Message, MessageData};
+use crate::service_manager::{CVM, ServiceManager};
+use crate::task::task_type::TaskType;
+use crate::utils::folders::Folder;
+
+const RUN_FOLDER: &str = "run";
+const SUPERVISOR_PID: &str = "supervisor";
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Built-in supervisor for hosts and containers without a service manager.
+/// [cvm run] keeps the node in the foreground, the other commands talk to it with signals:
+/// SIGUSR1 (re)starts the services, SIGUSR2 stops them and SIGTERM or SIGINT stops them and exits
+pub struct Supervisor {}
+
+impl ServiceManager for Supervisor {
+
+    fn name(self: &Self) -> String {
+        CVM.to_string()
+    }
+
+    fn is_available(self: &Self) -> bool {
+        true
+    }
+
+    fn needs_root(self: &Self) -> bool {
+        false
+    }
+
+    fn deploy(self: &Self, service: &Services, config: &RemoteConfig) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn start(self: &Self, service: &String) -> Result<Success, Message> {
+        match running_supervisor() {
+            Some(pid) => send_signal(pid, SIGUSR1),
+            None => Err(Message::ServiceManager(MessageData {
+                message: "The cvm supervisor is not running, start the node with [cvm run]".to_string(),
+                task: TaskType::ServicesManager,
+                ..Default::default()
+            }))
+        }
+    }
+
+    fn stop(self: &Self, service: &String) -> Result<Success, Message> {
+        match running_supervisor() {
+            Some(pid) => {
+                send_signal(pid, SIGUSR2)?;
+                wait_until(stop_timeout(), || !self.is_active(service));
+                Ok(Success {})
+            }
+            None => Ok(Success {}),
+        }
+    }
+
+    fn is_active(self: &Self, service: &String) -> bool {
+        running_supervisor().is_some() && read_pid(&pid_file(service)).map(is_alive).unwrap_or(false)
+    }
+}
+
+/// Run the services in the foreground until the supervisor receives SIGTERM or SIGINT,
+/// a service that exits is started again after the restart delay
+pub fn run_supervisor(config: &RemoteConfig, restart_delay: Duration) -> Result<Success, Message> {
+    if let Some(pid) = running_supervisor() {
+        return Err(Message::ServiceManager(MessageData {
+            message: format!("The cvm supervisor is already running with pid {}", pid),
+            task: TaskType::ServicesManager,
+            ..Default::default()
+        }));
+    }
+
+    fs::create_dir_all(run_folder()?)?;
+    fs::write(pid_file(&SUPERVISOR_PID.to_string()), std::process::id().to_string())?;
+
+    let (sender, receiver) = mpsc::channel();
+    let mut signals = Signals::new([SIGHUP, SIGINT, SIGQUIT, SIGTERM, SIGUSR1, SIGUSR2])?;
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            if sender.send(signal).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut services: Vec<SupervisedService> = config.services_item.iter()
+        .map(|service| SupervisedService { name: service.name.to_string(), child: None, restart_at: Some(Instant::now()) })
+        .collect();
+
+    let result = supervise(&mut services, config, restart_delay, receiver);
+
+    for service in services.iter_mut() {
+        service.stop();
+    }
+    let _ = fs::remove_file(pid_file(&SUPERVISOR_PID.to_string()));
+    result
+}
+
+fn supervise(services: &mut [SupervisedService], config: &RemoteConfig, restart_delay: Duration, receiver: mpsc::Receiver<i32>) -> Result<Success, Message> {
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(SIGUSR1) => {
+                for service in services.iter_mut() {
+                    service.stop();
+                    service.restart_at = Some(Instant::now());
+                }
+            }
+            Ok(SIGUSR2) => {
+                for service in services.iter_mut() {
+                    service.stop();
+                }
+            }
+            Ok(SIGHUP) => {
+                for service in services.iter() {
+                    service.forward(SIGHUP);
+                }
+            }
+            Ok(_) => return Ok(Success {}),
+            Err(_) => {}
+        }
+
+        for service in services.iter_mut() {
+            if service.exited() {
+                println!("{} exited, starting it again in {} seconds", service.name, restart_delay.as_secs());
+                service.restart_at = Some(Instant::now() + restart_delay);
+            }
+            if service.restart_at.map(|time| time <= Instant::now()).unwrap_or(false) {
+                service.start(config)?;
+            }
+        }
+    }
+}
+
+struct SupervisedService {
+    name: String,
+    child: Option<Child>,
+    /// Set while the service should be running and it is not
+    restart_at: Option<Instant>,
+}
+
+impl SupervisedService {
+
+    /// Same command as the systemd unit, through a login shell to get the user environment
+    fn start(&mut self, config: &RemoteConfig) -> Result<Success, Message> {
+        let scripts = Folder::get_path(Folder::SCRIPTS, config);
+        let script = url_build(vec![&scripts, &format!("{}.sh", self.name)], false);
+
+        let child = Command::new("/bin/bash").args(["-l", "-c", &format!("exec {}", script)]).current_dir(&scripts).spawn()?;
+        fs::write(pid_file(&self.name), child.id().to_string())?;
+        self.child = Some(child);
+        self.restart_at = None;
+        Ok(Success {})
+    }
+
+    fn exited(&mut self) -> bool {
+        let exited = match self.child.as_mut() {
+            Some(child) => child.try_wait().map(|status| status.is_some()).unwrap_or(false),
+            None => false,
+        };
+        if exited {
+            self.child = None;
+            let _ = fs::remove_file(pid_file(&self.name));
+        }
+        exited
+    }
+
+    /// SIGINT lets the node close the database, it is killed if it doesn't exit in time
+    fn stop(&mut self) {
+        self.restart_at = None;
+        if let Some(mut child) = self.child.take() {
+            let _ = send_signal(child.id() as i32, SIGINT);
+            if !wait_until(stop_timeout(), || child.try_wait().map(|status| status.is_some()).unwrap_or(true)) {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+        let _ = fs::remove_file(pid_file(&self.name));
+    }
+
+    fn forward(&self, signal: i32) {
+        if let Some(child) = &self.child {
+            let _ = send_signal(child.id() as i32, signal);
+        }
+    }
+}
+
+/// Time the node has to close the database after SIGINT before it is killed, like TimeoutStopSec in the unit
+fn stop_timeout() -> Duration {
+    let stop_timeout = Folder::get_cvm_home().and_then(|cvm_home| get_settings(&cvm_home)).map(|settings| settings.service.stop_timeout);
+    Duration::from_secs(stop_timeout.unwrap_or(ServiceSettings::default().stop_timeout))
+}
+
+fn run_folder() -> Result<String, Message> {
+    Ok(url_build(vec![&Folder::get_cvm_home()?, &RUN_FOLDER.to_string()], false))
+}
+
+fn pid_file(name: &String) -> String {
+    url_build(vec![&run_folder().unwrap_or_default(), &format!("{}.pid", name)], false)
+}
+
+fn read_pid(path: &String) -> Option<i32> {
+    fs::read_to_string(path).ok().and_then(|pid| pid.trim().parse::<i32>().ok())
+}
+
+/// Pid of the [cvm run] process, a pid file left by a killed supervisor is ignored
+fn running_supervisor() -> Option<i32> {
+    read_pid(&pid_file(&SUPERVISOR_PID.to_string())).filter(|pid| is_alive(*pid))
+}
+
+/// Signal 0 only checks the process, EPERM means it exists but belongs to another user
+fn is_alive(pid: i32) -> bool {
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+fn send_signal(pid: i32, signal: i32) -> Result<Success, Message> {
+    if unsafe { libc::kill(pid, signal) } != 0 {
+        return Err(Message::ServiceManager(MessageData {
+            message: format!("Could not send the signal {} to the process {}", signal, pid),
+            task: TaskType::ServicesManager,
+            stack: vec![std::io::Error::last_os_error().to_string()],
+            ..Default::default()
+        }));
+    }
+    Ok(Success {})
+}
+
+fn wait_until<F: FnMut() -> bool>(timeout: Duration, mut condition: F) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    condition()
+}
@@ -0,0 +1,61 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use std::process::Command;
+use crate::{Success, url_build};
+use crate::config::remote_config::{RemoteConfig, Services};
+use crate::message::message::Message;
+use crate::service_manager::{render_service_template, run_service_command, ServiceManager, SUPERVISORD, write_service_file};
+use crate::utils::command_utils::has_command;
+use crate::task::task::Task;
+use crate::task::task_impl::commons::run_command_task::{RunCommandInputData, RunCommandTask};
+
+/// Include folder of the debian package, the rhel package includes /etc/supervisord.d
+const CONF_FOLDERS: [(&str, &str); 2] = [("/etc/supervisor/conf.d", "conf"), ("/etc/supervisord.d", "ini")];
+const PROGRAM_TEMPLATE: &str = include_str!("../../../config/supervisord/cnode.conf");
+
+/// Mostly used in docker images, where the node is one more program of supervisord
+pub struct Supervisord {}
+
+impl ServiceManager for Supervisord {
+
+    fn name(self: &Self) -> String {
+        SUPERVISORD.to_string()
+    }
+
+    fn is_available(self: &Self) -> bool {
+        has_command("supervisorctl") && CONF_FOLDERS.iter().any(|(folder, _)| Path::new(folder).exists())
+    }
+
+    fn deploy(self: &Self, service: &Services, config: &RemoteConfig) -> Result<Success, Message> {
+        let (folder, extension) = CONF_FOLDERS.iter().find(|(folder, _)| Path::new(folder).exists()).unwrap_or(&CONF_FOLDERS[0]);
+        let program = render_service_template(PROGRAM_TEMPLATE, service, config)?;
+        write_service_file(&url_build(vec![&folder.to_string(), &format!("{}.{}", service.name, extension)], false), &program, 0o644)
+    }
+
+    fn reload_tasks(self: &Self, config: &RemoteConfig) -> Result<Vec<Box<dyn Task>>, Message> {
+        Ok(vec![
+            Box::new(RunCommandTask { input_data: build_supervisorctl_command("reread"), command_description: "Reading the supervisord programs".to_string() }),
+            Box::new(RunCommandTask { input_data: build_supervisorctl_command("update"), command_description: "Updating the supervisord programs".to_string() }),
+        ])
+    }
+
+    fn start(self: &Self, service: &String) -> Result<Success, Message> {
+        run_service_command("supervisorctl", vec!["restart".to_string(), service.to_string()])
+    }
+
+    fn stop(self: &Self, service: &String) -> Result<Success, Message> {
+        run_service_command("supervisorctl", vec!["stop".to_string(), service.to_string()])
+    }
+
+    fn is_active(self: &Self, service: &String) -> bool {
+        match Command::new("supervisorctl").args(["status", service]).output() {
+            Ok(output) => String::from_utf8_lossy(&output.stdout).contains("RUNNING"),
+            Err(_) => false,
+        }
+    }
+}
+
+fn build_supervisorctl_command(action: &str) -> RunCommandInputData {
+    RunCommandInputData { command: "supervisorctl".to_string(), args: vec![action.to_string()], current_dir: "".to_string() }
+}
@@ -0,0 +1,161 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::path::Path;
+use tinytemplate::{format_unescaped, TinyTemplate};
+use serde::Serialize;
+use crate::{Success, url_build};
+use crate::config::remote_config::{RemoteConfig, Services};
+use crate::config::settings_config::get_settings;
+use crate::message::message::Message;
+use crate::service_manager::{run_service_command, ServiceManager, SYSTEMD, write_service_file};
+use crate::task::task::Task;
+use crate::task::task_impl::commons::run_command_task::{build_command, Cmd, RunCommandInputData, RunCommandTask};
+use crate::utils::folders::Folder;
+use crate::utils::user::get_current_user;
+
+pub const LINGER_FOLDER: &str = "/var/lib/systemd/linger";
+const SYSTEMD_RUNTIME: &str = "/run/systemd/system";
+const DROP_IN_FILE: &str = "override.conf";
+const UNIT_TEMPLATE: &str = include_str!("../../../config/systemd/cnode.service.template");
+
+/// The node unit is rendered from the template embedded in cvm.
+/// In rootless mode it goes to ~/.config/systemd/user and lingering is enabled,
+/// so the user manager keeps the node running without an open session
+pub struct Systemd {}
+
+impl ServiceManager for Systemd {
+
+    fn name(self: &Self) -> String {
+        SYSTEMD.to_string()
+    }
+
+    fn is_available(self: &Self) -> bool {
+        Path::new(SYSTEMD_RUNTIME).exists()
+    }
+
+    fn needs_root(self: &Self) -> bool {
+        !Folder::is_rootless()
+    }
+
+    fn deploy(self: &Self, service: &Services, config: &RemoteConfig) -> Result<Success, Message> {
+        let unit = render_unit(service, config)?;
+        write_service_file(&url_build(vec![&Folder::get_systemd_dir()?, &service.file], false), &unit, 0o644)
+    }
+
+    fn drop_in_file(self: &Self, service: &Services) -> Result<Option<String>, Message> {
+        let drop_in_folder = format!("{}.d", service.file);
+        Ok(Some(url_build(vec![&Folder::get_systemd_dir()?, &drop_in_folder, &DROP_IN_FILE.to_string()], false)))
+    }
+
+    fn reload_tasks(self: &Self, config: &RemoteConfig) -> Result<Vec<Box<dyn Task>>, Message> {
+        let mut tasks: Vec<Box<dyn Task>> = vec![
+            Box::new(RunCommandTask { input_data: build_systemctl_command(vec![Cmd::DaemonReload.as_string()]), command_description: "Reset systemctl daemon".to_string() }),
+        ];
+
+        let user = get_current_user()?;
+        if Folder::is_rootless() && !Path::new(&url_build(vec![&LINGER_FOLDER.to_string(), &user], false)).exists() {
+            tasks.push(Box::new(RunCommandTask { input_data: build_enable_linger_command(&user), command_description: "Enable lingering for the user services".to_string() }));
+        }
+        Ok(tasks)
+    }
+
+    fn start(self: &Self, service: &String) -> Result<Success, Message> {
+        run_systemctl(vec!["restart".to_string(), service.to_string()])
+    }
+
+    fn stop(self: &Self, service: &String) -> Result<Success, Message> {
+        run_systemctl(vec!["stop".to_string(), service.to_string()])
+    }
+
+    fn is_active(self: &Self, service: &String) -> bool {
+        let input_data = build_systemctl_command(vec!["is-active".to_string(), "--quiet".to_string(), service.to_string()]);
+        build_command(&input_data).output().map(|output| output.status.success()).unwrap_or(false)
+    }
+}
+
+/// systemctl for the system units, or for the units of the user manager in rootless mode
+pub fn build_systemctl_command(args: Vec<String>) -> RunCommandInputData {
+    let mut systemctl_args = vec![];
+    if Folder::is_rootless() {
+        systemctl_args.push(Cmd::User.as_string());
+    }
+    systemctl_args.extend(args);
+    RunCommandInputData { command: Cmd::Systemctl.as_string(), args: systemctl_args, current_dir: "".to_string() }
+}
+
+fn run_systemctl(args: Vec<String>) -> Result<Success, Message> {
+    let input_data = build_systemctl_command(args);
+    run_service_command(&input_data.command, input_data.args)
+}
+
+/// The unit is embedded in cvm and filled with the [service] table of the settings file,
+/// local changes live in drop-ins so a new deploy never overwrites them
+fn render_unit(service: &Services, config: &RemoteConfig) -> Result<String, Message> {
+    let settings = get_settings(&Folder::get_cvm_home()?)?.service;
+    let context = UnitContext {
+        user: get_current_user()?,
+        rootless: Folder::is_rootless(),
+        scripts: Folder::get_path(Folder::SCRIPTS, config),
+        port: settings.port,
+        rts_options: settings.rts_options,
+        memory_max: settings.memory_max,
+        cpu_quota: settings.cpu_quota,
+        restart: settings.restart,
+        restart_sec: settings.restart_sec,
+        limit_nofile: settings.limit_nofile,
+        stop_timeout: settings.stop_timeout,
+    };
+
+    let mut template = TinyTemplate::new();
+    template.set_default_formatter(&format_unescaped);
+    template.add_template(&service.name, UNIT_TEMPLATE)?;
+    Ok(template.render(&service.name, &context)?)
+}
+
+/// Write one of the unit templates embedded in cvm, like the timers of the backups and upgrades
+pub fn write_unit<C: Serialize>(name: &String, template_text: &str, context: &C) -> Result<Success, Message> {
+    let mut template = TinyTemplate::new();
+    template.set_default_formatter(&format_unescaped);
+    template.add_template(name, template_text)?;
+    let text = template.render(name, context)?;
+    fs::write(unit_path(name)?, text)?;
+    Ok(Success {})
+}
+
+/// Command line for ExecStart, every argument is quoted so spaces, `%` specifiers and `$` variables stay literal
+pub fn exec_command_line(args: &[String]) -> String {
+    args.iter().map(|arg| {
+        let escaped = arg.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%").replace('$', "$$");
+        format!("\"{}\"", escaped)
+    }).collect::<Vec<String>>().join(" ")
+}
+
+/// Value of an Environment= assignment, quoted by the template
+pub fn environment_value(value: &String) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%")
+}
+
+pub fn unit_path(name: &String) -> Result<String, Message> {
+    Ok(url_build(vec![&Folder::get_systemd_dir()?, name], false))
+}
+
+fn build_enable_linger_command(user: &String) -> RunCommandInputData {
+    let args = vec![Cmd::EnableLinger.as_string(), user.to_string()];
+    RunCommandInputData { command: Cmd::Loginctl.as_string(), args, current_dir: "".to_string() }
+}
+
+#[derive(Serialize)]
+struct UnitContext {
+    user: String,
+    rootless: bool,
+    scripts: String,
+    port: u16,
+    rts_options: String,
+    memory_max: String,
+    cpu_quota: String,
+    restart: String,
+    restart_sec: u64,
+    limit_nofile: u64,
+    stop_timeout: u64,
+}
@@ -12,7 +12,14 @@ pub fn command_config() -> ArgMatches {
                 .long("help"),
             Arg::new("version")
                 .short('v')
-                .long("version")
+                .long("version"),
+            get_arg_path(Args::WORKSPACE, "Folder where the cardano workspace is created [env: CVM_WORKSPACE]"),
+            get_arg_path(Args::CVMHOME, "Folder where cvm keeps its state and temporal files [env: CVM_HOME]"),
+            get_arg_path(Args::GHCUPPREFIX, "Base folder of the ghcup installation [env: GHCUP_INSTALL_BASE_PREFIX]"),
+            Arg::new(Args::ROOTLESS._to_string())
+                .long(Args::ROOTLESS._to_string())
+                .global(true)
+                .help("Run without root, with the workspace in the user home and user level systemd units [env: CVM_ROOTLESS]"),
         ])
         .subcommand(Command::new(CommandsConfig::INIT.to_string())
             .about("Start the environment to be able to build a Cardano node")
@@ -24,14 +31,51 @@ pub fn command_config() -> ArgMatches {
             .about("Build the cardano node and make it available for use")
             .arg(get_arg_version())
             .arg(get_arg_build_install())
+            .arg(Arg::new(Args::REF._to_string())
+                .long(Args::REF._to_string())
+                .takes_value(true)
+                .requires(Args::BUILD._to_string())
+                .help("Branch, tag or commit to build instead of the version tag"))
+            .arg(Arg::new(Args::REPO._to_string())
+                .long(Args::REPO._to_string())
+                .takes_value(true)
+                .requires(Args::BUILD._to_string())
+                .help("Git url of a fork or path of a local checkout to build"))
+            .arg(Arg::new(Args::CLEAN._to_string())
+                .long(Args::CLEAN._to_string())
+                .requires(Args::BUILD._to_string())
+                .help("Clone the repository again and rebuild from scratch"))
+            .arg(Arg::new(Args::SHALLOW._to_string())
+                .long(Args::SHALLOW._to_string())
+                .requires(Args::BUILD._to_string())
+                .help("Download only the commit to build, without the repository history"))
+            .arg(Arg::new(Args::BACKEND._to_string())
+                .long(Args::BACKEND._to_string())
+                .takes_value(true)
+                .requires(Args::BUILD._to_string())
+                .help("Toolchain used to build cardano-node: cabal (default) or nix"))
+            .arg(Arg::new(Args::NAME._to_string())
+                .long(Args::NAME._to_string())
+                .takes_value(true)
+                .help("Name of the installed version, required with --ref or --repo and only used with them"))
+            .arg(get_arg_component())
         )
         .subcommand(Command::new(CommandsConfig::USE.to_string())
             .about("Change the current cardano-node to the new version")
             .arg(get_arg_version())
+            .arg(Arg::new(Args::ACCEPTREPLAY._to_string())
+                .long(Args::ACCEPTREPLAY._to_string())
+                .help("Switch even if the new version replays the ledger or can not read the current db"))
+            .arg(Arg::new(Args::NORESTART._to_string())
+                .long(Args::NORESTART._to_string())
+                .help("Leave the node stopped after the switch"))
+            .arg(get_arg_force_leader())
+            .arg(get_arg_component())
         )
         .subcommand(Command::new(CommandsConfig::REMOVE.to_string())
             .about("Remove cardano-node binaries from {version}")
             .arg(get_arg_version())
+            .arg(get_arg_component())
         )
         .subcommand(Command::new(CommandsConfig::CONFIG.to_string())
             .subcommand(Command::new(CommandsConfig::UPDATE.to_string())
@@ -39,16 +83,252 @@ pub fn command_config() -> ArgMatches {
                 .arg(get_arg_force_config())
             )
         )
+        .subcommand(Command::new(CommandsConfig::STATE.to_string())
+            .about("Inspect and move the cvm state")
+            .subcommand(Command::new(CommandsConfig::SHOW.to_string())
+                .about("Print the current state")
+            )
+            .subcommand(Command::new(CommandsConfig::EXPORT.to_string())
+                .about("Save the current state to a file")
+                .arg(get_arg_file())
+            )
+            .subcommand(Command::new(CommandsConfig::IMPORT.to_string())
+                .about("Replace the current state with the one saved in a file, older layouts are upgraded")
+                .arg(get_arg_file())
+            )
+        )
+        .subcommand(Command::new(CommandsConfig::DB.to_string())
+            .about("Protect the chain database")
+            .subcommand(Command::new(CommandsConfig::BACKUP.to_string())
+                .about("Stop the node and save a verified copy of the chain database")
+                .arg(get_arg_incremental())
+                .arg(get_arg_keep())
+                .arg(get_arg_dest())
+                .arg(get_arg_ignore_leader_schedule())
+            )
+            .subcommand(Command::new(CommandsConfig::RESTORE.to_string())
+                .about("Stop the node and replace the chain database with a backup, by default the latest one")
+                .arg(Arg::new(Args::BACKUP._to_string()).takes_value(true))
+                .arg(get_arg_dest())
+                .arg(get_arg_ignore_leader_schedule())
+            )
+            .subcommand(Command::new(CommandsConfig::BOOTSTRAP.to_string())
+                .about("Fill the chain database from a snapshot archive instead of syncing from genesis")
+                .arg(Arg::new(Args::FROM._to_string())
+                    .long(Args::FROM._to_string())
+                    .takes_value(true)
+                    .required(true)
+                    .help("Url or local path of the snapshot, a tar or tar.gz archive of the db folder"))
+                .arg(Arg::new(Args::DIGEST._to_string())
+                    .long(Args::DIGEST._to_string())
+                    .takes_value(true)
+                    .help("sha256 of the snapshot, by default it is read from <from>.sha256"))
+                .arg(Arg::new(Args::FORCE._to_string())
+                    .short('f')
+                    .long(Args::FORCE._to_string())
+                    .help("Replace the chain database even if it is not empty, the leader window is still checked unless --ignore-leader-schedule is given"))
+                .arg(get_arg_ignore_leader_schedule())
+            )
+            .subcommand(Command::new(CommandsConfig::LS.to_string())
+                .about("List the chain database backups")
+                .arg(get_arg_dest())
+            )
+            .subcommand(Command::new(CommandsConfig::SCHEDULE.to_string())
+                .about("Run the backup periodically with a systemd timer")
+                .arg(Arg::new(Args::CALENDAR._to_string())
+                    .long(Args::CALENDAR._to_string())
+                    .takes_value(true)
+                    .help("systemd calendar expression, by default daily"))
+                .arg(Arg::new(Args::DISABLE._to_string())
+                    .long(Args::DISABLE._to_string())
+                    .help("Remove the scheduled backup"))
+                .arg(get_arg_incremental())
+                .arg(get_arg_keep())
+                .arg(get_arg_dest())
+            )
+        )
+        .subcommand(Command::new(CommandsConfig::TOOLCHAIN.to_string())
+            .about("Manage the ghc and cabal versions used to build each cardano-node version")
+            .subcommand(Command::new(CommandsConfig::LS.to_string())
+                .about("List the toolchain of every cardano-node version and the installed ones")
+            )
+            .subcommand(Command::new(CommandsConfig::INSTALL.to_string())
+                .about("Install the ghc and cabal needed to build {version}")
+                .arg(get_arg_version())
+            )
+            .subcommand(Command::new(CommandsConfig::REMOVE.to_string())
+                .about("Remove the ghc and cabal used to build {version}")
+                .arg(get_arg_version())
+            )
+        )
+        .subcommand(Command::new(CommandsConfig::DOCTOR.to_string())
+            .about("Check that the server is ready to build and run a Cardano node"))
         .subcommand(Command::new(CommandsConfig::CLEAN.to_string())
             .about("Remove temporary and build files"))
         .subcommand(Command::new(CommandsConfig::LS.to_string())
-            .about("List all installed versions of cardano node"))
+            .about("List all installed versions of cardano node")
+            .arg(get_arg_component()))
         .subcommand(Command::new(CommandsConfig::UPDATE.to_string())
             .about("Update to the new version of CVM if it exists"))
         .subcommand(Command::new(CommandsConfig::START.to_string())
-            .about("Start cardano node services"))
+            .about("Start cardano node services, a running node is restarted")
+            .arg(get_arg_force_leader()))
         .subcommand(Command::new(CommandsConfig::STOP.to_string())
-            .about("Stop cardano node services"))
+            .about("Stop cardano node services")
+            .arg(get_arg_force_leader()))
+        .subcommand(Command::new(CommandsConfig::SERVICE.to_string())
+            .about("Customize the cardano node service")
+            .subcommand(Command::new(CommandsConfig::EDIT.to_string())
+                .about("Edit the drop-in with the local changes to the service, kept when cvm deploys the service again")
+                .arg(Arg::new(Args::NAME._to_string())
+                    .long(Args::NAME._to_string())
+                    .takes_value(true)
+                    .help("Service to edit, by default cnode"))
+            )
+        )
+        .subcommand(Command::new(CommandsConfig::RUN.to_string())
+            .about("Run cardano node in the foreground with the built-in supervisor, for containers and hosts without a service manager")
+            .arg(Arg::new(Args::RESTARTDELAY._to_string())
+                .long(Args::RESTARTDELAY._to_string())
+                .takes_value(true)
+                .help("Seconds to wait before starting the node again after it exits, by default 60")))
+        .subcommand(Command::new(CommandsConfig::UPGRADE.to_string())
+            .about("Install a new cardano-node version and switch to it now, at a given time or with the upgrade policy of the settings")
+            .arg(get_arg_version())
+            .arg(get_arg_build_install())
+            .arg(Arg::new(Args::AT._to_string())
+                .long(Args::AT._to_string())
+                .takes_value(true)
+                .help("Install now and switch at this time: next-epoch, a UTC date like 2022-08-01 02:00 or a systemd calendar"))
+            .arg(Arg::new(Args::CANCEL._to_string())
+                .long(Args::CANCEL._to_string())
+                .help("Cancel the upgrade scheduled with --at"))
+            .arg(Arg::new(Args::POLICY._to_string())
+                .long(Args::POLICY._to_string())
+                .help("Upgrade to the newest release allowed by the upgrade policy of the settings"))
+            .arg(Arg::new(Args::DOWNLOADONLY._to_string())
+                .long(Args::DOWNLOADONLY._to_string())
+                .help("Install the new version without switching to it"))
+            .arg(Arg::new(Args::AUTO._to_string())
+                .long(Args::AUTO._to_string())
+                .help("Run the upgrade policy of the settings with systemd timers"))
+            .arg(Arg::new(Args::DISABLE._to_string())
+                .long(Args::DISABLE._to_string())
+                .requires(Args::AUTO._to_string())
+                .help("Remove the timers of the upgrade policy"))
+            .arg(Arg::new(Args::ACCEPTREPLAY._to_string())
+                .long(Args::ACCEPTREPLAY._to_string())
+                .help("Switch even if the new version replays the ledger or can not read the current db"))
+            .arg(get_arg_force_leader())
+        )
+        .subcommand(Command::new(CommandsConfig::TOPOLOGY.to_string())
+            .about("Edit the topology of the node, legacy or P2P, the running node reloads a P2P topology")
+            .subcommand(Command::new(CommandsConfig::LS.to_string())
+                .about("List the peers of the topology")
+            )
+            .subcommand(Command::new(CommandsConfig::ADD.to_string())
+                .about("Add a peer, in P2P topologies as a new group of the local roots")
+                .arg(get_arg_address())
+                .arg(Arg::new(Args::PORT._to_string())
+                    .required(true)
+                    .takes_value(true)
+                    .help("Port of the peer"))
+                .arg(Arg::new(Args::VALENCY._to_string())
+                    .long(Args::VALENCY._to_string())
+                    .takes_value(true)
+                    .help("Connections kept to the peer, by default 1"))
+                .arg(Arg::new(Args::PUBLIC._to_string())
+                    .long(Args::PUBLIC._to_string())
+                    .help("Add the peer to the public roots of a P2P topology"))
+            )
+            .subcommand(Command::new(CommandsConfig::REMOVE.to_string())
+                .about("Remove the peers with the address")
+                .arg(get_arg_address())
+                .arg(get_arg_port())
+            )
+            .subcommand(Command::new(CommandsConfig::SETVALENCY.to_string())
+                .about("Change the valency of a legacy producer or of the local roots group of the peer")
+                .arg(get_arg_address())
+                .arg(Arg::new(Args::VALENCY._to_string())
+                    .required(true)
+                    .takes_value(true)
+                    .help("Connections kept to the peer or group"))
+                .arg(get_arg_port())
+            )
+            .subcommand(Command::new(CommandsConfig::VALIDATE.to_string())
+                .about("Check the topology of the workspace or another file")
+                .arg(Arg::new(Args::FILE._to_string())
+                    .takes_value(true)
+                    .help("Topology to check, by default the one of the workspace"))
+            )
+            .subcommand(Command::new(CommandsConfig::CONVERT.to_string())
+                .about("Convert the topology between the legacy and the P2P formats")
+                .arg(Arg::new(Args::TO._to_string())
+                    .long(Args::TO._to_string())
+                    .takes_value(true)
+                    .required(true)
+                    .possible_values(["p2p", "legacy"])
+                    .help("Format of the converted topology"))
+            )
+        )
+        .subcommand(Command::new(CommandsConfig::NODECONFIG.to_string())
+            .about("Read and change the cardano-node config.json")
+            .subcommand(Command::new(CommandsConfig::GET.to_string())
+                .about("Print a value, or the whole config without a key")
+                .arg(Arg::new(Args::KEY._to_string())
+                    .takes_value(true)
+                    .help("Key like TraceMempool, a dotted path like setupScribes.0.scName or a json pointer"))
+            )
+            .subcommand(Command::new(CommandsConfig::SET.to_string())
+                .about("Change a value, the previous config is kept as config.json.bak")
+                .arg(Arg::new(Args::KEY._to_string())
+                    .required(true)
+                    .takes_value(true)
+                    .help("Key like TraceMempool, a dotted path like setupScribes.0.scName or a json pointer"))
+                .arg(Arg::new(Args::VALUE._to_string())
+                    .required(true)
+                    .takes_value(true)
+                    .help("New value, read as json like true, 12 or [\"a\"] and as a string otherwise"))
+                .arg(Arg::new(Args::CREATE._to_string())
+                    .long(Args::CREATE._to_string())
+                    .help("Add the key if it is not in the config"))
+                .arg(Arg::new(Args::FORCE._to_string())
+                    .short('f')
+                    .long(Args::FORCE._to_string())
+                    .help("Change the type of the value"))
+            )
+        )
+        .subcommand(Command::new(CommandsConfig::LOGS.to_string())
+            .about("Show the cardano node logs from journald or the node log files")
+            .arg(Arg::new(Args::FOLLOW._to_string())
+                .short('f')
+                .long(Args::FOLLOW._to_string())
+                .help("Keep printing the new records"))
+            .arg(Arg::new(Args::SINCE._to_string())
+                .long(Args::SINCE._to_string())
+                .takes_value(true)
+                .help("Show the records newer than a duration (30m, 2h, 1d) or a UTC date (2022-08-01 12:00)"))
+            .arg(Arg::new(Args::LEVEL._to_string())
+                .long(Args::LEVEL._to_string())
+                .takes_value(true)
+                .help("Minimum severity: debug, info, notice, warn, error, critical, alert or emergency"))
+            .arg(Arg::new(Args::NAMESPACE._to_string())
+                .long(Args::NAMESPACE._to_string())
+                .takes_value(true)
+                .help("Show only the records whose namespace contains this text, like ChainDB"))
+            .arg(Arg::new(Args::GREP._to_string())
+                .long(Args::GREP._to_string())
+                .takes_value(true)
+                .help("Show only the lines that match this regular expression"))
+            .arg(Arg::new(Args::LINES._to_string())
+                .short('n')
+                .long(Args::LINES._to_string())
+                .takes_value(true)
+                .help("Number of records shown before following, by default 50, 0 shows all"))
+            .arg(Arg::new(Args::SOURCE._to_string())
+                .long(Args::SOURCE._to_string())
+                .takes_value(true)
+                .help("Read from journal or file, by default the node log files if there are any")))
         .get_matches();
 }
 
@@ -60,6 +340,10 @@ fn get_arg_network() -> Arg<'static> {
     Arg::new(Args::NETWORK._to_string()).default_value(Args::LATEST._to_string()).takes_value(true)
 }
 
+fn get_arg_file() -> Arg<'static> {
+    Arg::new(Args::FILE._to_string()).required(true).takes_value(true)
+}
+
 fn get_arg_build_install() -> Arg<'static> {
     Arg::new(Args::BUILD._to_string())
         .short('b')
@@ -74,6 +358,42 @@ fn get_arg_build_init() -> Arg<'static> {
         .help("Prepare server for build cardano-node")
 }
 
+fn get_arg_path(arg: Args, help: &'static str) -> Arg<'static> {
+    Arg::new(arg._to_string())
+        .long(arg._to_string())
+        .takes_value(true)
+        .global(true)
+        .help(help)
+}
+
+fn get_arg_incremental() -> Arg<'static> {
+    Arg::new(Args::INCREMENTAL._to_string())
+        .short('i')
+        .long(Args::INCREMENTAL._to_string())
+        .help("Copy the database hard linking the files unchanged since the previous incremental backup")
+}
+
+fn get_arg_keep() -> Arg<'static> {
+    Arg::new(Args::KEEP._to_string())
+        .long(Args::KEEP._to_string())
+        .takes_value(true)
+        .help("Number of backups to keep, the oldest ones are removed")
+}
+
+fn get_arg_component() -> Arg<'static> {
+    Arg::new(Args::COMPONENT._to_string())
+        .long(Args::COMPONENT._to_string())
+        .takes_value(true)
+        .help("Component to manage instead of cardano-node, e.g. cncli, ogmios or kupo")
+}
+
+fn get_arg_dest() -> Arg<'static> {
+    Arg::new(Args::DEST._to_string())
+        .long(Args::DEST._to_string())
+        .takes_value(true)
+        .help("Folder with the backups")
+}
+
 fn get_arg_force_init() -> Arg<'static> {
     Arg::new(Args::FORCE._to_string())
         .short('f')
@@ -88,6 +408,33 @@ fn get_arg_force_config() -> Arg<'static> {
         .help("Force update all configuration files and scripts to their latest released version")
 }
 
+fn get_arg_address() -> Arg<'static> {
+    Arg::new(Args::ADDRESS._to_string())
+        .required(true)
+        .takes_value(true)
+        .help("Host name or ip of the peer")
+}
+
+fn get_arg_port() -> Arg<'static> {
+    Arg::new(Args::PORT._to_string())
+        .long(Args::PORT._to_string())
+        .takes_value(true)
+        .help("Only the peer with this port")
+}
+
+fn get_arg_force_leader() -> Arg<'static> {
+    Arg::new(Args::FORCE._to_string())
+        .short('f')
+        .long(Args::FORCE._to_string())
+        .help("Stop the block producer even if a block is scheduled in the leader window")
+}
+
+fn get_arg_ignore_leader_schedule() -> Arg<'static> {
+    Arg::new(Args::IGNORELEADERSCHEDULE._to_string())
+        .long(Args::IGNORELEADERSCHEDULE._to_string())
+        .help("Stop the block producer even if a block is scheduled in the leader window")
+}
+
 pub enum CommandsConfig {
     INIT,
     INSTALL,
@@ -99,6 +446,30 @@ pub enum CommandsConfig {
     STOP,
     CLEAN,
     CONFIG,
+    STATE,
+    SHOW,
+    EXPORT,
+    IMPORT,
+    DOCTOR,
+    TOOLCHAIN,
+    RUN,
+    SERVICE,
+    EDIT,
+    LOGS,
+    UPGRADE,
+    DB,
+    BACKUP,
+    RESTORE,
+    SCHEDULE,
+    BOOTSTRAP,
+    TOPOLOGY,
+    ADD,
+    SETVALENCY,
+    VALIDATE,
+    CONVERT,
+    NODECONFIG,
+    GET,
+    SET,
 }
 
 impl Display for CommandsConfig {
@@ -114,6 +485,30 @@ impl Display for CommandsConfig {
             CommandsConfig::STOP => write!(f, "stop"),
             CommandsConfig::CLEAN => write!(f, "clean"),
             CommandsConfig::CONFIG => write!(f, "config"),
+            CommandsConfig::STATE => write!(f, "state"),
+            CommandsConfig::SHOW => write!(f, "show"),
+            CommandsConfig::EXPORT => write!(f, "export"),
+            CommandsConfig::IMPORT => write!(f, "import"),
+            CommandsConfig::DOCTOR => write!(f, "doctor"),
+            CommandsConfig::TOOLCHAIN => write!(f, "toolchain"),
+            CommandsConfig::RUN => write!(f, "run"),
+            CommandsConfig::SERVICE => write!(f, "service"),
+            CommandsConfig::EDIT => write!(f, "edit"),
+            CommandsConfig::LOGS => write!(f, "logs"),
+            CommandsConfig::UPGRADE => write!(f, "upgrade"),
+            CommandsConfig::DB => write!(f, "db"),
+            CommandsConfig::BACKUP => write!(f, "backup"),
+            CommandsConfig::RESTORE => write!(f, "restore"),
+            CommandsConfig::SCHEDULE => write!(f, "schedule"),
+            CommandsConfig::BOOTSTRAP => write!(f, "bootstrap"),
+            CommandsConfig::TOPOLOGY => write!(f, "topology"),
+            CommandsConfig::ADD => write!(f, "add"),
+            CommandsConfig::SETVALENCY => write!(f, "set-valency"),
+            CommandsConfig::VALIDATE => write!(f, "validate"),
+            CommandsConfig::CONVERT => write!(f, "convert"),
+            CommandsConfig::NODECONFIG => write!(f, "node-config"),
+            CommandsConfig::GET => write!(f, "get"),
+            CommandsConfig::SET => write!(f, "set"),
         }
     }
 }
@@ -124,16 +519,104 @@ pub enum Args {
     LATEST,
     FORCE,
     BUILD,
+    WORKSPACE,
+    CVMHOME,
+    GHCUPPREFIX,
+    FILE,
+    INCREMENTAL,
+    KEEP,
+    DEST,
+    BACKUP,
+    CALENDAR,
+    DISABLE,
+    FROM,
+    DIGEST,
+    ACCEPTREPLAY,
+    REF,
+    REPO,
+    NAME,
+    CLEAN,
+    SHALLOW,
+    COMPONENT,
+    BACKEND,
+    ROOTLESS,
+    RESTARTDELAY,
+    FOLLOW,
+    SINCE,
+    LEVEL,
+    NAMESPACE,
+    GREP,
+    LINES,
+    SOURCE,
+    NORESTART,
+    AT,
+    CANCEL,
+    POLICY,
+    DOWNLOADONLY,
+    AUTO,
+    ADDRESS,
+    PORT,
+    VALENCY,
+    PUBLIC,
+    TO,
+    KEY,
+    VALUE,
+    CREATE,
+    IGNORELEADERSCHEDULE,
 }
 
 impl Args {
-    pub fn _to_string(&self) -> &str {
+    pub fn _to_string(&self) -> &'static str {
         match self {
             Args::NETWORK => { "network" }
             Args::VERSION => { "version" }
             Args::LATEST => { "latest" }
             Args::FORCE => { "force" }
             Args::BUILD => { "build" }
+            Args::WORKSPACE => { "workspace" }
+            Args::CVMHOME => { "cvm-home" }
+            Args::GHCUPPREFIX => { "ghcup-prefix" }
+            Args::FILE => { "file" }
+            Args::INCREMENTAL => { "incremental" }
+            Args::KEEP => { "keep" }
+            Args::DEST => { "dest" }
+            Args::BACKUP => { "backup" }
+            Args::CALENDAR => { "calendar" }
+            Args::DISABLE => { "disable" }
+            Args::FROM => { "from" }
+            Args::DIGEST => { "digest" }
+            Args::ACCEPTREPLAY => { "accept-replay" }
+            Args::REF => { "ref" }
+            Args::REPO => { "repo" }
+            Args::NAME => { "name" }
+            Args::CLEAN => { "clean" }
+            Args::SHALLOW => { "shallow" }
+            Args::COMPONENT => { "component" }
+            Args::BACKEND => { "backend" }
+            Args::ROOTLESS => { "rootless" }
+            Args::RESTARTDELAY => { "restart-delay" }
+            Args::FOLLOW => { "follow" }
+            Args::SINCE => { "since" }
+            Args::LEVEL => { "level" }
+            Args::NAMESPACE => { "namespace" }
+            Args::GREP => { "grep" }
+            Args::LINES => { "lines" }
+            Args::SOURCE => { "source" }
+            Args::NORESTART => { "no-restart" }
+            Args::AT => { "at" }
+            Args::CANCEL => { "cancel" }
+            Args::POLICY => { "policy" }
+            Args::DOWNLOADONLY => { "download-only" }
+            Args::AUTO => { "auto" }
+            Args::ADDRESS => { "address" }
+            Args::PORT => { "port" }
+            Args::VALENCY => { "valency" }
+            Args::PUBLIC => { "public" }
+            Args::TO => { "to" }
+            Args::KEY => { "key" }
+            Args::VALUE => { "value" }
+            Args::CREATE => { "create" }
+            Args::IGNORELEADERSCHEDULE => { "ignore-leader-schedule" }
         }
     }
 }
@@ -146,6 +629,50 @@ impl Display for Args {
             Args::LATEST => write!(f, "latest"),
             Args::FORCE => write!(f, "force"),
             Args::BUILD => write!(f, "build"),
+            Args::WORKSPACE => write!(f, "workspace"),
+            Args::CVMHOME => write!(f, "cvm-home"),
+            Args::GHCUPPREFIX => write!(f, "ghcup-prefix"),
+            Args::FILE => write!(f, "file"),
+            Args::INCREMENTAL => write!(f, "incremental"),
+            Args::KEEP => write!(f, "keep"),
+            Args::DEST => write!(f, "dest"),
+            Args::BACKUP => write!(f, "backup"),
+            Args::CALENDAR => write!(f, "calendar"),
+            Args::DISABLE => write!(f, "disable"),
+            Args::FROM => write!(f, "from"),
+            Args::DIGEST => write!(f, "digest"),
+            Args::ACCEPTREPLAY => write!(f, "accept-replay"),
+            Args::REF => write!(f, "ref"),
+            Args::REPO => write!(f, "repo"),
+            Args::NAME => write!(f, "name"),
+            Args::CLEAN => write!(f, "clean"),
+            Args::SHALLOW => write!(f, "shallow"),
+            Args::COMPONENT => write!(f, "component"),
+            Args::BACKEND => write!(f, "backend"),
+            Args::ROOTLESS => write!(f, "rootless"),
+            Args::RESTARTDELAY => write!(f, "restart-delay"),
+            Args::FOLLOW => write!(f, "follow"),
+            Args::SINCE => write!(f, "since"),
+            Args::LEVEL => write!(f, "level"),
+            Args::NAMESPACE => write!(f, "namespace"),
+            Args::GREP => write!(f, "grep"),
+            Args::LINES => write!(f, "lines"),
+            Args::SOURCE => write!(f, "source"),
+            Args::NORESTART => write!(f, "no-restart"),
+            Args::AT => write!(f, "at"),
+            Args::CANCEL => write!(f, "cancel"),
+            Args::POLICY => write!(f, "policy"),
+            Args::DOWNLOADONLY => write!(f, "download-only"),
+            Args::AUTO => write!(f, "auto"),
+            Args::ADDRESS => write!(f, "address"),
+            Args::PORT => write!(f, "port"),
+            Args::VALENCY => write!(f, "valency"),
+            Args::PUBLIC => write!(f, "public"),
+            Args::TO => write!(f, "to"),
+            Args::KEY => write!(f, "key"),
+            Args::VALUE => write!(f, "value"),
+            Args::CREATE => write!(f, "create"),
+            Args::IGNORELEADERSCHEDULE => write!(f, "ignore-leader-schedule"),
         }
     }
 }
\ No newline at end of file
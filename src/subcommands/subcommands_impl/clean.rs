@@ -17,9 +17,7 @@ impl CommandStrategy for Clean{
         let config = config::remote_config::get_remote_config()?;
         let mut term = Term { stdout: stdout() };
 
-        let home_dir = Folder::get_home_dir()?;
-        let mut ghcup_dir = String::from(home_dir);
-        ghcup_dir.push_str(format!("/{}", &config.init.ghcup_bin_path).as_str());
+        let ghcup_dir = Folder::get_ghcup_bin_dir(&config)?;
         let git_folder = Folder::get_path(Folder::GIT, &config);
 
 
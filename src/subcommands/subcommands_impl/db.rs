@@ -0,0 +1,203 @@
+#![allow(dead_code, unused_variables)]
+
+use std::io::stdout;
+use std::path::Path;
+use clap::ArgMatches;
+use crate::{CommandStrategy, config, Message, MessageData, Success, Term, url_build};
+use crate::config::remote_config::RemoteConfig;
+use crate::config::settings_config::get_settings;
+use crate::message::message::MessageKind;
+use crate::subcommands::commands_config::Args;
+use crate::task::task::Task;
+use crate::task::task_impl::db::backup_db_task::{BackupDbData, BackupDbTask, list_backups};
+use crate::task::task_impl::db::bootstrap_db_task::{BootstrapDbData, BootstrapDbTask};
+use crate::task::task_impl::db::manifest::{BackupMode, read_manifest};
+use crate::task::task_impl::db::restore_db_task::{RestoreDbData, RestoreDbTask};
+use crate::task::task_impl::db::schedule_backup_task::{ScheduleBackupAction, ScheduleBackupTask};
+use crate::service_manager::{escalate_for_services, services_active};
+use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesData, ServicesManagerTask};
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L1;
+use crate::utils::folders::Folder;
+use crate::utils::user::escalate_if_needed;
+
+const DEFAULT_CALENDAR: &str = "daily";
+
+pub struct Db {}
+
+impl CommandStrategy for Db {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+        let mut term = Term { stdout: stdout() };
+
+        match command.subcommand() {
+            Some(("backup", matches)) => {
+                backup(matches, &config, &mut term)
+            }
+            Some(("restore", matches)) => {
+                restore(matches, &config, &mut term)
+            }
+            Some(("bootstrap", matches)) => {
+                bootstrap(matches, &config, &mut term)
+            }
+            Some(("ls", matches)) => {
+                list(matches, &config)
+            }
+            Some(("schedule", matches)) => {
+                schedule(matches, &config, &mut term)
+            }
+            _ => { Ok(Success {}) }
+        }
+    }
+}
+
+fn backup(command: &ArgMatches, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let mode = if command.contains_id(Args::INCREMENTAL._to_string()) { BackupMode::INCREMENTAL } else { BackupMode::ARCHIVE };
+
+    let task = BackupDbTask {
+        input_data: BackupDbData {
+            db_folder: Folder::get_path(Folder::DB, config),
+            backup_dir: get_backup_dir(command, config)?,
+            mode,
+            keep: get_keep(command)?,
+        }
+    };
+    with_services_stopped(Box::new(task), !command.contains_id(Args::IGNORELEADERSCHEDULE._to_string()), config, term)
+}
+
+fn restore(command: &ArgMatches, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let backup_dir = get_backup_dir(command, config)?;
+
+    let name = match command.get_one::<String>(Args::BACKUP._to_string()) {
+        Some(name) => name.to_string(),
+        None => match list_backups(&backup_dir)?.pop() {
+            Some(name) => name,
+            None => return Err(db_error(format!("There are no backups in {}", backup_dir))),
+        }
+    };
+
+    let backup_folder = url_build(vec![&backup_dir, &name], false);
+    if !Path::new(&backup_folder).exists() {
+        return Err(db_error(format!("The backup {} does not exist, list them with [cvm db ls]", name)));
+    }
+
+    println!("Restoring backup {}", name);
+    let task = RestoreDbTask {
+        input_data: RestoreDbData {
+            backup_folder,
+            db_folder: Folder::get_path(Folder::DB, config),
+        }
+    };
+    with_services_stopped(Box::new(task), !command.contains_id(Args::IGNORELEADERSCHEDULE._to_string()), config, term)
+}
+
+fn bootstrap(command: &ArgMatches, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let task = BootstrapDbTask {
+        input_data: BootstrapDbData {
+            source: command.get_one::<String>(Args::FROM._to_string()).unwrap().to_string(),
+            digest: command.get_one::<String>(Args::DIGEST._to_string()).map(|digest| digest.to_string()).unwrap_or_default(),
+            db_folder: Folder::get_path(Folder::DB, config),
+            force: command.contains_id(Args::FORCE._to_string()),
+        }
+    };
+    with_services_stopped(Box::new(task), !command.contains_id(Args::IGNORELEADERSCHEDULE._to_string()), config, term)
+}
+
+fn list(command: &ArgMatches, config: &RemoteConfig) -> Result<Success, Message> {
+    let backup_dir = get_backup_dir(command, config)?;
+    let backups = list_backups(&backup_dir)?;
+
+    if backups.is_empty() {
+        println!("There are no backups in {}", backup_dir);
+    }
+
+    for name in backups {
+        match read_manifest(&url_build(vec![&backup_dir, &name], false)) {
+            Ok(manifest) => println!("{}  {:?}  node {}  {} files  {} MB", name, manifest.mode, manifest.node_version,
+                                     manifest.files.len(), manifest.total_size() / 1024 / 1024),
+            Err(_) => println!("{}  without manifest", name),
+        }
+    }
+    Ok(Success {})
+}
+
+fn schedule(command: &ArgMatches, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    escalate_if_needed()?;
+
+    let action = if command.contains_id(Args::DISABLE._to_string()) {
+        ScheduleBackupAction::Disable
+    } else {
+        let calendar = command.get_one::<String>(Args::CALENDAR._to_string()).map(|value| value.to_string()).unwrap_or(DEFAULT_CALENDAR.to_string());
+        let mut args = vec![];
+        if command.contains_id(Args::INCREMENTAL._to_string()) {
+            args.push(format!("--{}", Args::INCREMENTAL));
+        }
+        if let Some(keep) = command.get_one::<String>(Args::KEEP._to_string()) {
+            args.push(format!("--{}", Args::KEEP));
+            args.push(keep.to_string());
+        }
+        if let Some(dest) = command.get_one::<String>(Args::DEST._to_string()) {
+            args.push(format!("--{}", Args::DEST));
+            args.push(dest.to_string());
+        }
+        ScheduleBackupAction::Enable(calendar, args)
+    };
+
+    TaskManager::default().start(vec![Box::new(ScheduleBackupTask { input_data: action })], config, term, L1)
+}
+
+/// The chain database can only be copied consistently with the node stopped,
+/// the services are started again after the task even if it failed.
+/// With guard a block producer is only stopped outside the blocks of its leader schedule
+fn with_services_stopped(task: Box<dyn Task>, guard: bool, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let running = services_active(config);
+
+    if running {
+        escalate_for_services()?;
+        TaskManager::default().start(vec![Box::new(ServicesManagerTask { input_data: ServicesData { action: ServicesAction::STOP, guard } })], config, term, L1)?;
+    }
+
+    let result = TaskManager::default().start(vec![task], config, term, L1);
+
+    let restarted = if running {
+        TaskManager::default().start(vec![Box::new(ServicesManagerTask { input_data: ServicesData { action: ServicesAction::START, guard: false } })], config, term, L1)
+    } else {
+        Ok(Success {})
+    };
+
+    // the error of the task comes first, a failed restart is only printed next to it
+    match (result, restarted) {
+        (Err(error), Err(restart_error)) => {
+            restart_error.print();
+            Err(error)
+        }
+        (Err(error), Ok(_)) => Err(error),
+        (Ok(_), Err(restart_error)) => Err(restart_error),
+        (Ok(success), Ok(_)) => Ok(success),
+    }
+}
+
+fn get_backup_dir(command: &ArgMatches, config: &RemoteConfig) -> Result<String, Message> {
+    match command.get_one::<String>(Args::DEST._to_string()) {
+        Some(dest) => Ok(dest.trim_end_matches('/').to_string()),
+        None => Folder::get_backup_dir(config),
+    }
+}
+
+fn get_keep(command: &ArgMatches) -> Result<usize, Message> {
+    match command.get_one::<String>(Args::KEEP._to_string()) {
+        Some(keep) => keep.parse::<usize>().map_err(|_| Message::ParseArg(MessageData {
+            message: format!("The value {} of --{} must be a number", keep, Args::KEEP),
+            ..Default::default()
+        })),
+        None => Ok(get_settings(&Folder::get_cvm_home()?)?.backup_keep),
+    }
+}
+
+fn db_error(message: String) -> Message {
+    Message::DbBackup(MessageData {
+        message,
+        kind: MessageKind::Error,
+        ..Default::default()
+    })
+}
@@ -0,0 +1,18 @@
+#![allow(dead_code, unused_variables)]
+
+use std::io::stdout;
+use clap::ArgMatches;
+use crate::{CommandStrategy, config, Message, Success, Term};
+use crate::doctor::check::run_checks;
+use crate::doctor::check_impl::doctor_checks;
+
+pub struct Doctor {}
+
+impl CommandStrategy for Doctor {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+        let mut term = Term { stdout: stdout() };
+
+        run_checks(doctor_checks(), &config, &mut term, false)
+    }
+}
@@ -4,6 +4,8 @@ use std::io::stdout;
 use clap::ArgMatches;
 use crate::subcommands::subcommand_strategy::CommandStrategy;
 use crate::subcommands::commands_config::Args;
+use crate::doctor::check::run_checks;
+use crate::doctor::check_impl::init_checks;
 use crate::config::state_config::{reset_init, set_init_network, set_init_success};
 use crate::message::message::Message;
 use crate::task::task::{Success, Task};
@@ -12,7 +14,7 @@ use crate::task::task_impl::init::create_folder_structure_task::CreateFolderStru
 use crate::task::task_impl::init::download_config_files_task::DownloadConfigFilesTask;
 use crate::task::task_impl::init::install_dependences_task::InstallDependenciesTask;
 use crate::task::task_impl::init::install_ghcup_task::InstallHanskellGhcTask;
-use crate::task::task_impl::init::install_libsecp256k1_task::Installlibsecp256k1Task;
+use crate::task::task_impl::init::install_library_task::InstallLibraryTask;
 use crate::task_manager::task_manager::TaskManager;
 use crate::{config, Term};
 use crate::term::log_level::LogLevel::L1;
@@ -36,6 +38,8 @@ impl CommandStrategy for Init {
             }
         }
 
+        run_checks(init_checks(), &config, &mut term, true)?;
+
         if command.contains_id(Args::FORCE._to_string()) {
             reset_init()?;
         };
@@ -46,10 +50,12 @@ impl CommandStrategy for Init {
         ];
 
         if command.contains_id(Args::BUILD._to_string()) {
-            tasks.append(&mut vec![
-                Box::new(InstallHanskellGhcTask::default()),
-                Box::new(Installlibsecp256k1Task::default()),
-            ]);
+            tasks.push(Box::new(InstallHanskellGhcTask::default()));
+            for recipe in &config.library {
+                let mut install_library_task = InstallLibraryTask::default();
+                install_library_task.recipe = recipe.clone();
+                tasks.push(Box::new(install_library_task));
+            }
         } else {
             tasks.append(&mut vec![
                 Box::new(CreateFolderStructure::default()),
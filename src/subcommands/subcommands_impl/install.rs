@@ -5,13 +5,20 @@ use std::path::Path;
 use clap::ArgMatches;
 use crate::subcommands::commands_config::{Args};
 use crate::task::task::Success;
-use crate::utils::version_utils::{get_last_tag, LATEST, verify_version};
+use crate::utils::version_utils::{get_last_component_version, get_last_tag, LATEST, verify_label, verify_version};
 use crate::{Message, CommandStrategy, Term, MessageData, url_build, config};
+use crate::config::remote_config::{get_component, is_node_component, RemoteConfig};
 use crate::config::state_config::get_state;
+use crate::doctor::check::run_checks;
+use crate::doctor::check_impl::install_checks;
 use crate::message::message::MessageKind;
 use crate::task::task_impl::commons::folder_manager_task::{FolderManagerAction, FolderManagerTask};
-use crate::task::task_impl::install::build::build_cardano_node_task::BuildCardanoNodeTask;
+use crate::task::task_impl::install::build::backend::get_build_backend;
+use crate::task::task_impl::install::build::build_cardano_node_task::{BuildCardanoNodeTask, get_source_folder};
 use crate::task::task_impl::install::build::copy_bin_task::{CopyBinInputData, CopyBinTask};
+use crate::task::task_impl::install::build::record_build_task::{RecordBuildData, RecordBuildTask};
+use crate::task::task_impl::install::component::build_component_task::{BuildComponentTask, get_component_source_folder};
+use crate::task::task_impl::install::component::download_component_task::DownloadComponentTask;
 use crate::task::task_impl::install::download::download_install_task::DownloadInstallTask;
 use crate::task_manager::task_manager::TaskManager;
 use crate::term::log_level::LogLevel::L1;
@@ -25,18 +32,50 @@ impl CommandStrategy for Install {
         let mut term = Term { stdout: stdout() };
 
         let version_arg = command.get_one::<String>(Args::VERSION._to_string()).unwrap();
-        let mut version = verify_version(version_arg.as_str())?.to_string();
+        let repository = command.get_one::<String>(Args::REPO._to_string()).map(|value| value.to_string()).unwrap_or_default();
+        let git_ref = match command.get_one::<String>(Args::REF._to_string()) {
+            Some(git_ref) => git_ref.to_string(),
+            // the version of a fork without --ref is the tag or branch to build
+            None if !repository.is_empty() && version_arg != LATEST => version_arg.to_string(),
+            None if !repository.is_empty() => return Err(Message::ParseArg(MessageData {
+                message: format!("The {} version of another repository is not known, give the tag or branch as the version or with --ref", LATEST),
+                ..Default::default()
+            })),
+            None => "".to_string(),
+        };
+        let custom_source = !git_ref.is_empty() || !repository.is_empty();
+
+        // a build from a ref or another repository is installed under its own name instead of a release number,
+        // the name is only the folder of the build, never the version to download or check out
+        let mut version = match command.get_one::<String>(Args::NAME._to_string()) {
+            Some(name) if custom_source => verify_label(name.as_str())?.to_string(),
+            Some(name) => return Err(Message::ParseArg(MessageData {
+                message: format!("--name is only used with --ref or --repo, the release {} is installed under its own version", version_arg),
+                ..Default::default()
+            })),
+            None if custom_source => return Err(Message::ParseArg(MessageData {
+                message: "A build from --ref or --repo needs a --name to install it".to_string(),
+                ..Default::default()
+            })),
+            None => verify_version(version_arg.as_str())?.to_string(),
+        };
 
-        if !get_state()?.init.success {
-            return Err(Message::ProjectNotInit(
-                MessageData {
-                    message: "The project is still not initialized, please execute the [cvm init] command".to_string(),
+        let backend = command.get_one::<String>(Args::BACKEND._to_string()).map(|value| value.to_string()).unwrap_or_default();
+        let build = command.contains_id(Args::BUILD._to_string());
+        check_install(build, &backend, &config, &mut term)?;
+
+        let component = command.get_one::<String>(Args::COMPONENT._to_string()).map(|value| value.to_string()).unwrap_or_default();
+        if !is_node_component(&component) {
+            if custom_source {
+                return Err(Message::ParseArg(MessageData {
+                    message: "--ref and --repo can only be used to build cardano-node".to_string(),
                     ..Default::default()
-                }
-            ));
+                }));
+            }
+            return install_component(&component, version, build, &config, &mut term);
         }
 
-        if version == LATEST {
+        if version == LATEST && !custom_source {
             let last_tag = get_last_tag(&config.build_cardano_node.cnode_release);
             match last_tag {
                 Ok(tag) => version = tag,
@@ -44,41 +83,163 @@ impl CommandStrategy for Install {
             }
         }
 
-        let bin_folder = Folder::get_path(Folder::BIN, &config);
-        let version_folder = url_build(vec![&bin_folder, &version], false);
-        let version_folder_path = Path::new(&version_folder);
-        let cardano_folder = url_build(vec![&Folder::get_path(Folder::GIT, &config), &config.build_cardano_node.cnode_repository_name], false);
+        install_node(NodeInstall {
+            version,
+            build,
+            git_ref,
+            repository,
+            clean: command.contains_id(Args::CLEAN._to_string()),
+            shallow: command.contains_id(Args::SHALLOW._to_string()),
+            backend,
+        }, &config, &mut term)
+    }
+}
+
+/// Version of cardano-node to download or build and how
+#[derive(Default)]
+pub struct NodeInstall {
+    pub version: String,
+    pub build: bool,
+    pub git_ref: String,
+    pub repository: String,
+    pub clean: bool,
+    pub shallow: bool,
+    pub backend: String,
+}
 
-        if version_folder_path.exists() {
-            return Err(Message::VersionExist(MessageData {
-                message: format!("the version {ver} is already installed to reinstall it firts remove it with the command [cvm remove {ver}]", ver = version),
-                kind: MessageKind::Info,
+/// The workspace is initialized and the server passes the checks needed to install
+pub fn check_install(build: bool, backend: &String, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    if !get_state()?.init.success {
+        return Err(Message::ProjectNotInit(
+            MessageData {
+                message: "The project is still not initialized, please execute the [cvm init] command".to_string(),
                 ..Default::default()
-            }));
-        }
+            }
+        ));
+    }
 
-        let mut build_cardano_task = BuildCardanoNodeTask::default();
-        build_cardano_task.version = version.to_string();
-
-        if command.contains_id(Args::BUILD._to_string()) {
-            TaskManager::default().start(vec![
-                Box::new(build_cardano_task),
-                Box::new(FolderManagerTask { input_data: FolderManagerAction::Create(vec![(bin_folder.clone(), version.clone())]) }),
-                Box::new(CopyBinTask {
-                    input_data: CopyBinInputData {
-                        files_names: config.binaries.required_files.clone(),
-                        origin_path: cardano_folder.clone(),
-                        version: version.clone(),
-                        bin_folder: bin_folder.clone(),
-                        version_folder: version_folder.clone(),
-                    }
-                }),
-            ], &config, &mut term, L1)
-        } else {
-            TaskManager::default().start(vec![
-                Box::new(FolderManagerTask { input_data: FolderManagerAction::Create(vec![(bin_folder.clone(), version.clone())]) }),
-                Box::new(DownloadInstallTask { version }),
-            ], &config, &mut term, L1)
-        }
+    let build_checks = if build { get_build_backend(backend)?.checks() } else { vec![] };
+    run_checks(install_checks(build_checks), config, term, true)?;
+    Ok(Success {})
+}
+
+pub fn install_node(install: NodeInstall, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let NodeInstall { version, build, git_ref, repository, clean, shallow, backend: backend_name } = install;
+    let backend = get_build_backend(&backend_name)?;
+    let custom_source = !git_ref.is_empty() || !repository.is_empty();
+
+    let bin_folder = Folder::get_path(Folder::BIN, config);
+    let version_folder = url_build(vec![&bin_folder, &version], false);
+    let version_folder_path = Path::new(&version_folder);
+    let cardano_folder = get_source_folder(&repository, config);
+
+    if version_folder_path.exists() {
+        return Err(Message::VersionExist(MessageData {
+            message: format!("the version {ver} is already installed to reinstall it firts remove it with the command [cvm remove {ver}]", ver = version),
+            kind: MessageKind::Info,
+            ..Default::default()
+        }));
+    }
+
+    let mut build_cardano_task = BuildCardanoNodeTask::default();
+    build_cardano_task.version = version.to_string();
+    build_cardano_task.git_ref = git_ref.clone();
+    build_cardano_task.repository = repository.clone();
+    build_cardano_task.clean = clean;
+    build_cardano_task.shallow = shallow;
+    build_cardano_task.backend = backend_name.clone();
+    let build_source = build_cardano_task.get_build_source(config);
+
+    if build {
+        TaskManager::default().start(vec![
+            Box::new(build_cardano_task),
+            Box::new(FolderManagerTask { input_data: FolderManagerAction::Create(vec![(bin_folder.clone(), version.clone())]) }),
+            Box::new(CopyBinTask {
+                input_data: CopyBinInputData {
+                    files_names: config.binaries.required_files.clone(),
+                    origin_path: backend.output_folder(&build_source),
+                    version: version.clone(),
+                    bin_folder: bin_folder.clone(),
+                    version_folder: version_folder.clone(),
+                    follow_links: backend.output_links(),
+                }
+            }),
+            Box::new(RecordBuildTask {
+                input_data: RecordBuildData {
+                    repository: if repository.is_empty() { config.build_cardano_node.cnode_repository.clone() } else { repository },
+                    git_ref: if git_ref.is_empty() { version.clone() } else { git_ref },
+                    source_folder: cardano_folder.clone(),
+                    version_folder: version_folder.clone(),
+                    backend: backend.name(),
+                }
+            }),
+        ], config, term, L1)
+    } else if custom_source {
+        Err(Message::ParseArg(MessageData {
+            message: "--ref and --repo can only be used with --build".to_string(),
+            ..Default::default()
+        }))
+    } else {
+        TaskManager::default().start(vec![
+            Box::new(FolderManagerTask { input_data: FolderManagerAction::Create(vec![(bin_folder.clone(), version.clone())]) }),
+            Box::new(DownloadInstallTask { version }),
+        ], config, term, L1)
+    }
+}
+
+fn install_component(name: &String, mut version: String, build: bool, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let component = get_component(config, name)?;
+
+    if version == LATEST {
+        version = get_last_component_version(component)?;
+    }
+
+    let bin_folder = Folder::get_component_bin_dir(name, config);
+    let version_folder = url_build(vec![&bin_folder, &version], false);
+
+    if Path::new(&version_folder).exists() {
+        return Err(Message::VersionExist(MessageData {
+            message: format!("the version {ver} of {name} is already installed to reinstall it firts remove it with the command [cvm remove {ver} --component {name}]", ver = version, name = name),
+            kind: MessageKind::Info,
+            ..Default::default()
+        }));
+    }
+
+    // bin/<component> is created the first time a version of the component is installed
+    let folders = vec![(Folder::get_path(Folder::BIN, config), name.clone()), (bin_folder.clone(), version.clone())];
+
+    if build {
+        let source_folder = get_component_source_folder(component, config);
+        let mut build_component_task = BuildComponentTask::default();
+        build_component_task.component = component.clone();
+        build_component_task.version = version.clone();
+        TaskManager::default().start(vec![
+            Box::new(build_component_task),
+            Box::new(FolderManagerTask { input_data: FolderManagerAction::Create(folders) }),
+            Box::new(CopyBinTask {
+                input_data: CopyBinInputData {
+                    files_names: component.binaries.clone(),
+                    origin_path: source_folder.clone(),
+                    version: version.clone(),
+                    bin_folder: bin_folder.clone(),
+                    version_folder: version_folder.clone(),
+                    follow_links: false,
+                }
+            }),
+            Box::new(RecordBuildTask {
+                input_data: RecordBuildData {
+                    repository: component.repository.clone(),
+                    git_ref: version.clone(),
+                    source_folder,
+                    version_folder,
+                    backend: String::new(),
+                }
+            }),
+        ], config, term, L1)
+    } else {
+        TaskManager::default().start(vec![
+            Box::new(FolderManagerTask { input_data: FolderManagerAction::Create(folders) }),
+            Box::new(DownloadComponentTask { component: component.clone(), version, bin_folder, version_folder }),
+        ], config, term, L1)
     }
-}
\ No newline at end of file
+}
@@ -1,11 +1,15 @@
 #![allow(dead_code, unused_variables)]
 
+use std::path::Path;
 use clap::ArgMatches;
 use crossterm::style::Stylize;
 use fs_extra::dir::get_size;
 use walkdir::WalkDir;
 use crate::{CommandStrategy, config, Message, Success};
-use crate::utils::version_utils::verify_version;
+use crate::config::remote_config::{get_component, is_node_component};
+use crate::subcommands::commands_config::Args;
+use crate::task::task_impl::install::build::record_build_task::read_build_info;
+use crate::utils::version_utils::{verify_label, verify_version};
 use crate::config::state_config::get_state;
 use crate::utils::folders::Folder;
 
@@ -16,18 +20,36 @@ impl CommandStrategy for List{
 
         let config = config::remote_config::get_remote_config()?;
 
-        let bin_folder = Folder::get_path(Folder::BIN, &config);
-        let current_folder = Folder::get_path(Folder::CURRENT, &config);
+        let component = command.get_one::<String>(Args::COMPONENT._to_string()).map(|value| value.to_string()).unwrap_or_default();
+        if !is_node_component(&component) {
+            get_component(&config, &component)?;
+        }
+
+        let bin_folder = Folder::get_component_bin_dir(&component, &config);
+        let current_folder = Folder::get_component_current_dir(&component, &config);
+
+        let current_version = get_state()?.r#use.get_version(&component);
 
-        let current_version = get_state()?.r#use.version;
+        if !Path::new(&bin_folder).exists() {
+            return Ok(Success {});
+        }
 
-        for entry in WalkDir::new(bin_folder) {
+        for entry in WalkDir::new(bin_folder).min_depth(1).max_depth(1).sort_by_file_name() {
             let entry = entry.unwrap();
             let name = entry.file_name().to_str().unwrap().to_string();
-            if entry.path().is_dir() && verify_version(name.as_str()).is_ok() {
+            // the versions of the other components live in bin/<component>
+            let is_component_folder = is_node_component(&component) && config.component.iter().any(|item| item.name == name);
+            if entry.path().is_dir() && entry.path().to_str().unwrap() != current_folder && !is_component_folder && verify_label(name.as_str()).is_ok() {
 
                 let size = get_size(entry.path()).unwrap() / 1024 / 1024;
-                let size_format = format!("{} MB", size);
+                let mut size_format = format!("{} MB", size);
+
+                // versions built from a ref or a fork show where they come from
+                if let Some(info) = read_build_info(&entry.path().to_str().unwrap().to_string()) {
+                    if verify_version(name.as_str()).is_err() || info.git_ref != name {
+                        size_format = format!("{}  {}@{}{}", size_format, info.git_ref, &info.commit[..info.commit.len().min(10)], if info.dirty { "-dirty" } else { "" });
+                    }
+                }
 
                 if name != current_version {
                     print(format!("{}  {}", name.red(), size_format.red()));
@@ -0,0 +1,274 @@
+#![allow(dead_code, unused_variables)]
+
+use std::collections::VecDeque;
+use std::fs;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, stdout};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use clap::ArgMatches;
+use regex::Regex;
+use crate::{CommandStrategy, config, Message, MessageData, Success, Term};
+use crate::config::remote_config::{RemoteConfig, Services};
+use crate::service_manager::{get_service_manager, SYSTEMD};
+use crate::subcommands::commands_config::Args;
+use crate::task::task_impl::commons::run_command_task::Cmd;
+use crate::utils::folders::Folder;
+use crate::utils::node_log::{LogFilter, parse_record, parse_since, Severity};
+
+const DEFAULT_LINES: usize = 50;
+const JOURNAL: &str = "journal";
+const FILE: &str = "file";
+const JOURNAL_CURSOR: &str = "-- cursor: ";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct Logs {}
+
+impl CommandStrategy for Logs {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+        let mut term = Term { stdout: stdout() };
+
+        let service = config.services_item.first().ok_or(logs_error("The remote config has no services".to_string()))?;
+        let filter = get_filter(command)?;
+        let follow = command.contains_id(Args::FOLLOW._to_string());
+        let lines = match command.get_one::<String>(Args::LINES._to_string()) {
+            Some(lines) => lines.parse::<usize>().map_err(|_| parse_error(format!("The value {} of --{} must be a number", lines, Args::LINES)))?,
+            None => DEFAULT_LINES,
+        };
+
+        // the node json files have every record, the journal only what cnode.sh prints
+        let files = log_files(&config);
+        let source = match command.get_one::<String>(Args::SOURCE._to_string()) {
+            Some(source) => source.to_string(),
+            None if !files.is_empty() => FILE.to_string(),
+            None => JOURNAL.to_string(),
+        };
+
+        match source.as_str() {
+            JOURNAL => {
+                if get_service_manager()?.name() != SYSTEMD {
+                    return Err(logs_error(format!("The journal is only available with systemd, the node logs are in {}", Folder::get_path(Folder::LOGS, &config))));
+                }
+                read_journal(service, &filter, lines, follow, &mut term)
+            }
+            FILE => {
+                if files.is_empty() {
+                    return Err(logs_error(format!("There are no log files in {}", Folder::get_path(Folder::LOGS, &config))));
+                }
+                read_files(&config, files, &filter, lines, follow, &mut term)
+            }
+            other => Err(parse_error(format!("The value {} of --{} must be {} or {}", other, Args::SOURCE, JOURNAL, FILE))),
+        }
+    }
+}
+
+fn get_filter(command: &ArgMatches) -> Result<LogFilter, Message> {
+    let level = match command.get_one::<String>(Args::LEVEL._to_string()) {
+        Some(level) => Some(Severity::parse(level).ok_or(parse_error(format!("Unknown severity {}, use debug, info, notice, warn, error, critical, alert or emergency", level)))?),
+        None => None,
+    };
+    let grep = match command.get_one::<String>(Args::GREP._to_string()) {
+        Some(grep) => Some(Regex::new(grep).map_err(|error| parse_error(format!("The value {} of --{} is not a valid regular expression: {}", grep, Args::GREP, error)))?),
+        None => None,
+    };
+    let since = match command.get_one::<String>(Args::SINCE._to_string()) {
+        Some(since) => Some(parse_since(since)?),
+        None => None,
+    };
+    let namespace = command.get_one::<String>(Args::NAMESPACE._to_string()).map(|namespace| namespace.to_string());
+
+    Ok(LogFilter { level, namespace, grep, since })
+}
+
+/// Journal of the unit, the last lines are read first and then followed from the cursor where the first read stopped
+fn read_journal(service: &Services, filter: &LogFilter, lines: usize, follow: bool, term: &mut Term) -> Result<Success, Message> {
+    let mut args = vec!["-o".to_string(), "cat".to_string(), "--no-pager".to_string(), "--show-cursor".to_string()];
+    args.push(if Folder::is_rootless() { "--user-unit".to_string() } else { "-u".to_string() });
+    args.push(service.file.to_string());
+    if let Some(since) = &filter.since {
+        args.push(format!("--since={} UTC", since.replace('T', " ")));
+    }
+    // the filters run after journalctl, it can't cut the lines before them
+    let filtered = filter.level.is_some() || filter.namespace.is_some() || filter.grep.is_some();
+    args.push(format!("--lines={}", if lines == 0 || filtered { "all".to_string() } else { lines.to_string() }));
+
+    let mut cursor = None;
+    let mut tail = Tail::new(lines);
+    run_journalctl(&args, |line| {
+        match line.strip_prefix(JOURNAL_CURSOR) {
+            Some(value) => cursor = Some(value.to_string()),
+            None => tail.push(filter, line),
+        }
+    })?;
+    tail.print(term);
+
+    if !follow {
+        return Ok(Success {});
+    }
+
+    let mut follow_args: Vec<String> = args.into_iter().filter(|arg| !arg.starts_with("--lines=") && arg != "--show-cursor").collect();
+    follow_args.push("--follow".to_string());
+    match cursor {
+        Some(cursor) => follow_args.push(format!("--after-cursor={}", cursor)),
+        None => follow_args.push("--lines=0".to_string()),
+    }
+    run_journalctl(&follow_args, |line| print_line(filter, line, term))
+}
+
+fn run_journalctl<F: FnMut(&str)>(args: &Vec<String>, mut on_line: F) -> Result<Success, Message> {
+    let mut child = Command::new(Cmd::Journalctl.as_string()).args(args).stdout(Stdio::piped()).spawn()?;
+    if let Some(output) = child.stdout.take() {
+        for line in BufReader::new(output).lines() {
+            on_line(&line?);
+        }
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(logs_error(format!("journalctl {} exited with {}", args.join(" "), status)));
+    }
+    Ok(Success {})
+}
+
+/// The node json logs, or the output saved by the service manager when the node logs only to stdout
+fn log_files(config: &RemoteConfig) -> Vec<PathBuf> {
+    let logs = Folder::get_path(Folder::LOGS, config);
+    let mut files = vec![];
+    collect_files(&PathBuf::from(&logs), 1, &mut files);
+
+    let json: Vec<PathBuf> = files.iter().filter(|file| file.file_name().map(|name| name.to_string_lossy().contains(".json")).unwrap_or(false)).cloned().collect();
+    let mut files = if json.is_empty() { files } else { json };
+    files.sort_by_key(|file| fs::metadata(file).and_then(|metadata| metadata.modified()).ok());
+    files
+}
+
+/// runit keeps the log of each service in its own folder
+fn collect_files(folder: &PathBuf, depth: usize, files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        if path.is_dir() && depth > 0 {
+            collect_files(&path, depth - 1, files);
+        } else if path.is_file() && (name.contains(".json") || name.ends_with(".log") || name == "current") {
+            files.push(path);
+        }
+    }
+}
+
+/// The files are read from the oldest to the newest, then the newest is polled for new lines.
+/// A new file or a smaller one means the node rotated the log
+fn read_files(config: &RemoteConfig, files: Vec<PathBuf>, filter: &LogFilter, lines: usize, follow: bool, term: &mut Term) -> Result<Success, Message> {
+    let mut tail = Tail::new(lines);
+    for file in files.iter() {
+        for line in BufReader::new(File::open(file)?).lines() {
+            tail.push(filter, &line?);
+        }
+    }
+    tail.print(term);
+
+    if !follow {
+        return Ok(Success {});
+    }
+
+    let mut current = files.last().cloned().unwrap_or_default();
+    let mut offset = fs::metadata(&current).map(|metadata| metadata.len()).unwrap_or(0);
+    let mut pending = String::new();
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        if let Some(newest) = log_files(config).last() {
+            if *newest != current {
+                current = newest.clone();
+                offset = 0;
+                pending.clear();
+            }
+        }
+
+        let length = match fs::metadata(&current) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => continue,
+        };
+        if length < offset {
+            offset = 0;
+            pending.clear();
+        }
+        if length == offset {
+            continue;
+        }
+
+        let mut file = File::open(&current)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buffer = vec![];
+        file.read_to_end(&mut buffer)?;
+        offset += buffer.len() as u64;
+
+        // a line is printed once the node wrote all of it
+        pending.push_str(&String::from_utf8_lossy(&buffer));
+        while let Some(end) = pending.find('\n') {
+            let line: String = pending.drain(..=end).collect();
+            print_line(filter, line.trim_end(), term);
+        }
+    }
+}
+
+fn print_line(filter: &LogFilter, line: &str, term: &mut Term) {
+    let record = parse_record(line);
+    if line.trim().is_empty() || !filter.matches(line, &record) {
+        return;
+    }
+    match record {
+        Some(record) => term.print_log_record(&record),
+        None => term.print_log_line(line),
+    }
+}
+
+/// Last lines that pass the filter, all of them when the size is 0
+struct Tail {
+    size: usize,
+    lines: VecDeque<String>,
+}
+
+impl Tail {
+    fn new(size: usize) -> Tail {
+        Tail { size, lines: VecDeque::new() }
+    }
+
+    fn push(&mut self, filter: &LogFilter, line: &str) {
+        if line.trim().is_empty() || !filter.matches(line, &parse_record(line)) {
+            return;
+        }
+        self.lines.push_back(line.to_string());
+        if self.size > 0 && self.lines.len() > self.size {
+            self.lines.pop_front();
+        }
+    }
+
+    fn print(&self, term: &mut Term) {
+        for line in self.lines.iter() {
+            match parse_record(line) {
+                Some(record) => term.print_log_record(&record),
+                None => term.print_log_line(line),
+            }
+        }
+    }
+}
+
+fn logs_error(message: String) -> Message {
+    Message::Logs(MessageData {
+        message,
+        ..Default::default()
+    })
+}
+
+fn parse_error(message: String) -> Message {
+    Message::ParseArg(MessageData {
+        message,
+        ..Default::default()
+    })
+}
@@ -8,3 +8,13 @@ pub mod stop;
 pub mod remove;
 pub mod clean;
 pub mod config;
+pub mod state;
+pub mod doctor;
+pub mod db;
+pub mod toolchain;
+pub mod run;
+pub mod service;
+pub mod logs;
+pub mod upgrade;
+pub mod topology;
+pub mod node_config;
@@ -0,0 +1,106 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use clap::ArgMatches;
+use serde_json::Value;
+use crate::{CommandStrategy, config, Message, MessageData, Success, url_build};
+use crate::config::remote_config::{JsonPatch, RemoteConfig};
+use crate::service_manager::escalate_for_services;
+use crate::subcommands::commands_config::Args;
+use crate::utils::folders::Folder;
+use crate::utils::json_patch::{apply_patches, get_mut, to_pointer, write_json};
+
+const NODE_CONFIG_FILE: &str = "config.json";
+
+pub struct NodeConfig {}
+
+impl CommandStrategy for NodeConfig {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+        let path = node_config_path(&config);
+
+        match command.subcommand() {
+            Some(("get", matches)) => {
+                let key = matches.get_one::<String>(Args::KEY._to_string()).map(|key| key.to_string()).unwrap_or_default();
+                get(&path, &key)
+            }
+            Some(("set", matches)) => {
+                let key = matches.get_one::<String>(Args::KEY._to_string()).unwrap().to_string();
+                let value = matches.get_one::<String>(Args::VALUE._to_string()).unwrap().to_string();
+                set(&path, &key, &value, matches.contains_id(Args::CREATE._to_string()), matches.contains_id(Args::FORCE._to_string()))
+            }
+            _ => { Ok(Success {}) }
+        }
+    }
+}
+
+fn node_config_path(config: &RemoteConfig) -> String {
+    url_build(vec![&Folder::get_path(Folder::FILES, config), &NODE_CONFIG_FILE.to_string()], false)
+}
+
+fn read_json(path: &String) -> Result<Value, Message> {
+    serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|error| node_config_error(format!("{} is not a valid json file: {}", path, error)))
+}
+
+/// The whole config when there is no key, strings are printed without quotes
+fn get(path: &String, key: &String) -> Result<Success, Message> {
+    let mut json = read_json(path)?;
+    let value = get_mut(&mut json, &to_pointer(key)).map_err(|error| node_config_error(format!("{} is not in {}", key, NODE_CONFIG_FILE)))?;
+    match value {
+        Value::String(text) => println!("{}", text),
+        other => println!("{}", serde_json::to_string_pretty(other).unwrap_or_default()),
+    }
+    Ok(Success {})
+}
+
+/// The value is read as json, `true`, `12` or `["a"]`, and as a string otherwise.
+/// The key has to exist unless --create, and keep its type unless --force, so a typo is not saved silently
+fn set(path: &String, key: &String, value: &String, create: bool, force: bool) -> Result<Success, Message> {
+    escalate_for_services()?;
+
+    let mut json = read_json(path)?;
+    let pointer = to_pointer(key);
+    let new_value = serde_json::from_str::<Value>(value).unwrap_or(Value::String(value.to_string()));
+
+    let op = match get_mut(&mut json, &pointer) {
+        Ok(current) => {
+            if !force && !current.is_null() && type_name(current) != type_name(&new_value) {
+                return Err(node_config_error(format!("{} is {} and {} is {}, use --force to change its type", key, type_name(current), value, type_name(&new_value))));
+            }
+            if *current == new_value {
+                println!("{} is already {}", key, value);
+                return Ok(Success {});
+            }
+            "replace"
+        }
+        Err(_) if create => "add",
+        Err(_) => return Err(node_config_error(format!("{} is not in {}, use --create to add it", key, NODE_CONFIG_FILE))),
+    };
+
+    apply_patches(&mut json, &[JsonPatch { op: op.to_string(), path: pointer, value: Some(new_value), ..Default::default() }])
+        .map_err(|error| node_config_error(format!("{} can't be set: {}", key, error)))?;
+    write_json(path, &json)?;
+
+    println!("{} = {}, the previous config is kept in {}.bak", key, value, path);
+    println!("cardano-node reads its config when it starts, restart it with [cvm stop] and [cvm start] to apply the change");
+    Ok(Success {})
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn node_config_error(message: String) -> Message {
+    Message::UpdateConfigFile(MessageData {
+        message,
+        ..Default::default()
+    })
+}
@@ -4,11 +4,13 @@ use std::io::stdout;
 use std::path::Path;
 use clap::ArgMatches;
 use crate::{CommandStrategy, config, Message, Success, Term, url_build};
+use crate::config::remote_config::{get_component, is_node_component, RemoteConfig};
+use crate::config::state_config::{get_state, set_component_use};
 use crate::subcommands::commands_config::Args;
-use crate::utils::version_utils::{get_last_tag, LATEST, read_version, verify_version};
+use crate::utils::version_utils::{get_last_component_version, get_last_tag, LATEST, read_version, verify_label};
 use crate::task::task::Task;
 use crate::task::task_impl::commons::folder_manager_task::{FolderManagerAction, FolderManagerTask};
-use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesManagerTask};
+use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesData, ServicesManagerTask};
 use crate::task_manager::task_manager::TaskManager;
 use crate::term::log_level::LogLevel::L1;
 use crate::utils::folders::Folder;
@@ -22,7 +24,16 @@ impl CommandStrategy for Remove {
         let mut term = Term { stdout: stdout() };
 
         let version_arg = command.get_one::<String>(Args::VERSION._to_string()).unwrap();
-        let mut version = verify_version(version_arg.as_str())?.to_string();
+        let mut version = verify_label(version_arg.as_str())?.to_string();
+        let component = command.get_one::<String>(Args::COMPONENT._to_string()).map(|value| value.to_string()).unwrap_or_default();
+
+        if !is_node_component(&component) {
+            let item = get_component(&config, &component)?;
+            if version == LATEST {
+                version = get_last_component_version(item)?;
+            }
+            return remove_component(&component, &version, &config, &mut term);
+        }
 
         if version == LATEST {
             let last_tag = get_last_tag(&config.build_cardano_node.cnode_release);
@@ -40,7 +51,7 @@ impl CommandStrategy for Remove {
         let mut task_queue:Vec<Box<dyn Task>> = vec![];
 
         if current_version == version {
-            task_queue.push(Box::new(ServicesManagerTask { input_data: ServicesAction::STOP }));
+            task_queue.push(Box::new(ServicesManagerTask { input_data: ServicesData { action: ServicesAction::STOP, guard: false } }));
             task_queue.push(Box::new(FolderManagerTask { input_data: FolderManagerAction::Clean(vec![current_folder]) }));
         }
 
@@ -51,4 +62,27 @@ impl CommandStrategy for Remove {
         task_queue.reverse();
         TaskManager::default().start(task_queue, &config, &mut term, L1)
     }
-}
\ No newline at end of file
+}
+
+fn remove_component(component: &String, version: &String, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let current_folder = Folder::get_component_current_dir(component, config);
+    let version_folder = url_build(vec![&Folder::get_component_bin_dir(component, config), version], false);
+
+    let mut task_queue: Vec<Box<dyn Task>> = vec![];
+
+    if Path::new(&version_folder).exists() {
+        task_queue.push(Box::new(FolderManagerTask { input_data: FolderManagerAction::Remove(vec![version_folder]) }));
+    }
+
+    let in_use = &get_state()?.r#use.get_version(component) == version;
+    if in_use {
+        task_queue.push(Box::new(FolderManagerTask { input_data: FolderManagerAction::Clean(vec![current_folder]) }));
+    }
+
+    TaskManager::default().start(task_queue, config, term, L1)?;
+
+    if in_use {
+        set_component_use(component, String::new())?;
+    }
+    Ok(Success {})
+}
@@ -0,0 +1,49 @@
+#![allow(dead_code, unused_variables)]
+
+use std::time::Duration;
+use clap::ArgMatches;
+use crate::{CommandStrategy, config, Message, MessageData, Success};
+use crate::config::state_config::get_state;
+use crate::message::message::MessageKind;
+use crate::service_manager::{CVM, get_service_manager};
+use crate::service_manager::service_manager_impl::supervisor::run_supervisor;
+use crate::subcommands::commands_config::Args;
+
+const DEFAULT_RESTART_DELAY: u64 = 60;
+
+pub struct Run {}
+
+impl CommandStrategy for Run {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+
+        if get_state()?.r#use.version == "" {
+            return Err(Message::UseVersion(
+                MessageData {
+                    message: "There is no version in use yet, run the command [cvm use x.x.x]".to_string(),
+                    kind: MessageKind::Info,
+                    ..Default::default()
+                }
+            ));
+        }
+
+        // two copies of the node can't share the database
+        let service_manager = get_service_manager()?;
+        if service_manager.name() != CVM && config.services_item.iter().any(|service| service_manager.is_active(&service.name)) {
+            return Err(Message::ServiceManager(MessageData {
+                message: format!("cardano-node is already running under {}, stop it with [cvm stop] first", service_manager.name()),
+                ..Default::default()
+            }));
+        }
+
+        let restart_delay = match command.get_one::<String>(Args::RESTARTDELAY._to_string()) {
+            Some(delay) => delay.parse::<u64>().map_err(|_| Message::ParseArg(MessageData {
+                message: format!("The value {} of --{} must be a number of seconds", delay, Args::RESTARTDELAY),
+                ..Default::default()
+            }))?,
+            None => DEFAULT_RESTART_DELAY,
+        };
+
+        run_supervisor(&config, Duration::from_secs(restart_delay))
+    }
+}
@@ -0,0 +1,97 @@
+#![allow(dead_code, unused_variables)]
+
+use std::env;
+use std::fs;
+use std::io::stdout;
+use std::path::Path;
+use std::process::Command;
+use clap::ArgMatches;
+use crate::{CommandStrategy, config, Message, MessageData, Success, Term};
+use crate::config::remote_config::{RemoteConfig, Services};
+use crate::service_manager::{escalate_for_services, get_service_manager};
+use crate::subcommands::commands_config::Args;
+use crate::task::task_type::TaskType;
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L1;
+
+const DEFAULT_EDITOR: &str = "vi";
+
+pub struct Service {}
+
+impl CommandStrategy for Service {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+        let mut term = Term { stdout: stdout() };
+
+        match command.subcommand() {
+            Some(("edit", matches)) => {
+                edit(matches, &config, &mut term)
+            }
+            _ => { Ok(Success {}) }
+        }
+    }
+}
+
+/// Open the drop-in of the service in the editor and reload the service manager.
+/// A drop-in left with only comments is removed
+fn edit(command: &ArgMatches, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    escalate_for_services()?;
+
+    let service_manager = get_service_manager()?;
+    let service = get_service(command, config)?;
+    let drop_in = match service_manager.drop_in_file(service)? {
+        Some(drop_in) => drop_in,
+        None => return Err(service_error(format!("{} has no drop-in files, change the {} service in the settings file", service_manager.name(), service.name))),
+    };
+
+    if !Path::new(&drop_in).exists() {
+        if let Some(parent) = Path::new(&drop_in).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&drop_in, drop_in_header(service))?;
+    }
+
+    // the editor variable can carry arguments, like "code --wait"
+    let editor = env::var("VISUAL").or(env::var("EDITOR")).unwrap_or(DEFAULT_EDITOR.to_string());
+    let status = Command::new("sh").args(["-c", &format!("{} \"$1\"", editor), "sh", &drop_in]).status()?;
+    if !status.success() {
+        return Err(service_error(format!("The editor {} exited with {}, the drop-in {} was not applied", editor, status, drop_in)));
+    }
+
+    let content = fs::read_to_string(&drop_in)?;
+    if content.lines().all(|line| line.trim().is_empty() || line.trim_start().starts_with('#')) {
+        fs::remove_file(&drop_in)?;
+        if let Some(parent) = Path::new(&drop_in).parent() {
+            let _ = fs::remove_dir(parent);
+        }
+        println!("The drop-in is empty, {} uses the unit deployed by cvm", service.name);
+    }
+
+    TaskManager::default().start(service_manager.reload_tasks(config)?, config, term, L1)?;
+    println!("Run [cvm start] to restart {} with the changes", service.name);
+    Ok(Success {})
+}
+
+fn get_service<'a>(command: &ArgMatches, config: &'a RemoteConfig) -> Result<&'a Services, Message> {
+    match command.get_one::<String>(Args::NAME._to_string()) {
+        Some(name) => config.services_item.iter().find(|service| &service.name == name)
+            .ok_or(service_error(format!("There is no service {}", name))),
+        None => config.services_item.first().ok_or(service_error("The remote config has no services".to_string())),
+    }
+}
+
+fn drop_in_header(service: &Services) -> String {
+    format!("# Local changes to {}, cvm never overwrites this file\n\
+             # Example:\n\
+             # [Service]\n\
+             # Environment=\"GHCRTS=-N8 -A64m\"\n\
+             # Nice=-5\n", service.file)
+}
+
+fn service_error(message: String) -> Message {
+    Message::ServiceManager(MessageData {
+        message,
+        task: TaskType::ServicesManager,
+        ..Default::default()
+    })
+}
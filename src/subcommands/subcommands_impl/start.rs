@@ -5,9 +5,11 @@ use clap::ArgMatches;
 use crate::{CommandStrategy, config, Message, MessageData, Success, Term};
 use crate::config::state_config::get_state;
 use crate::message::message::MessageKind;
-use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesManagerTask};
+use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesData, ServicesManagerTask};
 use crate::task_manager::task_manager::TaskManager;
 use crate::term::log_level::LogLevel::L1;
+use crate::service_manager::escalate_for_services;
+use crate::subcommands::commands_config::Args;
 
 pub struct Start {}
 
@@ -26,9 +28,9 @@ impl CommandStrategy for Start {
             ));
         }
 
-        sudo::escalate_if_needed().expect("Super user permissions are required");
+        escalate_for_services()?;
         TaskManager::default().start(vec![
-            Box::new(ServicesManagerTask { input_data: ServicesAction::START }),
+            Box::new(ServicesManagerTask { input_data: ServicesData { action: ServicesAction::START, guard: !command.contains_id(Args::FORCE._to_string()) } }),
         ], &config, &mut term, L1)
     }
 }
\ No newline at end of file
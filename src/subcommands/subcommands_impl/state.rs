@@ -0,0 +1,59 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use clap::ArgMatches;
+use crate::subcommands::subcommand_strategy::CommandStrategy;
+use crate::subcommands::commands_config::Args;
+use crate::config::state_config::{get_state, import_state, parse_state, state_to_string};
+use crate::message::message::{Message, MessageData, MessageKind};
+use crate::task::task::Success;
+use crate::utils::file_utils::write_file_atomic;
+
+pub struct State {}
+
+impl CommandStrategy for State {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        match command.subcommand() {
+            Some(("show", matches)) => {
+                show()
+            }
+            Some(("export", matches)) => {
+                let file = matches.get_one::<String>(Args::FILE._to_string()).unwrap();
+                export(file)
+            }
+            Some(("import", matches)) => {
+                let file = matches.get_one::<String>(Args::FILE._to_string()).unwrap();
+                import(file)
+            }
+            _ => { Ok(Success {}) }
+        }
+    }
+}
+
+fn show() -> Result<Success, Message> {
+    println!("{}", state_to_string(&get_state()?));
+    Ok(Success {})
+}
+
+fn export(file: &String) -> Result<Success, Message> {
+    write_file_atomic(file, state_to_string(&get_state()?).as_bytes())?;
+    println!("State exported to {}", file);
+    Ok(Success {})
+}
+
+fn import(file: &String) -> Result<Success, Message> {
+    let text = fs::read_to_string(file)?;
+    let (state, migrated) = parse_state(&text).map_err(|error| Message::StateFile(MessageData {
+        message: format!("The file {} is not a valid cvm state", file),
+        stack: vec![error.data().message.clone()],
+        kind: MessageKind::Error,
+        ..Default::default()
+    }))?;
+
+    import_state(state)?;
+    if migrated {
+        println!("The imported state was upgraded to the current schema");
+    }
+    println!("State imported from {}", file);
+    Ok(Success {})
+}
@@ -3,9 +3,11 @@
 use std::io::stdout;
 use clap::ArgMatches;
 use crate::{CommandStrategy, config, Message, Success, Term};
-use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesManagerTask};
+use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesData, ServicesManagerTask};
 use crate::task_manager::task_manager::TaskManager;
 use crate::term::log_level::LogLevel::L1;
+use crate::subcommands::commands_config::Args;
+use crate::service_manager::escalate_for_services;
 
 pub struct Stop{}
 
@@ -15,10 +17,10 @@ impl CommandStrategy for Stop{
         let config = config::remote_config::get_remote_config()?;
         let mut term = Term { stdout: stdout() };
 
-        sudo::escalate_if_needed().expect("Super user permissions are required");
+        escalate_for_services()?;
 
         TaskManager::default().start(vec![
-            Box::new(ServicesManagerTask { input_data: ServicesAction::STOP }),
+            Box::new(ServicesManagerTask { input_data: ServicesData { action: ServicesAction::STOP, guard: !command.contains_id(Args::FORCE._to_string()) } }),
         ], &config, &mut term, L1)
     }
 }
\ No newline at end of file
@@ -0,0 +1,95 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::io::stdout;
+use clap::ArgMatches;
+use crossterm::style::Stylize;
+use regex::Regex;
+use crate::{CommandStrategy, config, Message, Success, Term};
+use crate::config::remote_config;
+use crate::config::remote_config::{get_toolchain, RemoteConfig};
+use crate::subcommands::commands_config::Args;
+use crate::task::task_impl::toolchain::toolchain_task::{active_version, is_installed, ToolchainAction, ToolchainTask};
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L1;
+use crate::utils::folders::Folder;
+use crate::utils::version_utils::{get_last_tag, LATEST, verify_version};
+
+pub struct Toolchain {}
+
+impl CommandStrategy for Toolchain {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+        let mut term = Term { stdout: stdout() };
+
+        match command.subcommand() {
+            Some(("ls", matches)) => {
+                list(&config)
+            }
+            Some(("install", matches)) => {
+                let toolchain = resolve_toolchain(matches, &config)?;
+                TaskManager::default().start(vec![
+                    Box::new(ToolchainTask { input_data: ToolchainAction::Install(toolchain) }),
+                ], &config, &mut term, L1)
+            }
+            Some(("remove", matches)) => {
+                let toolchain = resolve_toolchain(matches, &config)?;
+                TaskManager::default().start(vec![
+                    Box::new(ToolchainTask { input_data: ToolchainAction::Remove(toolchain) }),
+                ], &config, &mut term, L1)
+            }
+            _ => { Ok(Success {}) }
+        }
+    }
+}
+
+/// Toolchain of the node version passed, the latest release by default
+fn resolve_toolchain(command: &ArgMatches, config: &RemoteConfig) -> Result<remote_config::Toolchain, Message> {
+    let version_arg = command.get_one::<String>(Args::VERSION._to_string()).unwrap();
+    let mut version = verify_version(version_arg.as_str())?.to_string();
+
+    if version == LATEST {
+        version = get_last_tag(&config.build_cardano_node.cnode_release)?;
+    }
+    Ok(get_toolchain(config, &version))
+}
+
+/// Print the version matrix of the remote config and the versions installed with ghcup
+fn list(config: &RemoteConfig) -> Result<Success, Message> {
+    let ghcup_dir = Folder::get_ghcup_bin_dir(config)?;
+    let active_ghc = active_version(&ghcup_dir, "ghc");
+    let active_cabal = active_version(&ghcup_dir, "cabal");
+
+    println!("{:<20}{:<12}{:<12}", "cardano-node", "ghc", "cabal");
+    for toolchain in &config.toolchain {
+        let range = format!("{} - {}", toolchain.from, toolchain.to);
+        let line = format!("{:<20}{:<12}{:<12}", range, toolchain.ghc, toolchain.cabal);
+
+        if toolchain.ghc == active_ghc && toolchain.cabal == active_cabal {
+            println!("{}{}", line.green(), "active".yellow());
+        } else if is_installed(&ghcup_dir, "ghc", &toolchain.ghc) && is_installed(&ghcup_dir, "cabal", &toolchain.cabal) {
+            println!("{}{}", line.green(), "installed".green());
+        } else {
+            println!("{}", line.red());
+        }
+    }
+
+    println!();
+    println!("installed ghc: {}", installed_versions(&ghcup_dir, "ghc").join(", "));
+    println!("installed cabal: {}", installed_versions(&ghcup_dir, "cabal").join(", "));
+    Ok(Success {})
+}
+
+/// ghcup also links the short versions like ghc-8.10, only the full ones are listed
+fn installed_versions(ghcup_dir: &String, tool: &str) -> Vec<String> {
+    let regex = Regex::new(&format!(r"^{}-(\d+\.\d+\.\d+(\.\d+)?)$", tool)).unwrap();
+    let mut versions: Vec<String> = match fs::read_dir(ghcup_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| regex.captures(entry.file_name().to_str().unwrap_or_default()).map(|captures| captures[1].to_string()))
+            .collect(),
+        Err(_) => vec![],
+    };
+    versions.sort();
+    versions
+}
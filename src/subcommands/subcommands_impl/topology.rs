@@ -0,0 +1,182 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use clap::ArgMatches;
+use serde_json::Value;
+use crate::{CommandStrategy, config, Message, MessageData, Success, url_build};
+use crate::config::remote_config::RemoteConfig;
+use crate::message::message::MessageKind;
+use crate::service_manager::escalate_for_services;
+use crate::subcommands::commands_config::Args;
+use crate::utils::folders::Folder;
+use crate::utils::node_process::reload_node;
+use crate::utils::topology::{display_peer, NodeTopology, TOPOLOGY_FILE, topology_error, TopologyFormat};
+
+const NODE_CONFIG_FILE: &str = "config.json";
+
+pub struct Topology {}
+
+impl CommandStrategy for Topology {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+        let path = topology_path(&config);
+
+        match command.subcommand() {
+            Some(("ls", matches)) => {
+                list(&NodeTopology::read(&path)?)
+            }
+            Some(("validate", matches)) => {
+                let file = matches.get_one::<String>(Args::FILE._to_string()).map(|file| file.to_string()).unwrap_or(path);
+                validate(&file)
+            }
+            Some(("add", matches)) => {
+                let address = get_address(matches);
+                let port = parse_number(matches, Args::PORT)?.unwrap();
+                let valency = parse_number(matches, Args::VALENCY)?.unwrap_or(1);
+                let public = matches.contains_id(Args::PUBLIC._to_string());
+                edit(&path, &config, |topology| {
+                    topology.add(&address, port, valency, public)?;
+                    Ok(format!("{} added", display_peer(&address, Some(port))))
+                })
+            }
+            Some(("remove", matches)) => {
+                let address = get_address(matches);
+                let port = parse_number(matches, Args::PORT)?;
+                edit(&path, &config, |topology| {
+                    topology.remove(&address, port)?;
+                    Ok(format!("{} removed from the topology", display_peer(&address, port)))
+                })
+            }
+            Some(("set-valency", matches)) => {
+                let address = get_address(matches);
+                let port = parse_number(matches, Args::PORT)?;
+                let valency = parse_number(matches, Args::VALENCY)?.unwrap();
+                edit(&path, &config, |topology| {
+                    topology.set_valency(&address, port, valency)?;
+                    Ok(format!("The valency of {} is {}", display_peer(&address, port), valency))
+                })
+            }
+            Some(("convert", matches)) => {
+                let format = TopologyFormat::parse(matches.get_one::<String>(Args::TO._to_string()).unwrap()).unwrap();
+                convert(&path, format, &config)
+            }
+            _ => { Ok(Success {}) }
+        }
+    }
+}
+
+fn topology_path(config: &RemoteConfig) -> String {
+    url_build(vec![&Folder::get_path(Folder::FILES, config), &TOPOLOGY_FILE.to_string()], false)
+}
+
+fn list(topology: &NodeTopology) -> Result<Success, Message> {
+    let peers = topology.peers();
+    if peers.is_empty() {
+        println!("The {} topology has no peers", topology.format);
+    }
+
+    for peer in peers {
+        let valency = peer.valency.map(|valency| format!("  valency {}", valency)).unwrap_or_default();
+        println!("{} #{}  {}:{}{}", peer.list, peer.group + 1, peer.address, peer.port, valency);
+    }
+    Ok(Success {})
+}
+
+fn validate(path: &String) -> Result<Success, Message> {
+    let topology = NodeTopology::read(path)?;
+    let problems = topology.validate();
+    if !problems.is_empty() {
+        return Err(problems_error(path, problems));
+    }
+    println!("{} is a valid {} topology with {} peers", path, topology.format, topology.peers().len());
+    Ok(Success {})
+}
+
+/// The change is only written when the topology is still valid, the previous one is kept as topology.json.bak
+fn edit<F: FnOnce(&mut NodeTopology) -> Result<String, Message>>(path: &String, config: &RemoteConfig, change: F) -> Result<Success, Message> {
+    escalate_for_services()?;
+
+    let mut topology = NodeTopology::read(path)?;
+    let message = change(&mut topology)?;
+    let problems = topology.validate();
+    if !problems.is_empty() {
+        return Err(problems_error(path, problems));
+    }
+    topology.write(path)?;
+    println!("{}", message);
+
+    match topology.format {
+        TopologyFormat::P2P => {
+            if reload_node(config) > 0 {
+                println!("cardano-node reloaded the topology");
+            }
+        }
+        TopologyFormat::LEGACY => {
+            println!("The legacy topology is read when the node starts, restart it with [cvm stop] and [cvm start] to apply the change");
+        }
+    }
+    Ok(Success {})
+}
+
+/// The node only reads the topology as P2P with EnableP2P in its config, the switch needs a restart
+fn convert(path: &String, format: TopologyFormat, config: &RemoteConfig) -> Result<Success, Message> {
+    escalate_for_services()?;
+
+    let topology = NodeTopology::read(path)?;
+    if topology.format == format {
+        println!("The topology is already {}", format);
+        return Ok(Success {});
+    }
+
+    let converted = topology.convert(format);
+    let problems = converted.validate();
+    if !problems.is_empty() {
+        return Err(problems_error(path, problems));
+    }
+    converted.write(path)?;
+    println!("The topology was converted to {} with {} peers, the {} one is kept in {}.bak", format, converted.peers().len(), topology.format, path);
+
+    let lost = topology.lost_valencies(format);
+    if !lost.is_empty() {
+        Message::Topology(MessageData {
+            message: format!("The {} topology can't keep these valencies, check them with [cvm topology set-valency]:\n  {}", format, lost.join("\n  ")),
+            kind: MessageKind::Warning,
+            ..Default::default()
+        }).print();
+    }
+
+    let p2p = format == TopologyFormat::P2P;
+    if p2p_enabled(config) != Some(p2p) {
+        Message::Topology(MessageData {
+            message: format!("Set EnableP2P to {} in {} with [cvm node-config set EnableP2P {} --create] before the node is started again", p2p, NODE_CONFIG_FILE, p2p),
+            kind: MessageKind::Warning,
+            ..Default::default()
+        }).print();
+    }
+    println!("Restart the node with [cvm stop] and [cvm start] to use the new topology");
+    Ok(Success {})
+}
+
+fn p2p_enabled(config: &RemoteConfig) -> Option<bool> {
+    let path = url_build(vec![&Folder::get_path(Folder::FILES, config), &NODE_CONFIG_FILE.to_string()], false);
+    let json: Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    Some(json.get("EnableP2P").and_then(|value| value.as_bool()).unwrap_or(false))
+}
+
+fn get_address(command: &ArgMatches) -> String {
+    command.get_one::<String>(Args::ADDRESS._to_string()).unwrap().to_string()
+}
+
+fn parse_number(command: &ArgMatches, arg: Args) -> Result<Option<u64>, Message> {
+    match command.get_one::<String>(arg._to_string()) {
+        Some(value) => value.parse::<u64>().map(Some).map_err(|_| Message::ParseArg(MessageData {
+            message: format!("The value {} of {} must be a number", value, arg),
+            ..Default::default()
+        })),
+        None => Ok(None),
+    }
+}
+
+fn problems_error(path: &String, problems: Vec<String>) -> Message {
+    topology_error(format!("{} has problems:\n  {}", path, problems.join("\n  ")))
+}
@@ -0,0 +1,197 @@
+#![allow(dead_code, unused_variables)]
+
+use std::cmp::Ordering;
+use std::io::stdout;
+use std::path::Path;
+use clap::ArgMatches;
+use regex::Regex;
+use crate::{CommandStrategy, config, Message, MessageData, Success, Term, url_build};
+use crate::config::remote_config::RemoteConfig;
+use crate::config::settings_config::get_settings;
+use crate::config::state_config::{add_upgrade_record, get_state, set_upgrade_pending, set_upgrade_scheduled, UpgradeRecord};
+use crate::service_manager::escalate_for_services;
+use crate::subcommands::commands_config::Args;
+use crate::subcommands_impl::install::{check_install, install_node, NodeInstall};
+use crate::subcommands_impl::r#use::use_node_version;
+use crate::task::task_impl::upgrade::schedule_upgrade_task::{ScheduleUpgradeAction, ScheduleUpgradeTask};
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L1;
+use crate::utils::folders::Folder;
+use crate::utils::genesis::next_epoch_start;
+use crate::utils::time_utils::{utc_iso, utc_timestamp};
+use crate::utils::version_utils::{compare_versions, get_last_tag, get_release_versions, LATEST, verify_version};
+
+const NEXT_EPOCH: &str = "next-epoch";
+const POLICY_NONE: &str = "none";
+const POLICY_PATCH: &str = "patch";
+const POLICY_MINOR: &str = "minor";
+const POLICY_MAJOR: &str = "major";
+
+pub struct Upgrade {}
+
+impl CommandStrategy for Upgrade {
+    fn start(command: &ArgMatches) -> Result<Success, Message> {
+        let config = config::remote_config::get_remote_config()?;
+        let mut term = Term { stdout: stdout() };
+
+        escalate_for_services()?;
+
+        if command.contains_id(Args::AUTO._to_string()) {
+            return schedule_policy(command.contains_id(Args::DISABLE._to_string()), &config, &mut term);
+        }
+
+        if command.contains_id(Args::CANCEL._to_string()) {
+            TaskManager::default().start(vec![
+                Box::new(ScheduleUpgradeTask { input_data: ScheduleUpgradeAction::CancelAt }),
+            ], &config, &mut term, L1)?;
+            return set_upgrade_scheduled(String::new(), String::new());
+        }
+
+        let current = get_state()?.r#use.version;
+        let version = if command.contains_id(Args::POLICY._to_string()) {
+            let policy = get_settings(&Folder::get_cvm_home()?)?.upgrade.policy;
+            match policy_version(&policy, &current, &config)? {
+                Some(version) => version,
+                None => {
+                    println!("cardano-node {} is the newest version allowed by the {} policy", current, policy);
+                    return Ok(Success {});
+                }
+            }
+        } else {
+            let version = verify_version(command.get_one::<String>(Args::VERSION._to_string()).unwrap())?.to_string();
+            if version == LATEST { get_last_tag(&config.build_cardano_node.cnode_release)? } else { version }
+        };
+
+        if version == current {
+            println!("cardano-node {} is already in use", version);
+            return Ok(Success {});
+        }
+
+        // the timers run unattended, the state keeps what happened
+        let record = |result: String| add_upgrade_record(UpgradeRecord { at: utc_timestamp(), from: current.clone(), to: version.clone(), result });
+        match run_upgrade(&version, command, &config, &mut term) {
+            Ok(Some(result)) => {
+                record(result)?;
+                Ok(Success {})
+            }
+            Ok(None) => Ok(Success {}),
+            Err(error) => {
+                record(error.data().message.to_string())?;
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Install the version and switch to it, later with --at or never with --download-only.
+/// Returns the result to record in the state
+fn run_upgrade(version: &String, command: &ArgMatches, config: &RemoteConfig, term: &mut Term) -> Result<Option<String>, Message> {
+    let installed = install_if_missing(version, command.contains_id(Args::BUILD._to_string()), config, term)?;
+    let accept_replay = command.contains_id(Args::ACCEPTREPLAY._to_string());
+
+    if command.contains_id(Args::DOWNLOADONLY._to_string()) {
+        set_upgrade_pending(version.to_string())?;
+        println!("cardano-node {} is installed and ready for the next upgrade", version);
+        return Ok(if installed { Some("installed".to_string()) } else { None });
+    }
+
+    if let Some(at) = command.get_one::<String>(Args::AT._to_string()) {
+        let calendar = get_calendar(at, config)?;
+        let mut args = vec![version.to_string()];
+        if accept_replay {
+            args.push(format!("--{}", Args::ACCEPTREPLAY));
+        }
+        if command.contains_id(Args::FORCE._to_string()) {
+            args.push(format!("--{}", Args::FORCE));
+        }
+        TaskManager::default().start(vec![
+            Box::new(ScheduleUpgradeTask { input_data: ScheduleUpgradeAction::At(calendar.clone(), args) }),
+        ], config, term, L1)?;
+        set_upgrade_scheduled(version.to_string(), calendar.clone())?;
+        println!("cardano-node will switch to {} at {}", version, calendar);
+        return Ok(None);
+    }
+
+    use_node_version(version.to_string(), accept_replay, true, command.contains_id(Args::FORCE._to_string()), config, term)?;
+
+    let upgrade = get_state()?.upgrade;
+    if &upgrade.pending == version {
+        set_upgrade_pending(String::new())?;
+    }
+    if &upgrade.scheduled_version == version {
+        set_upgrade_scheduled(String::new(), String::new())?;
+    }
+    Ok(Some("upgraded".to_string()))
+}
+
+/// Returns true when the version was installed now
+fn install_if_missing(version: &String, build: bool, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+    let version_folder = url_build(vec![&Folder::get_path(Folder::BIN, config), version], false);
+    if Path::new(&version_folder).exists() {
+        return Ok(false);
+    }
+
+    check_install(build, &String::new(), config, term)?;
+    install_node(NodeInstall { version: version.to_string(), build, ..Default::default() }, config, term)?;
+    Ok(true)
+}
+
+/// Newest release newer than the version in use that the policy allows
+fn policy_version(policy: &String, current: &String, config: &RemoteConfig) -> Result<Option<String>, Message> {
+    let same_parts = match policy.to_lowercase().as_str() {
+        POLICY_PATCH => 2,
+        POLICY_MINOR => 1,
+        POLICY_MAJOR => 0,
+        POLICY_NONE => return Err(upgrade_error("The upgrade policy is none, set policy to patch, minor or major in the [upgrade] table of the settings file".to_string())),
+        _ => return Err(upgrade_error(format!("Unknown upgrade policy {}, use {}, {}, {} or {}", policy, POLICY_NONE, POLICY_PATCH, POLICY_MINOR, POLICY_MAJOR))),
+    };
+    if current.is_empty() || verify_version(current).is_err() {
+        return Err(upgrade_error(format!("The version in use \"{}\" is not a release, switch to one with [cvm use x.x.x] before upgrading with a policy", current)));
+    }
+
+    let prefix = |version: &String| version.split('.').take(same_parts).map(|part| part.to_string()).collect::<Vec<String>>();
+    let newest = get_release_versions(&config.build_cardano_node.cnode_release)?.into_iter()
+        .filter(|version| compare_versions(version, current) == Ordering::Greater && prefix(version) == prefix(current))
+        .max_by(|left, right| compare_versions(left, right));
+    Ok(newest)
+}
+
+/// systemd calendar of --at, the start of the next epoch is read from the shelley genesis
+fn get_calendar(at: &String, config: &RemoteConfig) -> Result<String, Message> {
+    if at == NEXT_EPOCH {
+        let start = next_epoch_start(config).ok_or(upgrade_error("The shelley genesis of the workspace is missing or incomplete, the next epoch can't be calculated".to_string()))?;
+        return Ok(utc_iso(start).replace('T', " ").replace('Z', " UTC"));
+    }
+
+    let date = Regex::new(r"^\d{4}-\d{2}-\d{2}[ T]\d{2}:\d{2}(:\d{2})?$").unwrap();
+    if date.is_match(at) {
+        return Ok(format!("{} UTC", at.replace('T', " ")));
+    }
+    Ok(at.to_string())
+}
+
+fn schedule_policy(disable: bool, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    if disable {
+        return TaskManager::default().start(vec![
+            Box::new(ScheduleUpgradeTask { input_data: ScheduleUpgradeAction::DisablePolicy }),
+        ], config, term, L1);
+    }
+
+    let settings = get_settings(&Folder::get_cvm_home()?)?.upgrade;
+    if settings.policy.to_lowercase() == POLICY_NONE {
+        return Err(upgrade_error("The upgrade policy is none, set policy to patch, minor or major in the [upgrade] table of the settings file".to_string()));
+    }
+    let message = format!("The releases allowed by the {} policy are installed at [{}] and put in use at [{}]", settings.policy, settings.prepare, settings.window);
+    TaskManager::default().start(vec![
+        Box::new(ScheduleUpgradeTask { input_data: ScheduleUpgradeAction::EnablePolicy(settings) }),
+    ], config, term, L1)?;
+    println!("{}", message);
+    Ok(Success {})
+}
+
+fn upgrade_error(message: String) -> Message {
+    Message::Upgrade(MessageData {
+        message,
+        ..Default::default()
+    })
+}
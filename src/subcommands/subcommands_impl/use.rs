@@ -1,15 +1,22 @@
 #![allow(dead_code, unused_variables)]
 
 use std::io::stdout;
+use std::time::{Duration, Instant};
 use clap::{ArgMatches};
 use crate::{CommandStrategy, config, Message, Success, Term};
+use crate::config::remote_config::{get_component, is_node_component, RemoteConfig};
+use crate::config::settings_config::get_settings;
+use crate::service_manager::{escalate_for_services, services_active};
 use crate::subcommands::commands_config::Args;
-use crate::utils::version_utils::{get_last_tag, LATEST, verify_version};
+use crate::utils::version_utils::{get_last_component_version, get_last_tag, LATEST, verify_label};
+use crate::task::task::Task;
 use crate::task::task_impl::r#use::deploy_system_task::DeploySystemTask;
-use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesManagerTask};
-use crate::task::task_impl::r#use::use_version_task::{UserVersionData, UserVersionTask};
+use crate::task::task_impl::r#use::health_check_task::{HealthCheckData, HealthCheckTask};
+use crate::task::task_impl::r#use::service_manager_task::{ServicesAction, ServicesData, ServicesManagerTask};
+use crate::task::task_impl::r#use::use_version_task::{UserVersionData, UserVersionTask, verify_db_switch};
 use crate::task_manager::task_manager::TaskManager;
 use crate::term::log_level::LogLevel::L1;
+use crate::utils::folders::Folder;
 
 pub struct Use{}
 
@@ -20,7 +27,19 @@ impl CommandStrategy for Use{
         let mut term = Term { stdout: stdout() };
 
         let version_arg = command.get_one::<String>(Args::VERSION._to_string()).unwrap();
-        let mut version = verify_version(version_arg.as_str())?.to_string();
+        let mut version = verify_label(version_arg.as_str())?.to_string();
+        let component = command.get_one::<String>(Args::COMPONENT._to_string()).map(|value| value.to_string()).unwrap_or_default();
+
+        // the other components are only binaries in the path, switching them doesn't touch the node services
+        if !is_node_component(&component) {
+            let item = get_component(&config, &component)?;
+            if version == LATEST {
+                version = get_last_component_version(item)?;
+            }
+            return TaskManager::default().start(vec![
+                Box::new(UserVersionTask { input_data: UserVersionData { version, component }}),
+            ], &config, &mut term, L1);
+        }
 
         if version == LATEST {
             let last_tag = get_last_tag(&config.build_cardano_node.cnode_release);
@@ -30,10 +49,28 @@ impl CommandStrategy for Use{
             }
         };
 
-        TaskManager::default().start(vec![
-            Box::new(ServicesManagerTask { input_data: ServicesAction::STOP }),
-            Box::new(UserVersionTask { input_data: UserVersionData { version }}),
-            Box::new(DeploySystemTask { }),
-        ], &config, &mut term, L1)
+        use_node_version(version, command.contains_id(Args::ACCEPTREPLAY._to_string()), !command.contains_id(Args::NORESTART._to_string()), command.contains_id(Args::FORCE._to_string()), &config, &mut term)
+    }
+}
+
+/// Switch cardano-node to an installed version, the node is started again and checked when it was running
+pub fn use_node_version(version: String, accept_replay: bool, restart: bool, force: bool, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    verify_db_switch(&version, accept_replay, config)?;
+
+    escalate_for_services()?;
+
+    // a node that was stopped before the switch stays stopped
+    let restart = restart && services_active(config);
+    let start_timeout = get_settings(&Folder::get_cvm_home()?)?.service.start_timeout;
+
+    let mut tasks: Vec<Box<dyn Task>> = vec![
+        Box::new(ServicesManagerTask { input_data: ServicesData { action: ServicesAction::STOP, guard: !force } }),
+        Box::new(UserVersionTask { input_data: UserVersionData { version, component: String::new() }}),
+        Box::new(DeploySystemTask { }),
+    ];
+    if restart {
+        tasks.push(Box::new(ServicesManagerTask { input_data: ServicesData { action: ServicesAction::START, guard: false } }));
+        tasks.push(Box::new(HealthCheckTask { input_data: HealthCheckData { down_since: Instant::now(), timeout: Duration::from_secs(start_timeout) }}));
     }
-}
\ No newline at end of file
+    TaskManager::default().start(tasks, config, term, L1)
+}
@@ -3,3 +3,4 @@ pub mod empty_task;
 pub mod folder_manager_task;
 pub mod file_manager_task;
 pub mod permission_task;
+pub mod sed_task;
@@ -166,7 +166,6 @@ pub enum Cmd {
     List,
     Bash,
     Sh,
-    Sed,
     Set,
     Ghc,
     Cabal,
@@ -185,6 +184,15 @@ pub enum Cmd {
     Clean,
     Help,
     Sudo,
+    Enable,
+    Disable,
+    Now,
+    Nix,
+    Rm,
+    User,
+    Loginctl,
+    EnableLinger,
+    Journalctl,
 }
 
 impl Cmd {
@@ -197,7 +205,6 @@ impl Cmd {
             Cmd::List => "list".to_string(),
             Cmd::Bash => "bash".to_string(),
             Cmd::Sh => "sh".to_string(),
-            Cmd::Sed => "sed".to_string(),
             Cmd::Set => "set".to_string(),
             Cmd::Ghc => "ghc".to_string(),
             Cmd::Cabal => "cabal".to_string(),
@@ -216,6 +223,15 @@ impl Cmd {
             Cmd::Help => {"help".to_string()}
             Cmd::Sudo => {"sudo".to_string()}
             Cmd::V2Update => {"v2-update".to_string()}
+            Cmd::Enable => {"enable".to_string()}
+            Cmd::Disable => {"disable".to_string()}
+            Cmd::Now => {"--now".to_string()}
+            Cmd::Nix => {"nix".to_string()}
+            Cmd::Rm => {"rm".to_string()}
+            Cmd::User => {"--user".to_string()}
+            Cmd::Loginctl => {"loginctl".to_string()}
+            Cmd::EnableLinger => {"enable-linger".to_string()}
+            Cmd::Journalctl => {"journalctl".to_string()}
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::path::Path;
+use crate::env::Env;
+use crate::{MessageData, Success, Term};
+use crate::config::remote_config::RemoteConfig;
+use crate::message::message::Message;
+use crate::task::task::Task;
+use crate::task::task_type::TaskType;
+use crate::utils::sed::SedScript;
+
+/// Edit a file with a sed script of the remote config, in process instead of `sed -i`.
+/// The file is only written when every expression found what it edits
+pub struct SedTask {
+    pub input_data: SedData,
+}
+
+pub struct SedData {
+    pub file_path: String,
+    pub script: String,
+    pub description: String,
+}
+
+impl Task for SedTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        Ok(!self.input_data.script.is_empty())
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let data = &self.input_data;
+        let name = Path::new(&data.file_path).file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+
+        let script = SedScript::parse(&data.script).map_err(|error| self.error(format!("The sed script of {} is not valid, {}", name, error)))?;
+        let (text, reports) = script.apply(&fs::read_to_string(&data.file_path)?);
+
+        for report in reports.iter() {
+            println!("      {} substitutions in {} lines  {}", report.substitutions, report.lines, report.expression);
+        }
+
+        let unmatched: Vec<String> = reports.iter().filter(|report| !report.matched()).map(|report| report.expression.to_string()).collect();
+        if !unmatched.is_empty() {
+            return Err(self.error(format!("The upstream {} changed, these expressions match nothing: {}", name, unmatched.join("  "))));
+        }
+
+        fs::write(&data.file_path, text)?;
+        Ok(Success {})
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::EditFile(self.input_data.description.to_string())
+    }
+}
+
+impl SedTask {
+    fn error(&self, message: String) -> Message {
+        Message::EditFile(MessageData {
+            message,
+            task: self.get_type(),
+            ..Default::default()
+        })
+    }
+}
@@ -17,9 +17,10 @@ use crate::config::state_config::{get_state, update_init_files};
 use crate::message::message::Message;
 use crate::task::task_impl::commons::file_manager_task::{FileManagerAction, FileManagerTask};
 use crate::utils::folders::Folder;
-use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData, RunCommandTask};
+use crate::task::task_impl::commons::sed_task::{SedData, SedTask};
 use crate::task_manager::task_manager::TaskManager;
 use crate::term::log_level::LogLevel::L2;
+use crate::utils::json_patch::apply_json_patch_file;
 use crate::utils::download_manager::download;
 
 pub struct UpdateConfigFilesTask {
@@ -70,7 +71,8 @@ fn download_config_files(update_config_file_task: &UpdateConfigFilesTask, items:
         let local_file = url_build(vec![&folder_path, &item.name.clone()], false);
         let remote_file = download_remote_file(&item)?;
 
-        apply_pattern_sed(&remote_file, &item.pattern_sed, config, term)?;
+        apply_pattern_sed(&remote_file, item.get_pattern_sed(), config, term)?;
+        apply_json_patch_file(&remote_file, &item.json_patch, config)?;
 
         if local_file_was_modify_manualy(&local_file)? && !update_config_file_task.force {
             continue;
@@ -146,11 +148,8 @@ fn download_remote_file(item: &&ConfigFileItem) -> Result<String, Message> {
 fn apply_pattern_sed(file_path: &String, pattern: &String, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
     if pattern.is_empty() { return Ok(Success {}); }
 
-    let args = vec!["-i".to_string(), pattern.to_string(), file_path.to_string()];
+    let description = format!("Editing {}", Path::new(file_path).file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default());
     TaskManager::default().start(vec![
-        Box::new(RunCommandTask {
-            input_data: RunCommandInputData { command: Cmd::Sed.as_string(), args, ..Default::default() },
-            command_description: "".to_string(),
-        }),
+        Box::new(SedTask { input_data: SedData { file_path: file_path.to_string(), script: Folder::fill_root(pattern, config), description } }),
     ], config, term, L2)
 }
\ No newline at end of file
@@ -0,0 +1,184 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use crate::env::Env;
+use crate::{MessageData, Success, Term, url_build};
+use crate::config::remote_config::RemoteConfig;
+use crate::config::state_config::get_state;
+use crate::message::message::{Message, MessageKind};
+use crate::task::task::Task;
+use crate::task::task_impl::db::manifest::{join_path, BackupMode, build_manifest_files, Manifest, ManifestFile, read_manifest, write_manifest};
+use crate::task::task_type::TaskType;
+use crate::utils::time_utils::utc_timestamp;
+use crate::utils::user::chown_to_user;
+
+pub const BACKUP_PREFIX: &str = "db-";
+pub const ARCHIVE_FILE: &str = "db.tar.gz";
+pub const COPY_FOLDER: &str = "db";
+const PARTIAL_EXTENSION: &str = "partial";
+
+pub struct BackupDbTask {
+    pub input_data: BackupDbData,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BackupDbData {
+    pub db_folder: String,
+    pub backup_dir: String,
+    pub mode: BackupMode,
+    /// Backups kept after this one is created, 0 keeps all of them
+    pub keep: usize,
+}
+
+impl Task for BackupDbTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        if !Path::new(&self.input_data.db_folder).exists() {
+            return Err(Message::DbBackup(MessageData {
+                message: format!("The chain database {} does not exist, there is nothing to back up", self.input_data.db_folder),
+                task: self.get_type(),
+                ..Default::default()
+            }));
+        }
+        fs::create_dir_all(&self.input_data.backup_dir)?;
+        Ok(true)
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let data = &self.input_data;
+        let name = format!("{}{}", BACKUP_PREFIX, utc_timestamp());
+        let backup_folder = url_build(vec![&data.backup_dir, &name], false);
+
+        // the backup is written in a partial folder and renamed when it is complete,
+        // so an interrupted backup is never listed or restored
+        let partial_folder = format!("{}.{}", backup_folder, PARTIAL_EXTENSION);
+        fs::create_dir_all(&partial_folder)?;
+
+        let result = match data.mode {
+            BackupMode::ARCHIVE => create_archive(&data.db_folder, &partial_folder),
+            BackupMode::INCREMENTAL => create_incremental(&data.db_folder, &data.backup_dir, &partial_folder),
+        };
+
+        if let Err(error) = result {
+            let _ = fs::remove_dir_all(&partial_folder);
+            return Err(error);
+        }
+
+        fs::rename(&partial_folder, &backup_folder)?;
+        chown_to_user(&backup_folder)?;
+        println!("  Backup created in {}", backup_folder);
+
+        prune_backups(&data.backup_dir, data.keep)
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::BackupDb
+    }
+}
+
+fn create_archive(db_folder: &String, backup_folder: &String) -> Result<Success, Message> {
+    let files = build_manifest_files(db_folder, None)?;
+
+    let archive_path = url_build(vec![backup_folder, &ARCHIVE_FILE.to_string()], false);
+    let encoder = GzEncoder::new(File::create(&archive_path)?, Compression::default());
+    let mut archive = tar::Builder::new(encoder);
+    archive.append_dir_all(".", db_folder)?;
+    archive.into_inner()?.finish()?.sync_all()?;
+
+    write_manifest(backup_folder, &new_manifest(BackupMode::ARCHIVE, files))
+}
+
+/// Copy the db folder hard linking the files that did not change since the last incremental backup,
+/// every backup folder is a complete copy but only the new files use disk space
+fn create_incremental(db_folder: &String, backup_dir: &String, backup_folder: &String) -> Result<Success, Message> {
+    let previous = list_backups(backup_dir)?.into_iter().rev()
+        .map(|name| url_build(vec![backup_dir, &name], false))
+        .find_map(|folder| read_manifest(&folder).ok().filter(|manifest| manifest.mode == BackupMode::INCREMENTAL).map(|manifest| (folder, manifest)));
+
+    let files = build_manifest_files(db_folder, previous.as_ref().map(|(_, manifest)| manifest))?;
+    let copy_folder = url_build(vec![backup_folder, &COPY_FOLDER.to_string()], false);
+
+    let mut linked = 0;
+    for file in &files {
+        let origin = join_path(db_folder, &file.path);
+        let destination = join_path(&copy_folder, &file.path);
+        if let Some(parent) = Path::new(&destination).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let unchanged = previous.as_ref()
+            .and_then(|(folder, manifest)| manifest.find(&file.path).filter(|old| old.sha256 == file.sha256).map(|_| folder));
+
+        match unchanged {
+            Some(folder) => {
+                let old_copy = join_path(&url_build(vec![folder, &COPY_FOLDER.to_string()], false), &file.path);
+                if fs::hard_link(&old_copy, &destination).is_ok() {
+                    linked += 1;
+                    continue;
+                }
+                fs::copy(&origin, &destination)?;
+            }
+            None => { fs::copy(&origin, &destination)?; }
+        }
+    }
+
+    if previous.is_some() {
+        println!("  {} of {} files unchanged since the previous backup", linked, files.len());
+    }
+    write_manifest(backup_folder, &new_manifest(BackupMode::INCREMENTAL, files))
+}
+
+fn new_manifest(mode: BackupMode, files: Vec<ManifestFile>) -> Manifest {
+    Manifest {
+        created: utc_timestamp(),
+        mode,
+        node_version: get_state().map(|state| state.r#use.version).unwrap_or_default(),
+        files,
+    }
+}
+
+/// Names of the complete backups, the oldest first
+pub fn list_backups(backup_dir: &String) -> Result<Vec<String>, Message> {
+    if !Path::new(backup_dir).exists() {
+        return Ok(vec![]);
+    }
+
+    let mut backups: Vec<String> = fs::read_dir(backup_dir)?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_str().unwrap_or_default().to_string())
+        .filter(|name| name.starts_with(BACKUP_PREFIX) && !name.ends_with(PARTIAL_EXTENSION))
+        .collect();
+    backups.sort();
+    Ok(backups)
+}
+
+fn prune_backups(backup_dir: &String, keep: usize) -> Result<Success, Message> {
+    if keep == 0 {
+        return Ok(Success {});
+    }
+
+    let backups = list_backups(backup_dir)?;
+    if backups.len() <= keep {
+        return Ok(Success {});
+    }
+
+    for name in &backups[..backups.len() - keep] {
+        fs::remove_dir_all(url_build(vec![backup_dir, name], false)).map_err(|error| Message::DbBackup(MessageData {
+            message: format!("Error removing the old backup {}", name),
+            stack: vec![error.to_string()],
+            kind: MessageKind::Warning,
+            ..Default::default()
+        }))?;
+        println!("  Removed old backup {}", name);
+    }
+    Ok(Success {})
+}
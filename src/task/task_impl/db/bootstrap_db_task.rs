@@ -0,0 +1,175 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use flate2::read::GzDecoder;
+use tar::Archive;
+use crate::env::Env;
+use crate::{MessageData, Success, Term, url_build};
+use crate::config::remote_config::RemoteConfig;
+use crate::message::message::{Message, MessageKind};
+use crate::task::task::Task;
+use crate::task::task_type::TaskType;
+use crate::utils::download_manager::{download_stream, download_text};
+use crate::utils::file_utils::HashReader;
+use crate::utils::user::chown_to_user;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const DIGEST_EXTENSION: &str = "sha256";
+const BOOTSTRAP_EXTENSION: &str = "bootstrap";
+const OLD_EXTENSION: &str = "old";
+const DB_FOLDER_NAME: &str = "db";
+
+pub struct BootstrapDbTask {
+    pub input_data: BootstrapDbData,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BootstrapDbData {
+    /// Url or local path of a tar or tar.gz archive of the db folder
+    pub source: String,
+    /// Expected sha256 of the archive, when empty it is read from `<source>.sha256`
+    pub digest: String,
+    pub db_folder: String,
+    pub force: bool,
+}
+
+impl Task for BootstrapDbTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        if !self.input_data.force && !is_empty_folder(&self.input_data.db_folder)? {
+            return Err(bootstrap_error(format!("The chain database {} is not empty, use --force to replace it", self.input_data.db_folder)));
+        }
+
+        if self.input_data.digest.is_empty() {
+            self.input_data.digest = read_digest(&self.input_data.source)?;
+        }
+        self.input_data.digest = self.input_data.digest.to_lowercase();
+        Ok(true)
+    }
+
+    /// The archive is hashed while it is unpacked next to the db, so a remote snapshot is read only once.
+    /// The db is replaced only when the digest matches
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let data = &self.input_data;
+        let bootstrap_folder = format!("{}.{}", data.db_folder, BOOTSTRAP_EXTENSION);
+        let old_folder = format!("{}.{}", data.db_folder, OLD_EXTENSION);
+
+        if Path::new(&bootstrap_folder).exists() {
+            fs::remove_dir_all(&bootstrap_folder)?;
+        }
+        fs::create_dir_all(&bootstrap_folder)?;
+
+        let result = open_source(&data.source)
+            .and_then(|reader| unpack_and_hash(reader, &bootstrap_folder))
+            .and_then(|digest| check_digest(&digest, &data.digest));
+
+        if let Err(error) = result {
+            let _ = fs::remove_dir_all(&bootstrap_folder);
+            return Err(error);
+        }
+
+        let snapshot_folder = find_db_root(&bootstrap_folder)?;
+
+        if Path::new(&old_folder).exists() {
+            fs::remove_dir_all(&old_folder)?;
+        }
+        if Path::new(&data.db_folder).exists() {
+            fs::rename(&data.db_folder, &old_folder)?;
+        }
+        fs::rename(&snapshot_folder, &data.db_folder)?;
+        fs::remove_dir_all(&bootstrap_folder).ok();
+        fs::remove_dir_all(&old_folder).ok();
+
+        chown_to_user(&data.db_folder)?;
+        Ok(Success {})
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        if is_empty_folder(&self.input_data.db_folder)? {
+            return Err(bootstrap_error(format!("The snapshot did not contain any file, {} is empty", self.input_data.db_folder)));
+        }
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::BootstrapDb
+    }
+}
+
+fn is_remote(source: &String) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+fn open_source(source: &String) -> Result<Box<dyn Read>, Message> {
+    if is_remote(source) {
+        return download_stream(source);
+    }
+
+    match File::open(source) {
+        Ok(file) => Ok(Box::new(file)),
+        Err(error) => Err(bootstrap_error(format!("The snapshot {} could not be opened: {}", source, error))),
+    }
+}
+
+/// The digest published next to the snapshot, in the `sha256sum` format or just the hash
+fn read_digest(source: &String) -> Result<String, Message> {
+    let digest_source = format!("{}.{}", source, DIGEST_EXTENSION);
+    let text = if is_remote(source) { download_text(&digest_source).ok() } else { fs::read_to_string(&digest_source).ok() };
+
+    match text.as_ref().and_then(|text| text.split_whitespace().next()) {
+        Some(digest) => Ok(digest.to_string()),
+        None => Err(bootstrap_error(format!("The digest {} was not found, pass the sha256 of the snapshot with --digest", digest_source))),
+    }
+}
+
+fn unpack_and_hash(reader: Box<dyn Read>, folder: &String) -> Result<String, Message> {
+    let mut buffer = BufReader::new(HashReader::new(reader));
+    let is_gzip = buffer.fill_buf()?.starts_with(&GZIP_MAGIC);
+
+    let hash_reader = if is_gzip {
+        unpack(GzDecoder::new(buffer), folder)?.into_inner().into_inner()
+    } else {
+        unpack(buffer, folder)?.into_inner()
+    };
+    hash_reader.finish()
+}
+
+fn unpack<R: Read>(reader: R, folder: &String) -> Result<R, Message> {
+    let mut archive = Archive::new(reader);
+    archive.unpack(folder).map_err(|error| bootstrap_error(format!("The snapshot is not a valid tar archive: {}", error)))?;
+    Ok(archive.into_inner())
+}
+
+fn check_digest(digest: &String, expected: &String) -> Result<Success, Message> {
+    if digest != expected {
+        return Err(bootstrap_error(format!("The snapshot digest {} does not match the expected {}", digest, expected)));
+    }
+    Ok(Success {})
+}
+
+/// Snapshots are published with the files at the top or inside a db folder
+fn find_db_root(folder: &String) -> Result<String, Message> {
+    let nested = url_build(vec![folder, &DB_FOLDER_NAME.to_string()], false);
+    if Path::new(&nested).is_dir() && fs::read_dir(folder)?.count() == 1 {
+        return Ok(nested);
+    }
+    Ok(folder.to_string())
+}
+
+fn is_empty_folder(folder: &String) -> Result<bool, Message> {
+    if !Path::new(folder).exists() {
+        return Ok(true);
+    }
+    Ok(fs::read_dir(folder)?.next().is_none())
+}
+
+fn bootstrap_error(message: String) -> Message {
+    Message::DbBackup(MessageData {
+        message,
+        kind: MessageKind::Error,
+        ..Default::default()
+    })
+}
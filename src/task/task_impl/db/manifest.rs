@@ -0,0 +1,133 @@
+#![allow(dead_code, unused_variables)]
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use crate::{Message, MessageData, Success, url_build};
+use crate::message::message::MessageKind;
+use crate::utils::file_utils::{sha256_file, write_file_atomic};
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub enum BackupMode {
+    /// Single compressed archive `db.tar.gz`
+    ARCHIVE,
+    /// Plain copy of the db folder, files unchanged since the previous incremental backup are hard links
+    INCREMENTAL,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Manifest {
+    pub created: String,
+    pub mode: BackupMode,
+    /// cardano-node version in use when the backup was taken
+    pub node_version: String,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ManifestFile {
+    /// Path relative to the db folder
+    pub path: String,
+    pub size: u64,
+    pub modified: u64,
+    pub sha256: String,
+}
+
+impl Manifest {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+
+    pub fn find(&self, path: &String) -> Option<&ManifestFile> {
+        self.files.iter().find(|file| &file.path == path)
+    }
+}
+
+/// Describe every file inside the folder.
+/// Files with the same size and modification time as in the `previous` manifest reuse its hash,
+/// chain files are immutable once written so most of them are not hashed again
+pub fn build_manifest_files(folder: &String, previous: Option<&Manifest>) -> Result<Vec<ManifestFile>, Message> {
+    let previous_files: HashMap<&String, &ManifestFile> = match previous {
+        Some(manifest) => manifest.files.iter().map(|file| (&file.path, file)).collect(),
+        None => HashMap::new(),
+    };
+
+    let mut files = vec![];
+    for entry in WalkDir::new(folder).sort_by_file_name() {
+        let entry = entry.map_err(|error| manifest_error(format!("Error reading {}: {}", folder, error)))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let metadata = entry.metadata().map_err(|error| manifest_error(error.to_string()))?;
+        let path = relative_path(folder, entry.path());
+        let modified = metadata.modified()?.duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0);
+
+        let sha256 = match previous_files.get(&path) {
+            Some(file) if file.size == metadata.len() && file.modified == modified => file.sha256.clone(),
+            _ => sha256_file(&entry.path().to_str().unwrap().to_string())?,
+        };
+
+        files.push(ManifestFile { path, size: metadata.len(), modified, sha256 });
+    }
+    Ok(files)
+}
+
+/// Check that the folder contains exactly the files of the manifest with the same content
+pub fn verify_manifest(folder: &String, manifest: &Manifest) -> Result<Success, Message> {
+    let mut count = 0;
+    for entry in WalkDir::new(folder).into_iter().flatten() {
+        if entry.file_type().is_file() {
+            count += 1;
+        }
+    }
+
+    if count != manifest.files.len() {
+        return Err(manifest_error(format!("The backup has {} files but the manifest lists {}", count, manifest.files.len())));
+    }
+
+    for file in &manifest.files {
+        let path = join_path(folder, &file.path);
+        if !Path::new(&path).exists() {
+            return Err(manifest_error(format!("The file {} is missing in the backup", file.path)));
+        }
+        if sha256_file(&path)? != file.sha256 {
+            return Err(manifest_error(format!("The file {} is corrupted, its hash does not match the manifest", file.path)));
+        }
+    }
+    Ok(Success {})
+}
+
+pub fn read_manifest(backup_folder: &String) -> Result<Manifest, Message> {
+    let path = url_build(vec![backup_folder, &MANIFEST_FILE.to_string()], false);
+    let text = fs::read_to_string(&path).map_err(|_| manifest_error(format!("The manifest {} was not found", path)))?;
+    serde_json::from_str(&text).map_err(|error| manifest_error(format!("The manifest {} is not valid: {}", path, error)))
+}
+
+pub fn write_manifest(backup_folder: &String, manifest: &Manifest) -> Result<Success, Message> {
+    let path = url_build(vec![backup_folder, &MANIFEST_FILE.to_string()], false);
+    let text = serde_json::to_string_pretty(manifest).map_err(|error| manifest_error(error.to_string()))?;
+    write_file_atomic(&path, text.as_bytes())
+}
+
+/// Paths inside the db come from the file system, they are joined without url_build format placeholders
+pub fn join_path(folder: &String, path: &String) -> String {
+    format!("{}/{}", folder.trim_end_matches('/'), path)
+}
+
+fn relative_path(folder: &String, path: &Path) -> String {
+    path.strip_prefix(folder).unwrap_or(path).to_str().unwrap().to_string()
+}
+
+fn manifest_error(message: String) -> Message {
+    Message::DbBackup(MessageData {
+        message,
+        kind: MessageKind::Error,
+        ..Default::default()
+    })
+}
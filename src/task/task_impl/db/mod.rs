@@ -0,0 +1,5 @@
+pub mod manifest;
+pub mod backup_db_task;
+pub mod restore_db_task;
+pub mod schedule_backup_task;
+pub mod bootstrap_db_task;
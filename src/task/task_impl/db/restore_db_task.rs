@@ -0,0 +1,106 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+use flate2::read::GzDecoder;
+use tar::Archive;
+use crate::env::Env;
+use crate::{MessageData, Success, Term, url_build};
+use crate::config::remote_config::RemoteConfig;
+use crate::message::message::Message;
+use crate::task::task::Task;
+use crate::task::task_impl::db::backup_db_task::{ARCHIVE_FILE, COPY_FOLDER};
+use crate::task::task_impl::db::manifest::{join_path, BackupMode, Manifest, read_manifest, verify_manifest};
+use crate::task::task_type::TaskType;
+use crate::utils::user::chown_to_user;
+
+const RESTORE_EXTENSION: &str = "restore";
+const OLD_EXTENSION: &str = "old";
+
+pub struct RestoreDbTask {
+    pub input_data: RestoreDbData,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RestoreDbData {
+    pub backup_folder: String,
+    pub db_folder: String,
+}
+
+impl Task for RestoreDbTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        Ok(true)
+    }
+
+    /// The backup is unpacked next to the db and verified against its manifest,
+    /// the current db is only replaced when the restored copy is complete
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let data = &self.input_data;
+        let manifest = read_manifest(&data.backup_folder)?;
+        let restore_folder = format!("{}.{}", data.db_folder, RESTORE_EXTENSION);
+        let old_folder = format!("{}.{}", data.db_folder, OLD_EXTENSION);
+
+        if Path::new(&restore_folder).exists() {
+            fs::remove_dir_all(&restore_folder)?;
+        }
+
+        let result = unpack(&data.backup_folder, &manifest, &restore_folder)
+            .and_then(|_| verify_manifest(&restore_folder, &manifest));
+
+        if let Err(error) = result {
+            let _ = fs::remove_dir_all(&restore_folder);
+            return Err(error);
+        }
+
+        if Path::new(&old_folder).exists() {
+            fs::remove_dir_all(&old_folder)?;
+        }
+        if Path::new(&data.db_folder).exists() {
+            fs::rename(&data.db_folder, &old_folder)?;
+        }
+        fs::rename(&restore_folder, &data.db_folder)?;
+        fs::remove_dir_all(&old_folder).ok();
+
+        chown_to_user(&data.db_folder)?;
+        Ok(Success {})
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        if !Path::new(&self.input_data.db_folder).exists() {
+            return Err(Message::DbBackup(MessageData {
+                message: format!("The chain database {} was not restored", self.input_data.db_folder),
+                task: self.get_type(),
+                ..Default::default()
+            }));
+        }
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::RestoreDb
+    }
+}
+
+fn unpack(backup_folder: &String, manifest: &Manifest, restore_folder: &String) -> Result<Success, Message> {
+    fs::create_dir_all(restore_folder)?;
+    match manifest.mode {
+        BackupMode::ARCHIVE => {
+            let archive_path = url_build(vec![backup_folder, &ARCHIVE_FILE.to_string()], false);
+            let mut archive = Archive::new(GzDecoder::new(File::open(archive_path)?));
+            archive.unpack(restore_folder)?;
+        }
+        BackupMode::INCREMENTAL => {
+            let copy_folder = url_build(vec![backup_folder, &COPY_FOLDER.to_string()], false);
+            for file in &manifest.files {
+                let destination = join_path(restore_folder, &file.path);
+                if let Some(parent) = Path::new(&destination).parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(join_path(&copy_folder, &file.path), &destination)?;
+            }
+        }
+    }
+    Ok(Success {})
+}
@@ -0,0 +1,108 @@
+#![allow(dead_code, unused_variables)]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use crate::env::Env;
+use crate::{Success, Term};
+use crate::config::remote_config::RemoteConfig;
+use crate::message::message::{Message, MessageData};
+use crate::task::task::Task;
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandTask};
+use crate::service_manager::service_manager_impl::systemd::{build_systemctl_command, environment_value, exec_command_line, unit_path, write_unit};
+use crate::service_manager::{get_service_manager, SYSTEMD};
+use crate::task::task_type::TaskType;
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L2;
+use crate::utils::folders::Folder;
+use crate::utils::user::get_current_user;
+
+pub const BACKUP_UNIT: &str = "cvm-db-backup";
+const SERVICE_TEMPLATE: &str = include_str!("../../../../config/systemd/cvm-db-backup.service");
+const TIMER_TEMPLATE: &str = include_str!("../../../../config/systemd/cvm-db-backup.timer");
+
+pub struct ScheduleBackupTask {
+    pub input_data: ScheduleBackupAction,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ScheduleBackupAction {
+    /// systemd calendar expression and the arguments passed to [cvm db backup], one flag or value each
+    Enable(String, Vec<String>),
+    Disable,
+}
+
+impl Task for ScheduleBackupTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        let service_manager = get_service_manager()?.name();
+        if service_manager != SYSTEMD {
+            return Err(Message::DbBackup(MessageData {
+                message: format!("Scheduled backups use a systemd timer and the node is managed by {}, run [cvm db backup] from cron instead", service_manager),
+                task: self.get_type(),
+                ..Default::default()
+            }));
+        }
+        Ok(true)
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let timer = format!("{}.timer", BACKUP_UNIT);
+
+        match &self.input_data {
+            ScheduleBackupAction::Enable(calendar, args) => {
+                let cvm = env::current_exe()?.to_str().unwrap().to_string();
+                let command = [vec![cvm, "db".to_string(), "backup".to_string()], args.clone()].concat();
+                let context = ScheduleContext {
+                    command: exec_command_line(&command),
+                    calendar: calendar.to_string(),
+                    user: environment_value(&get_current_user()?),
+                    cvm_home: environment_value(&Folder::get_cvm_home()?),
+                    workspace: environment_value(&Folder::get_workspaces_dir()),
+                    rootless: Folder::is_rootless(),
+                };
+                fs::create_dir_all(Folder::get_systemd_dir()?)?;
+                write_unit(&format!("{}.service", BACKUP_UNIT), SERVICE_TEMPLATE, &context)?;
+                write_unit(&timer, TIMER_TEMPLATE, &context)?;
+
+                TaskManager::default().start(vec![
+                    Box::new(RunCommandTask { input_data: build_systemctl_command(vec![Cmd::DaemonReload.as_string()]), command_description: "Reset systemctl daemon".to_string() }),
+                    Box::new(RunCommandTask { input_data: build_systemctl_command(vec![Cmd::Enable.as_string(), Cmd::Now.as_string(), timer]), command_description: "Enable the backup timer".to_string() }),
+                ], config, term, L2)
+            }
+            ScheduleBackupAction::Disable => {
+                TaskManager::default().start(vec![
+                    Box::new(RunCommandTask { input_data: build_systemctl_command(vec![Cmd::Disable.as_string(), Cmd::Now.as_string(), timer]), command_description: "Disable the backup timer".to_string() }),
+                ], config, term, L2)?;
+
+                for extension in ["service", "timer"] {
+                    let unit = unit_path(&format!("{}.{}", BACKUP_UNIT, extension))?;
+                    if Path::new(&unit).exists() {
+                        fs::remove_file(&unit)?;
+                    }
+                }
+                Ok(Success {})
+            }
+        }
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::ScheduleBackup
+    }
+}
+
+#[derive(Serialize)]
+struct ScheduleContext {
+    /// cvm and its arguments quoted for ExecStart
+    command: String,
+    calendar: String,
+    user: String,
+    cvm_home: String,
+    workspace: String,
+    rootless: bool,
+}
@@ -17,9 +17,10 @@ use crate::config::state_config::{add_init_file, get_task_complete, set_task_com
 use crate::message::message::Message;
 use crate::task::task_impl::commons::file_manager_task::{FileManagerAction, FileManagerTask};
 use crate::utils::folders::Folder;
-use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData, RunCommandTask};
+use crate::task::task_impl::commons::sed_task::{SedData, SedTask};
 use crate::task_manager::task_manager::TaskManager;
 use crate::term::log_level::LogLevel::L2;
+use crate::utils::json_patch::apply_json_patch_file;
 use crate::utils::download_manager::download_in_path;
 
 #[derive(Default)]
@@ -84,9 +85,10 @@ fn download_config_files(network: &String, items: &Vec<ConfigFileItem>, config:
             download_in_path(&item.url, folder_path.to_string(), item.name.clone())?;
         }
 
-        if item.pattern_sed != "" {
-            apply_pattern_sed(url_build(vec![&folder_path, &item.name], false), &item.pattern_sed, config, term)?;
+        if !item.get_pattern_sed().is_empty() {
+            apply_pattern_sed(url_build(vec![&folder_path, &item.name], false), item.get_pattern_sed(), config, term)?;
         }
+        apply_json_patch_file(&file_path, &item.json_patch, config)?;
 
         if item.folder_key == Folder::SCRIPTS.to_string() {
             fs::set_permissions(&file_path, fs::Permissions::from_mode(0o755))?;
@@ -98,11 +100,8 @@ fn download_config_files(network: &String, items: &Vec<ConfigFileItem>, config:
 }
 
 fn apply_pattern_sed(file_path: String, pattern: &String, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
-    let args = vec!["-i".to_string(), pattern.to_string(), file_path.to_string()];
+    let description = format!("Editing {}", Path::new(&file_path).file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default());
     TaskManager::default().start(vec![
-        Box::new(RunCommandTask {
-            input_data: RunCommandInputData { command: Cmd::Sed.as_string(), args, ..Default::default() },
-            command_description: "".to_string(),
-        }),
+        Box::new(SedTask { input_data: SedData { file_path, script: Folder::fill_root(pattern, config), description } }),
     ], config, term, L2)
 }
\ No newline at end of file
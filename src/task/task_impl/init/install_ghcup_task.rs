@@ -10,9 +10,11 @@ use crate::task::task_type::TaskType;
 use crate::{Term, url_build};
 use crate::config::state_config::{get_task_complete, set_task_complete};
 use crate::task::task_impl::commons::file_manager_task::{FileManagerAction, FileManagerTask};
+use crate::task::task_impl::commons::sed_task::{SedData, SedTask};
 use crate::term::log_level::LogLevel::L2;
 use crate::utils::download_manager::download;
-use crate::utils::folders::Folder;
+use crate::utils::folders::{Folder, GHCUP_PREFIX_ENV};
+use crate::utils::user::as_root;
 
 #[derive(Default)]
 pub struct InstallHanskellGhcTask {
@@ -35,13 +37,13 @@ impl Task for InstallHanskellGhcTask {
 
         self.home = Folder::get_home_dir()?;
         self.install_sh_uri = download_install_ghc_file(&config.init)?;
-        self.ghcup_dir = url_build(vec![&self.home, &config.init.ghcup_bin_path], false);
+        self.ghcup_dir = Folder::get_ghcup_bin_dir(config)?;
         Ok(true)
     }
 
     fn run(self: &Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
         TaskManager::default().start(vec![
-            Box::new(RunCommandTask { input_data: build_sed_install_file_command(&self.install_sh_uri, &config.init.ghcup_pattern_sed), command_description: "Editing ghcup installation file".to_string() }),
+            Box::new(SedTask { input_data: SedData { file_path: self.install_sh_uri.to_string(), script: config.init.ghcup_pattern_sed.to_string(), description: "Editing ghcup installation file".to_string() } }),
             Box::new(RunCommandTask { input_data: build_install_command(&self.install_sh_uri), command_description: "Installing ghcup".to_string() }),
             Box::new(RunCommandTask { input_data: build_install_ghc_version_command(&self.ghcup_dir, &config.init.haskell_ghc_version), command_description: "Installing ghc".to_string() }),
             Box::new(RunCommandTask { input_data: build_set_ghc_version_command(&self.ghcup_dir, &config.init.haskell_ghc_version), command_description: "Changing to the corresponding version of ghc".to_string() }),
@@ -51,9 +53,9 @@ impl Task for InstallHanskellGhcTask {
     }
 
     fn check(self: &Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
-        let home_dir = Folder::get_home_dir()?;
-        let cabal_bin_path = url_build(vec![&home_dir, &config.init.ghcup_bin_path, &"cabal".to_string()], false);
-        let ghc_bin_path = url_build(vec![&home_dir, &config.init.ghcup_bin_path, &"ghc".to_string()], false);
+        let ghcup_dir = Folder::get_ghcup_bin_dir(config)?;
+        let cabal_bin_path = url_build(vec![&ghcup_dir, &"cabal".to_string()], false);
+        let ghc_bin_path = url_build(vec![&ghcup_dir, &"ghc".to_string()], false);
 
         let result = TaskManager {}.start(vec![
             Box::new(FileManagerTask { input_data: FileManagerAction::Check(vec![cabal_bin_path, ghc_bin_path]) }),
@@ -71,32 +73,36 @@ fn download_install_ghc_file(init: &Init) -> Result<String, Message> {
     download(&init.ghcup_url, init.install_ghc_file.as_str())
 }
 
-fn build_sed_install_file_command(uri: &String, pattern: &String) -> RunCommandInputData {
-    let args = vec!["-i".to_string(), "-e".to_string(), pattern.to_string(), uri.to_string()];
-    RunCommandInputData { command: Cmd::Sed.as_string(), args, ..Default::default() }
-}
-
 fn build_install_command(uri: &String) -> RunCommandInputData {
     let args = vec![uri.to_string(), "&>/dev/null".to_string()];
     RunCommandInputData { command: Cmd::Bash.as_string(), args, ..Default::default() }
 }
 
-fn build_install_ghc_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
-    let args = vec![Cmd::Ghcup.as_string(), Cmd::Install.as_string(), Cmd::Ghc.as_string(), version.to_string()];
-    RunCommandInputData { command: Cmd::Sudo.as_string(), args, current_dir: ghcup_dir.to_string() }
+pub fn build_install_ghc_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
+    build_ghcup_command(ghcup_dir, vec![Cmd::Install.as_string(), Cmd::Ghc.as_string(), version.to_string()])
 }
 
-fn build_set_ghc_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
-    let args = vec![Cmd::Ghcup.as_string(), Cmd::Set.as_string(), Cmd::Ghc.as_string(), version.to_string()];
-    RunCommandInputData { command: Cmd::Sudo.as_string(), args, current_dir: ghcup_dir.to_string() }
+pub fn build_set_ghc_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
+    build_ghcup_command(ghcup_dir, vec![Cmd::Set.as_string(), Cmd::Ghc.as_string(), version.to_string()])
 }
 
-fn build_install_cabal_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
-    let args = vec![Cmd::Ghcup.as_string(), Cmd::Install.as_string(), Cmd::Cabal.as_string(), version.to_string()];
-    RunCommandInputData { command: Cmd::Sudo.as_string(), args, current_dir: ghcup_dir.to_string() }
+pub fn build_install_cabal_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
+    build_ghcup_command(ghcup_dir, vec![Cmd::Install.as_string(), Cmd::Cabal.as_string(), version.to_string()])
 }
 
-fn build_set_cabal_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
-    let args = vec![Cmd::Ghcup.as_string(), Cmd::Set.as_string(), Cmd::Cabal.as_string(), version.to_string()];
-    RunCommandInputData { command: Cmd::Sudo.as_string(), args, current_dir: ghcup_dir.to_string() }
-}
\ No newline at end of file
+pub fn build_set_cabal_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
+    build_ghcup_command(ghcup_dir, vec![Cmd::Set.as_string(), Cmd::Cabal.as_string(), version.to_string()])
+}
+
+pub fn build_remove_ghc_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
+    build_ghcup_command(ghcup_dir, vec![Cmd::Rm.as_string(), Cmd::Ghc.as_string(), version.to_string()])
+}
+
+pub fn build_remove_cabal_version_command(ghcup_dir: &String, version: &String) -> RunCommandInputData {
+    build_ghcup_command(ghcup_dir, vec![Cmd::Rm.as_string(), Cmd::Cabal.as_string(), version.to_string()])
+}
+
+/// sudo resets the environment, keep the ghcup location for the ghcup commands
+fn build_ghcup_command(ghcup_dir: &String, args: Vec<String>) -> RunCommandInputData {
+    as_root(RunCommandInputData { command: Cmd::Ghcup.as_string(), args, current_dir: ghcup_dir.to_string() }, &[GHCUP_PREFIX_ENV])
+}
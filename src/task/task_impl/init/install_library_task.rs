@@ -0,0 +1,83 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use crate::env::Env;
+use crate::{Success, Term, url_build};
+use crate::config::remote_config::{LibraryRecipe, RemoteConfig};
+use crate::config::state_config::{get_state, set_library_installed};
+use crate::message::message::Message;
+use crate::utils::folders::Folder;
+use crate::task::task::Task;
+use crate::task::task_impl::commons::file_manager_task::{FileManagerAction, FileManagerTask};
+use crate::task::task_impl::commons::folder_manager_task::{FolderManagerAction, FolderManagerTask};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData, RunCommandTask};
+use crate::task_manager::task_manager::TaskManager;
+use crate::task::task_type::TaskType;
+use crate::term::log_level::LogLevel::L2;
+
+/// Build and install any native library declared in the remote config
+#[derive(Default)]
+pub struct InstallLibraryTask {
+    pub recipe: LibraryRecipe,
+    git_folder: String,
+    library_folder: String,
+}
+
+impl Task for InstallLibraryTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        if is_library_installed(&self.recipe)? {
+            return Ok(false);
+        }
+        self.git_folder = Folder::get_path(Folder::GIT, &config);
+        self.library_folder = url_build(vec![&self.git_folder, &self.recipe.name], false);
+        Ok(true)
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let name = &self.recipe.name;
+        let mut tasks: Vec<Box<dyn Task>> = vec![
+            Box::new(FolderManagerTask { input_data: FolderManagerAction::Remove(vec![self.library_folder.clone()]) }),
+            Box::new(RunCommandTask { input_data: build_clone_repo_command(&self.recipe.repository, name, &self.git_folder), command_description: format!("Cloning {} repository", name) }),
+            Box::new(RunCommandTask { input_data: build_checkout_repo_command(&self.library_folder, &self.recipe.commit), command_description: format!("Switching to {}", self.recipe.commit) }),
+        ];
+
+        for step in &self.recipe.steps {
+            let command = self.recipe.fill_prefix(step);
+            tasks.push(Box::new(RunCommandTask { input_data: build_step_command(&command, &self.library_folder), command_description: command }));
+        }
+        TaskManager::default().start(tasks, config, term, L2)
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        TaskManager::default().start(vec![
+            Box::new(FileManagerTask { input_data: FileManagerAction::Check(self.recipe.get_files()) }),
+        ], config, term, L2)?;
+        set_library_installed(&self.recipe.name, &self.recipe.commit)
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::InstallLibrary(self.recipe.name.clone())
+    }
+}
+
+/// The library is skipped when the commit in the state is the one of the recipe and its files are still there
+pub fn is_library_installed(recipe: &LibraryRecipe) -> Result<bool, Message> {
+    let installed = get_state()?.init.libraries.get(&recipe.name) == Some(&recipe.commit);
+    Ok(installed && recipe.get_files().iter().all(|file| Path::new(file).exists()))
+}
+
+fn build_clone_repo_command(repo: &String, folder: &String, path: &String) -> RunCommandInputData {
+    let args = vec![Cmd::Clone.as_string(), repo.to_string(), folder.to_string()];
+    RunCommandInputData { command: Cmd::Git.as_string(), args, current_dir: path.to_string() }
+}
+
+fn build_checkout_repo_command(path: &String, commit: &String) -> RunCommandInputData {
+    let args = vec![Cmd::Checkout.as_string(), commit.to_string()];
+    RunCommandInputData { command: Cmd::Git.as_string(), args, current_dir: path.to_string() }
+}
+
+fn build_step_command(command: &String, path: &String) -> RunCommandInputData {
+    let args = vec!["-c".to_string(), command.to_string()];
+    RunCommandInputData { command: Cmd::Bash.as_string(), args, current_dir: path.to_string() }
+}
@@ -2,4 +2,4 @@ pub mod install_dependences_task;
 pub mod install_ghcup_task;
 pub mod create_folder_structure_task;
 pub mod download_config_files_task;
-pub mod install_libsecp256k1_task;
+pub mod install_library_task;
@@ -0,0 +1,97 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::path::Path;
+use crate::url_build;
+use crate::message::message::Message;
+use crate::config::remote_config::{get_toolchain, RemoteConfig};
+use crate::doctor::check::Check;
+use crate::doctor::check_impl::ghcup_check::GhcupCheck;
+use crate::doctor::check_impl::libraries_check::LibrariesCheck;
+use crate::task::task::Task;
+use crate::task::task_impl::commons::file_manager_task::{FileManagerAction, FileManagerTask};
+use crate::task::task_impl::commons::folder_manager_task::{FolderManagerAction, FolderManagerTask};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData, RunCommandTask};
+use crate::task::task_impl::install::build::backend::{BuildBackend, BuildSource, CABAL_BACKEND};
+use crate::task::task_impl::install::build::collect_cabal_bin_task::{CollectCabalBinData, CollectCabalBinTask};
+use crate::task::task_impl::toolchain::toolchain_task::{ToolchainAction, ToolchainTask};
+use crate::utils::folders::Folder;
+use crate::utils::user::as_root;
+
+const DIST_FOLDER: &str = "dist-newstyle";
+/// Toolchain used by the last build, kept inside the build folder
+const TOOLCHAIN_FILE: &str = "cvm-toolchain";
+/// Binaries of the last build, collected with cabal list-bin
+const OUTPUT_FOLDER: &str = "cvm-bin";
+
+/// ghc and cabal from ghcup, selected for the node version, with libsodium and secp256k1 built from source by [cvm init -b]
+pub struct CabalBackend {}
+
+impl BuildBackend for CabalBackend {
+
+    fn name(self: &Self) -> String {
+        CABAL_BACKEND.to_string()
+    }
+
+    fn build_tasks(self: &Self, source: &BuildSource, config: &RemoteConfig) -> Result<Vec<Box<dyn Task>>, Message> {
+        let ghcup_folder = Folder::get_ghcup_bin_dir(config)?;
+        let libsodium_ported_file = url_build(vec![&source.folder, &config.build_cardano_node.cnode_ported_libsodium_file_name], false);
+        let dist_folder = url_build(vec![&source.folder, &DIST_FOLDER.to_string()], false);
+        let toolchain_file = url_build(vec![&dist_folder, &TOOLCHAIN_FILE.to_string()], false);
+        let toolchain = get_toolchain(config, &source.version);
+        let toolchain_name = format!("ghc {} cabal {}", toolchain.ghc, toolchain.cabal);
+        let mut tasks: Vec<Box<dyn Task>> = vec![
+            Box::new(ToolchainTask { input_data: ToolchainAction::Use(toolchain) }),
+        ];
+
+        if !Path::new(&libsodium_ported_file).exists() || !source.local {
+            tasks.push(Box::new(FileManagerTask { input_data: FileManagerAction::CreateFileString((libsodium_ported_file, config.build_cardano_node.cnode_ported_libsodium_data.clone().to_string())) }));
+        }
+
+        // the cabal store is already split by ghc version, but the build folder has to be rebuilt when the toolchain changes
+        let same_toolchain = fs::read_to_string(&toolchain_file).map(|text| text.trim() == toolchain_name).unwrap_or(false);
+        if source.clean || !same_toolchain {
+            tasks.push(Box::new(FolderManagerTask { input_data: FolderManagerAction::Remove(vec![dist_folder.clone()]) }));
+        }
+
+        tasks.append(&mut vec![
+            Box::new(RunCommandTask { input_data: build_cabal_update_command(&ghcup_folder), command_description: "Updating cabal packages".to_string() }),
+            Box::new(RunCommandTask { input_data: build_run_cabal_command(&ghcup_folder, &source.folder, &config.binaries.required_files), command_description: "Building cardano node".to_string() }),
+            Box::new(FileManagerTask { input_data: FileManagerAction::CreateFileString((toolchain_file, toolchain_name)) }),
+            Box::new(CollectCabalBinTask {
+                input_data: CollectCabalBinData {
+                    cabal: url_build(vec![&ghcup_folder, &Cmd::Cabal.as_string()], false),
+                    source_folder: source.folder.clone(),
+                    binaries: config.binaries.required_files.clone(),
+                    output_folder: self.output_folder(source),
+                }
+            }),
+        ]);
+        Ok(tasks)
+    }
+
+    /// dist-newstyle also has the binaries of the versions built before, only the collected ones are copied
+    fn output_folder(self: &Self, source: &BuildSource) -> String {
+        url_build(vec![&source.folder, &DIST_FOLDER.to_string(), &OUTPUT_FOLDER.to_string()], false)
+    }
+
+    fn checks(self: &Self) -> Vec<Box<dyn Check>> {
+        vec![
+            Box::new(GhcupCheck {}),
+            Box::new(LibrariesCheck { required: true }),
+        ]
+    }
+}
+
+fn build_run_cabal_command(cabal_path: &String, folder_path: &String, binaries: &Vec<String>) -> RunCommandInputData {
+    let mut args: Vec<String> = vec![Cmd::Build.as_string()];
+    for binary in binaries {
+        args.push(binary.to_string());
+    }
+    RunCommandInputData { command: url_build(vec![&cabal_path, &Cmd::Cabal.as_string()], false), args, current_dir: folder_path.to_string() }
+}
+
+fn build_cabal_update_command(cabal_path: &String) -> RunCommandInputData {
+    let args: Vec<String> = vec![Cmd::V2Update.as_string()];
+    as_root(RunCommandInputData { command: url_build(vec![cabal_path, &Cmd::Cabal.as_string()], false), args, current_dir: "".to_string() }, &[])
+}
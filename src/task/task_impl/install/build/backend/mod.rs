@@ -0,0 +1,57 @@
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::Check;
+use crate::message::message::{Message, MessageData};
+use crate::task::task::Task;
+use crate::task::task_impl::install::build::backend::cabal_backend::CabalBackend;
+use crate::task::task_impl::install::build::backend::nix_backend::NixBackend;
+
+pub mod cabal_backend;
+pub mod nix_backend;
+
+pub const CABAL_BACKEND: &str = "cabal";
+pub const NIX_BACKEND: &str = "nix";
+
+/// Toolchain that compiles cardano-node from a checkout already moved to the ref to build
+pub trait BuildBackend {
+
+    fn name(self: &Self) -> String;
+
+    /// Tasks that build the binaries inside the checkout
+    /// * `BuildSource` - Checkout to build
+    /// * `RemoteConfig` - The config object
+    fn build_tasks(self: &Self, source: &BuildSource, config: &RemoteConfig) -> Result<Vec<Box<dyn Task>>, Message>;
+
+    /// Folder where the built binaries are searched by the CopyBinTask
+    fn output_folder(self: &Self, source: &BuildSource) -> String;
+
+    /// The output folder only has links to the binaries and they have to be followed to copy them
+    fn output_links(self: &Self) -> bool {
+        false
+    }
+
+    /// Checks that must pass before building with this backend
+    fn checks(self: &Self) -> Vec<Box<dyn Check>>;
+}
+
+#[derive(Default, Clone, Debug, Eq, PartialEq)]
+pub struct BuildSource {
+    pub folder: String,
+    /// Node version built, used to choose the toolchain
+    pub version: String,
+    /// The sources are a local checkout of the user instead of a clone made by cvm
+    pub local: bool,
+    /// Build from scratch, without the output of previous builds
+    pub clean: bool,
+}
+
+/// Backend selected with --backend, cabal when empty
+pub fn get_build_backend(name: &String) -> Result<Box<dyn BuildBackend>, Message> {
+    match name.as_str() {
+        "" | CABAL_BACKEND => Ok(Box::new(CabalBackend {})),
+        NIX_BACKEND => Ok(Box::new(NixBackend {})),
+        _ => Err(Message::ParseArg(MessageData {
+            message: format!("The build backend {} is not supported, the available ones are: {}, {}", name, CABAL_BACKEND, NIX_BACKEND),
+            ..Default::default()
+        }))
+    }
+}
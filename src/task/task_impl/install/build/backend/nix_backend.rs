@@ -0,0 +1,61 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::url_build;
+use crate::message::message::Message;
+use crate::config::remote_config::RemoteConfig;
+use crate::doctor::check::Check;
+use crate::doctor::check_impl::nix_check::NixCheck;
+use crate::task::task::Task;
+use crate::task::task_impl::commons::folder_manager_task::{FolderManagerAction, FolderManagerTask};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData, RunCommandTask};
+use crate::task::task_impl::install::build::backend::{BuildBackend, BuildSource, NIX_BACKEND};
+
+/// Folder inside the checkout with the links to the nix store outputs
+const RESULT_FOLDER: &str = "cvm-nix";
+const RESULT_LINK: &str = "result";
+
+/// Builds the flake of the repository, the toolchain and the native libraries come from nix
+pub struct NixBackend {}
+
+impl BuildBackend for NixBackend {
+
+    fn name(self: &Self) -> String {
+        NIX_BACKEND.to_string()
+    }
+
+    fn build_tasks(self: &Self, source: &BuildSource, config: &RemoteConfig) -> Result<Vec<Box<dyn Task>>, Message> {
+        let result_folder = self.output_folder(source);
+        Ok(vec![
+            // links left by other versions would be collected as outputs of this build
+            Box::new(FolderManagerTask { input_data: FolderManagerAction::Remove(vec![result_folder.clone()]) }),
+            Box::new(RunCommandTask { input_data: build_nix_command(&source.folder, &result_folder, &config.binaries.required_files), command_description: "Building cardano node with nix".to_string() }),
+        ])
+    }
+
+    fn output_folder(self: &Self, source: &BuildSource) -> String {
+        url_build(vec![&source.folder, &RESULT_FOLDER.to_string()], false)
+    }
+
+    fn output_links(self: &Self) -> bool {
+        true
+    }
+
+    fn checks(self: &Self) -> Vec<Box<dyn Check>> {
+        vec![Box::new(NixCheck { required: true })]
+    }
+}
+
+/// Every binary is a package of the flake, nix leaves a result, result-1... link for each one.
+/// The flake config adds the IOG binary cache, so most of the closure is downloaded instead of compiled
+fn build_nix_command(folder_path: &String, result_folder: &String, binaries: &Vec<String>) -> RunCommandInputData {
+    let mut args: Vec<String> = vec![
+        Cmd::Build.as_string(),
+        "--extra-experimental-features".to_string(), "nix-command flakes".to_string(),
+        "--accept-flake-config".to_string(),
+        "--out-link".to_string(), url_build(vec![result_folder, &RESULT_LINK.to_string()], false),
+    ];
+    for binary in binaries {
+        args.push(format!(".#{}", binary));
+    }
+    RunCommandInputData { command: Cmd::Nix.as_string(), args, current_dir: folder_path.to_string() }
+}
@@ -1,49 +1,78 @@
 #![allow(dead_code, unused_variables)]
 
+use std::path::Path;
 use crate::env::Env;
 use crate::{Success, Term, url_build};
 use crate::config::remote_config::RemoteConfig;
 use crate::message::message::Message;
 use crate::utils::folders::Folder;
 use crate::task::task::Task;
-use crate::task::task_impl::commons::file_manager_task::{FileManagerAction, FileManagerTask};
 use crate::task::task_impl::commons::folder_manager_task::{FolderManagerAction, FolderManagerTask};
+use crate::task::task_impl::install::build::backend::{BuildSource, get_build_backend};
 use crate::task::task_impl::commons::permission_task::{PermissionAction, PermissionTask};
 use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData, RunCommandTask};
 use crate::task_manager::task_manager::TaskManager;
 use crate::task::task_type::TaskType;
 use crate::term::log_level::LogLevel::L2;
+use crate::utils::git_utils::is_clone_of;
+use crate::utils::version_utils::verify_version;
 
 #[derive(Default)]
 pub struct BuildCardanoNodeTask {
     pub version: String,
+    /// Branch, tag or commit to build, the version tag when empty
+    pub git_ref: String,
+    /// Git url of a fork or path of a local checkout, the official repository when empty
+    pub repository: String,
+    /// Remove the checkout and the build folder before building
+    pub clean: bool,
+    /// Fetch only the commit to build, without the history
+    pub shallow: bool,
+    /// Build backend, cabal when empty
+    pub backend: String,
     cardano_folder: String,
-    ghcup_folder: String,
-    libsodium_ported_file: String,
     git_folder: String,
 }
 
 impl Task for BuildCardanoNodeTask {
 
     fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
-        self.cardano_folder = url_build(vec![&Folder::get_path(Folder::GIT, &config), &config.build_cardano_node.cnode_repository_name], false);
-        self.ghcup_folder = url_build(vec![&Folder::get_home_dir()?, &config.init.ghcup_bin_path], false);
-        self.libsodium_ported_file = url_build(vec![&self.cardano_folder, &config.build_cardano_node.cnode_ported_libsodium_file_name], false);
+        self.cardano_folder = get_source_folder(&self.repository, config);
         self.git_folder = Folder::get_path(Folder::GIT, &config);
         Ok(true)
     }
 
     fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
-        TaskManager::default().start(vec![
-            Box::new(PermissionTask { input_data: PermissionAction::CheckWrite(vec![self.git_folder.clone().to_string()]) }),
-            Box::new(FolderManagerTask { input_data: FolderManagerAction::Remove(vec![self.cardano_folder.clone()]) }),
-            Box::new(RunCommandTask { input_data: build_clone_repo_command(&config.build_cardano_node.cnode_repository, &self.git_folder.to_string()), command_description: "Cloning cardano node repository".to_string() }),
-            Box::new(FileManagerTask { input_data: FileManagerAction::CreateFileString((self.libsodium_ported_file.to_string(), config.build_cardano_node.cnode_ported_libsodium_data.clone().to_string()) ) }),
-            Box::new(RunCommandTask { input_data: build_fetch_all_command(&self.cardano_folder.clone()), command_description: "Fetch cardano node repository".to_string() }),
-            Box::new(RunCommandTask { input_data: build_checkout_version_command(&self.version, &self.cardano_folder), command_description: format!("changing to the version {}", &self.version) }),
-            Box::new(RunCommandTask { input_data: build_cabal_update_command(&self.ghcup_folder), command_description: "Updating cabal packages".to_string() }),
-            Box::new(RunCommandTask { input_data: build_run_cabal_command(&self.ghcup_folder, &self.cardano_folder, &config.binaries.required_files), command_description: "Building cardano node".to_string() }),
-        ], config, term, L2)
+        let git_ref = if self.git_ref.is_empty() { &self.version } else { &self.git_ref };
+        let mut tasks: Vec<Box<dyn Task>> = vec![];
+
+        if is_local_repository(&self.repository) {
+            // a local checkout is built as it is, only moved to the ref when one is passed
+            tasks.push(Box::new(PermissionTask { input_data: PermissionAction::CheckWrite(vec![self.cardano_folder.clone()]) }));
+            if !self.git_ref.is_empty() {
+                tasks.push(Box::new(RunCommandTask { input_data: build_checkout_version_command(git_ref, &self.cardano_folder), command_description: format!("changing to {}", git_ref) }));
+            }
+        } else {
+            let repository = if self.repository.is_empty() { &config.build_cardano_node.cnode_repository } else { &self.repository };
+            tasks.push(Box::new(PermissionTask { input_data: PermissionAction::CheckWrite(vec![self.git_folder.clone().to_string()]) }));
+
+            // the existing clone is updated in place so the objects already downloaded are reused
+            if self.clean || !is_clone_of(&self.cardano_folder, repository) {
+                tasks.append(&mut vec![
+                    Box::new(FolderManagerTask { input_data: FolderManagerAction::Remove(vec![self.cardano_folder.clone()]) }),
+                    Box::new(RunCommandTask { input_data: build_clone_repo_command(repository, &self.cardano_folder, &self.git_folder.to_string(), self.shallow), command_description: "Cloning cardano node repository".to_string() }),
+                ]);
+            }
+
+            tasks.append(&mut vec![
+                Box::new(RunCommandTask { input_data: build_fetch_ref_command(git_ref, &self.cardano_folder, self.shallow), command_description: format!("Fetching {}", git_ref) }),
+                Box::new(RunCommandTask { input_data: build_checkout_fetched_command(&self.cardano_folder), command_description: format!("changing to {}", git_ref) }),
+            ]);
+        }
+
+        let backend = get_build_backend(&self.backend)?;
+        tasks.append(&mut backend.build_tasks(&self.get_build_source(config), config)?);
+        TaskManager::default().start(tasks, config, term, L2)
     }
 
     fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
@@ -55,13 +84,65 @@ impl Task for BuildCardanoNodeTask {
     }
 }
 
-fn build_clone_repo_command(repo: &String, path: &String) -> RunCommandInputData {
-    let args = vec![Cmd::Clone.as_string(), repo.clone()];
+impl BuildCardanoNodeTask {
+    pub fn get_build_source(self: &Self, config: &RemoteConfig) -> BuildSource {
+        BuildSource {
+            folder: get_source_folder(&self.repository, config),
+            // a ref that is a release tag is built with the toolchain of that release
+            version: if verify_version(&self.git_ref).is_ok() { self.git_ref.clone() } else { self.version.clone() },
+            local: is_local_repository(&self.repository),
+            clean: self.clean,
+        }
+    }
+}
+
+/// Folder with the sources to build: the local checkout, or the clone of the repository inside the git folder
+pub fn get_source_folder(repository: &String, config: &RemoteConfig) -> String {
+    if is_local_repository(repository) {
+        return repository.trim_end_matches('/').to_string();
+    }
+
+    let name = if repository.is_empty() || repository == &config.build_cardano_node.cnode_repository {
+        config.build_cardano_node.cnode_repository_name.clone()
+    } else {
+        fork_folder_name(repository)
+    };
+    url_build(vec![&Folder::get_path(Folder::GIT, config), &name], false)
+}
+
+pub fn is_local_repository(repository: &String) -> bool {
+    !repository.is_empty() && Path::new(repository).is_dir()
+}
+
+/// Forks are cloned apart from the official repository as <owner>-<repository>
+fn fork_folder_name(repository: &String) -> String {
+    let parts: Vec<&str> = repository.trim_end_matches('/').trim_end_matches(".git").rsplit(['/', ':']).take(2).collect();
+    parts.iter().rev().map(|part| part.to_string()).collect::<Vec<String>>().join("-")
+}
+
+pub fn build_clone_repo_command(repo: &String, folder: &String, path: &String, shallow: bool) -> RunCommandInputData {
+    let mut args = vec![Cmd::Clone.as_string()];
+    if shallow {
+        args.append(&mut vec!["--depth".to_string(), "1".to_string(), "--no-checkout".to_string()]);
+    }
+    args.append(&mut vec![repo.clone(), folder.clone()]);
+    RunCommandInputData { command: Cmd::Git.as_string(), args, current_dir: path.to_string() }
+}
+
+/// Fetch the branch, tag or full commit hash from origin, it is left in FETCH_HEAD
+pub fn build_fetch_ref_command(git_ref: &String, path: &String, shallow: bool) -> RunCommandInputData {
+    let mut args = vec![Cmd::Fetch.as_string(), "--force".to_string(), "--recurse-submodules".to_string()];
+    if shallow {
+        args.append(&mut vec!["--depth".to_string(), "1".to_string()]);
+    } else {
+        args.push("--tags".to_string());
+    }
+    args.append(&mut vec!["origin".to_string(), git_ref.to_string()]);
     RunCommandInputData { command: Cmd::Git.as_string(), args, current_dir: path.to_string() }
 }
 
-fn build_fetch_all_command(path: &String) -> RunCommandInputData {
-    let args = vec![Cmd::Fetch.as_string(), "--all".to_string(), "--recurse-submodules".to_string(), "--tags".to_string()];
+pub fn build_checkout_fetched_command(path: &String) -> RunCommandInputData {
+    let args = vec![Cmd::Checkout.as_string(), "--force".to_string(), "--detach".to_string(), "FETCH_HEAD".to_string()];
     RunCommandInputData { command: Cmd::Git.as_string(), args, current_dir: path.to_string() }
 }
 
@@ -70,16 +151,3 @@ fn build_checkout_version_command(version: &String, path: &String) -> RunCommand
     let args = vec![Cmd::Checkout.as_string(), arg_version];
     RunCommandInputData { command: Cmd::Git.as_string(), args, current_dir: path.to_string() }
 }
-
-fn build_run_cabal_command(cabal_path: &String, folder_path: &String, binaries: &Vec<String>) -> RunCommandInputData {
-    let mut args: Vec<String> = vec![Cmd::Build.as_string()];
-    for binary in binaries {
-        args.push(binary.to_string());
-    }
-    RunCommandInputData { command: url_build(vec![&cabal_path, &Cmd::Cabal.as_string()], false), args, current_dir: folder_path.to_string() }
-}
-
-fn build_cabal_update_command(cabal_path: &String) -> RunCommandInputData {
-    let args: Vec<String> = vec![url_build(vec![cabal_path, &Cmd::Cabal.as_string()], false), Cmd::V2Update.as_string()];
-    RunCommandInputData { command: Cmd::Sudo.as_string() , args, current_dir: "".to_string() }
-}
\ No newline at end of file
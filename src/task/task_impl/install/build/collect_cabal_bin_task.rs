@@ -0,0 +1,73 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use crate::env::Env;
+use crate::{Success, Term, url_build};
+use crate::config::remote_config::RemoteConfig;
+use crate::message::message::{Message, MessageData};
+use crate::task::task::Task;
+use crate::task::task_type::TaskType;
+
+/// The build folder is kept between builds and still has the binaries of the previous versions,
+/// cabal is asked where the executables of the current plan are and only those are collected
+pub struct CollectCabalBinTask {
+    pub input_data: CollectCabalBinData,
+}
+
+#[derive(Default, Clone, Debug, Eq, PartialEq)]
+pub struct CollectCabalBinData {
+    pub cabal: String,
+    pub source_folder: String,
+    pub binaries: Vec<String>,
+    /// Emptied and filled with the binaries, it is the output folder of the cabal backend
+    pub output_folder: String,
+}
+
+impl Task for CollectCabalBinTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        Ok(true)
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let data = &self.input_data;
+        if Path::new(&data.output_folder).exists() {
+            fs::remove_dir_all(&data.output_folder)?;
+        }
+        fs::create_dir_all(&data.output_folder)?;
+
+        for binary in &data.binaries {
+            let path = self.list_bin(binary)?;
+            fs::copy(&path, url_build(vec![&data.output_folder, binary], false))?;
+        }
+        Ok(Success {})
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::CollectCabalBin
+    }
+}
+
+impl CollectCabalBinTask {
+    fn list_bin(&self, binary: &String) -> Result<String, Message> {
+        let data = &self.input_data;
+        let output = Command::new(&data.cabal).args(["list-bin", binary.as_str()]).current_dir(&data.source_folder).output()?;
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+        if !output.status.success() || !Path::new(&path).is_file() {
+            return Err(Message::CommandOutputError(MessageData {
+                message: format!("cabal did not build {} in {}", binary, data.source_folder),
+                task: self.get_type(),
+                stack: vec![String::from_utf8_lossy(&output.stderr).to_string(), path],
+                ..Default::default()
+            }));
+        }
+        Ok(path)
+    }
+}
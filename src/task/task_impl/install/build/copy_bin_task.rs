@@ -7,7 +7,6 @@ use crate::env::Env;
 use crate::{Success, Term, url_build};
 use crate::config::remote_config::RemoteConfig;
 use crate::message::message::Message;
-use crate::utils::folders::Folder;
 use crate::task::task::Task;
 use crate::task::task_impl::commons::file_manager_task::{FileManagerAction, FileManagerTask};
 use crate::task::task_impl::commons::folder_manager_task::{FolderManagerAction, FolderManagerTask};
@@ -26,6 +25,8 @@ pub struct CopyBinInputData {
     pub version: String,
     pub bin_folder: String,
     pub version_folder: String,
+    /// Follow the links found in the origin folder, the nix outputs are links to the store
+    pub follow_links: bool,
 }
 
 impl Task for CopyBinTask {
@@ -39,7 +40,7 @@ impl Task for CopyBinTask {
     }
 
     fn run(self: &Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
-        for entry in WalkDir::new(&self.input_data.origin_path) {
+        for entry in WalkDir::new(&self.input_data.origin_path).follow_links(self.input_data.follow_links) {
             let entry = entry.unwrap();
             for file_name in &self.input_data.files_names {
                 if entry.file_name().to_str().unwrap() == file_name && entry.path().is_file() {
@@ -51,12 +52,11 @@ impl Task for CopyBinTask {
     }
 
     fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
-        let bin_folder = Folder::get_path(Folder::BIN, &config);
-        let version_folder = url_build(vec![&bin_folder, &self.input_data.version], false);
+        let version_folder = self.input_data.version_folder.clone();
 
         let mut files_paths = vec![];
 
-        for required_file in &config.binaries.required_files {
+        for required_file in &self.input_data.files_names {
             files_paths.push(url_build(vec![&version_folder.to_string(), required_file], false))
         }
 
@@ -1,3 +1,5 @@
+pub mod backend;
 pub mod build_cardano_node_task;
+pub mod collect_cabal_bin_task;
 pub mod copy_bin_task;
-pub mod install_libsodium_task;
+pub mod record_build_task;
@@ -0,0 +1,82 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::env::Env;
+use crate::{Success, Term, url_build};
+use crate::config::remote_config::RemoteConfig;
+use crate::message::message::Message;
+use crate::task::task::Task;
+use crate::task::task_type::TaskType;
+use crate::utils::git_utils::git_output;
+use crate::utils::time_utils::utc_timestamp;
+
+pub const BUILD_INFO_FILE: &str = "build.toml";
+
+pub struct RecordBuildTask {
+    pub input_data: RecordBuildData,
+}
+
+#[derive(Default, Clone, Debug, Eq, PartialEq)]
+pub struct RecordBuildData {
+    pub repository: String,
+    pub git_ref: String,
+    pub source_folder: String,
+    pub version_folder: String,
+    pub backend: String,
+}
+
+/// Origin of a version built locally, saved next to its binaries
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BuildInfo {
+    pub repository: String,
+    pub git_ref: String,
+    pub commit: String,
+    /// The checkout had uncommitted changes when it was built
+    pub dirty: bool,
+    pub built: String,
+    /// Build backend used, empty for the versions built before it was recorded
+    pub backend: String,
+}
+
+impl Task for RecordBuildTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        Ok(Path::new(&self.input_data.version_folder).exists())
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let data = &self.input_data;
+        let commit = git_output(&data.source_folder, vec!["rev-parse", "HEAD"])?;
+        let dirty = !git_output(&data.source_folder, vec!["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+
+        let info = BuildInfo {
+            repository: data.repository.clone(),
+            git_ref: data.git_ref.clone(),
+            commit,
+            dirty,
+            built: utc_timestamp(),
+            backend: data.backend.clone(),
+        };
+
+        let file_path = url_build(vec![&data.version_folder, &BUILD_INFO_FILE.to_string()], false);
+        fs::write(file_path, toml::to_string(&info).unwrap())?;
+        Ok(Success {})
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::RecordBuild
+    }
+}
+
+pub fn read_build_info(version_folder: &String) -> Option<BuildInfo> {
+    let file_path = url_build(vec![version_folder, &BUILD_INFO_FILE.to_string()], false);
+    let text = fs::read_to_string(file_path).ok()?;
+    toml::from_str(&text).ok()
+}
@@ -0,0 +1,82 @@
+#![allow(dead_code, unused_variables)]
+
+use crate::env::Env;
+use crate::{Success, Term, url_build};
+use crate::config::remote_config::{Component, RemoteConfig};
+use crate::message::message::Message;
+use crate::task::task::Task;
+use crate::task::task_impl::commons::folder_manager_task::{FolderManagerAction, FolderManagerTask};
+use crate::task::task_impl::commons::permission_task::{PermissionAction, PermissionTask};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData, RunCommandTask};
+use crate::task::task_impl::install::build::build_cardano_node_task::{build_checkout_fetched_command, build_clone_repo_command, build_fetch_ref_command};
+use crate::task::task_type::TaskType;
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L2;
+use crate::utils::folders::Folder;
+use crate::utils::git_utils::is_clone_of;
+
+#[derive(Default)]
+pub struct BuildComponentTask {
+    pub component: Component,
+    pub version: String,
+    source_folder: String,
+    ghcup_folder: String,
+    git_folder: String,
+}
+
+impl Task for BuildComponentTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        self.git_folder = Folder::get_path(Folder::GIT, config);
+        self.source_folder = get_component_source_folder(&self.component, config);
+        self.ghcup_folder = Folder::get_ghcup_bin_dir(config)?;
+        Ok(true)
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let tag = get_component_tag(&self.component, &self.version);
+        let mut tasks: Vec<Box<dyn Task>> = vec![
+            Box::new(PermissionTask { input_data: PermissionAction::CheckWrite(vec![self.git_folder.clone()]) }),
+        ];
+
+        if !is_clone_of(&self.source_folder, &self.component.repository) {
+            tasks.append(&mut vec![
+                Box::new(FolderManagerTask { input_data: FolderManagerAction::Remove(vec![self.source_folder.clone()]) }),
+                Box::new(RunCommandTask { input_data: build_clone_repo_command(&self.component.repository, &self.source_folder, &self.git_folder, false), command_description: format!("Cloning {} repository", self.component.name) }),
+            ]);
+        }
+
+        tasks.append(&mut vec![
+            Box::new(RunCommandTask { input_data: build_fetch_ref_command(&tag, &self.source_folder, false), command_description: format!("Fetching {}", tag) }),
+            Box::new(RunCommandTask { input_data: build_checkout_fetched_command(&self.source_folder), command_description: format!("changing to {}", tag) }),
+        ]);
+
+        for step in &self.component.build {
+            tasks.push(Box::new(RunCommandTask { input_data: build_step_command(step, &self.ghcup_folder, &self.source_folder), command_description: format!("Building {}: {}", self.component.name, step) }));
+        }
+
+        TaskManager::default().start(tasks, config, term, L2)
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::BuildComponent(self.component.name.clone())
+    }
+}
+
+pub fn get_component_source_folder(component: &Component, config: &RemoteConfig) -> String {
+    url_build(vec![&Folder::get_path(Folder::GIT, config), &component.name], false)
+}
+
+pub fn get_component_tag(component: &Component, version: &String) -> String {
+    format!("{}{}", component.tag_prefix, version)
+}
+
+/// The recipes call cabal and ghc by name, so the ghcup toolchain goes first in the PATH
+fn build_step_command(command: &String, ghcup_folder: &String, path: &String) -> RunCommandInputData {
+    let args = vec!["-c".to_string(), format!("export PATH=\"{}:$PATH\" && {}", ghcup_folder, command)];
+    RunCommandInputData { command: Cmd::Bash.as_string(), args, current_dir: path.to_string() }
+}
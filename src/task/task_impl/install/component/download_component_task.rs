@@ -0,0 +1,77 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use crate::env::Env;
+use crate::{MessageData, Success, Term, url_build};
+use crate::config::remote_config::{Component, RemoteConfig};
+use crate::message::message::Message;
+use crate::task::task::Task;
+use crate::task::task_impl::install::build::copy_bin_task::{CopyBinInputData, CopyBinTask};
+use crate::task::task_impl::install::component::build_component_task::get_component_tag;
+use crate::task::task_impl::install::download::download_install_task::decompress;
+use crate::task::task_type::TaskType;
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L2;
+use crate::utils::download_manager::download_in_path;
+use crate::utils::folders::Folder;
+
+const ARCHIVE_NAME: &str = "component.tar.gz";
+const EXTRACT_FOLDER: &str = "extract";
+
+pub struct DownloadComponentTask {
+    pub component: Component,
+    pub version: String,
+    pub bin_folder: String,
+    pub version_folder: String,
+}
+
+impl Task for DownloadComponentTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        if self.component.download_url.is_empty() {
+            return Err(Message::DownloadFile(MessageData {
+                message: format!("{} has no binary release, build it with [cvm install --component {} -b]", self.component.name, self.component.name),
+                task: self.get_type(),
+                ..Default::default()
+            }));
+        }
+        Ok(true)
+    }
+
+    /// The archive is unpacked in the cvm temporal folder and only the component binaries are copied to the version folder
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let url = self.component.download_url
+            .replace("{version}", &self.version)
+            .replace("{tag}", &get_component_tag(&self.component, &self.version));
+
+        let tmp_folder = url_build(vec![&Folder::get_cvm_home()?, &"tmp".to_string(), &format!("{}-{}", self.component.name, self.version)], false);
+        let extract_folder = url_build(vec![&tmp_folder, &EXTRACT_FOLDER.to_string()], false);
+        fs::create_dir_all(&extract_folder)?;
+
+        let result = download_in_path(&url, tmp_folder.clone(), ARCHIVE_NAME.to_string())
+            .and_then(|file| decompress(&file, &extract_folder))
+            .and_then(|_| TaskManager::default().start(vec![
+                Box::new(CopyBinTask {
+                    input_data: CopyBinInputData {
+                        files_names: self.component.binaries.clone(),
+                        origin_path: extract_folder.clone(),
+                        version: self.version.clone(),
+                        bin_folder: self.bin_folder.clone(),
+                        version_folder: self.version_folder.clone(),
+                        follow_links: false,
+                    }
+                }),
+            ], config, term, L2));
+
+        let _ = fs::remove_dir_all(&tmp_folder);
+        result
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::DownloadInstall(format!("{} {}", self.component.name, self.version))
+    }
+}
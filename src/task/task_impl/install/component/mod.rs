@@ -0,0 +1,2 @@
+pub mod build_component_task;
+pub mod download_component_task;
@@ -45,7 +45,7 @@ impl Task for DownloadInstallTask {
     }
 }
 
-fn decompress(file_uri: &String, folder: &String) -> Result<Success, Message> {
+pub fn decompress(file_uri: &String, folder: &String) -> Result<Success, Message> {
     let file = File::open(file_uri)?;
     let tar = GzDecoder::new(file);
     let mut archive = Archive::new(tar);
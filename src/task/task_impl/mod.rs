@@ -3,4 +3,7 @@ pub mod install;
 pub mod update;
 pub mod r#use;
 pub mod commons;
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod db;
+pub mod toolchain;
+pub mod upgrade;
@@ -0,0 +1 @@
+pub mod toolchain_task;
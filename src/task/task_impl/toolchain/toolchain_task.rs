@@ -0,0 +1,127 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use std::process::Command;
+use crate::env::Env;
+use crate::{Success, Term, url_build};
+use crate::config::remote_config::{RemoteConfig, Toolchain};
+use crate::message::message::{Message, MessageData};
+use crate::task::task::Task;
+use crate::task::task_impl::commons::run_command_task::{RunCommandInputData, RunCommandTask};
+use crate::task::task_impl::init::install_ghcup_task::{build_install_cabal_version_command, build_install_ghc_version_command, build_remove_cabal_version_command, build_remove_ghc_version_command, build_set_cabal_version_command, build_set_ghc_version_command};
+use crate::task::task_type::TaskType;
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L2;
+use crate::utils::folders::Folder;
+
+pub struct ToolchainTask {
+    pub input_data: ToolchainAction,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ToolchainAction {
+    Install(Toolchain),
+    /// Install the toolchain if it is missing and make it the active one
+    Use(Toolchain),
+    Remove(Toolchain),
+}
+
+impl Task for ToolchainTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        let ghcup_dir = Folder::get_ghcup_bin_dir(config)?;
+        if !Path::new(&url_build(vec![&ghcup_dir, &"ghcup".to_string()], false)).exists() {
+            return Err(Message::Toolchain(MessageData {
+                message: format!("ghcup was not found in {}, run [cvm init -b] first", ghcup_dir),
+                task: self.get_type(),
+                ..Default::default()
+            }));
+        }
+        Ok(true)
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let ghcup_dir = Folder::get_ghcup_bin_dir(config)?;
+        let mut commands: Vec<(RunCommandInputData, String)> = vec![];
+
+        match &self.input_data {
+            ToolchainAction::Install(toolchain) | ToolchainAction::Use(toolchain) => {
+                // ghcup keeps every installed version as ghc-<version> and cabal-<version>
+                if !is_installed(&ghcup_dir, "ghc", &toolchain.ghc) {
+                    commands.push((build_install_ghc_version_command(&ghcup_dir, &toolchain.ghc), format!("Installing ghc {}", toolchain.ghc)));
+                }
+                if !is_installed(&ghcup_dir, "cabal", &toolchain.cabal) {
+                    commands.push((build_install_cabal_version_command(&ghcup_dir, &toolchain.cabal), format!("Installing cabal {}", toolchain.cabal)));
+                }
+            }
+            ToolchainAction::Remove(toolchain) => {
+                if is_installed(&ghcup_dir, "ghc", &toolchain.ghc) {
+                    commands.push((build_remove_ghc_version_command(&ghcup_dir, &toolchain.ghc), format!("Removing ghc {}", toolchain.ghc)));
+                }
+                if is_installed(&ghcup_dir, "cabal", &toolchain.cabal) {
+                    commands.push((build_remove_cabal_version_command(&ghcup_dir, &toolchain.cabal), format!("Removing cabal {}", toolchain.cabal)));
+                }
+            }
+        }
+
+        if let ToolchainAction::Use(toolchain) = &self.input_data {
+            if active_version(&ghcup_dir, "ghc") != toolchain.ghc {
+                commands.push((build_set_ghc_version_command(&ghcup_dir, &toolchain.ghc), format!("Changing to ghc {}", toolchain.ghc)));
+            }
+            if active_version(&ghcup_dir, "cabal") != toolchain.cabal {
+                commands.push((build_set_cabal_version_command(&ghcup_dir, &toolchain.cabal), format!("Changing to cabal {}", toolchain.cabal)));
+            }
+        }
+
+        let tasks: Vec<Box<dyn Task>> = commands.into_iter()
+            .map(|(input_data, command_description)| Box::new(RunCommandTask { input_data, command_description }) as Box<dyn Task>)
+            .collect();
+        TaskManager::default().start(tasks, config, term, L2)
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let ghcup_dir = Folder::get_ghcup_bin_dir(config)?;
+
+        let valid = match &self.input_data {
+            ToolchainAction::Install(toolchain) => {
+                is_installed(&ghcup_dir, "ghc", &toolchain.ghc) && is_installed(&ghcup_dir, "cabal", &toolchain.cabal)
+            }
+            ToolchainAction::Use(toolchain) => {
+                active_version(&ghcup_dir, "ghc") == toolchain.ghc && active_version(&ghcup_dir, "cabal") == toolchain.cabal
+            }
+            ToolchainAction::Remove(toolchain) => {
+                !is_installed(&ghcup_dir, "ghc", &toolchain.ghc) && !is_installed(&ghcup_dir, "cabal", &toolchain.cabal)
+            }
+        };
+
+        if !valid {
+            return Err(Message::Toolchain(MessageData {
+                message: format!("{} could not be completed", self.get_type().print()),
+                task: self.get_type(),
+                ..Default::default()
+            }));
+        }
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        match &self.input_data {
+            ToolchainAction::Install(toolchain) => TaskType::Toolchain(format!("Installing ghc {} and cabal {}", toolchain.ghc, toolchain.cabal)),
+            ToolchainAction::Use(toolchain) => TaskType::Toolchain(format!("Selecting ghc {} and cabal {}", toolchain.ghc, toolchain.cabal)),
+            ToolchainAction::Remove(toolchain) => TaskType::Toolchain(format!("Removing ghc {} and cabal {}", toolchain.ghc, toolchain.cabal)),
+        }
+    }
+}
+
+pub fn is_installed(ghcup_dir: &String, tool: &str, version: &String) -> bool {
+    Path::new(&url_build(vec![ghcup_dir, &format!("{}-{}", tool, version)], false)).exists()
+}
+
+/// Version of the tool set with [ghcup set], empty when there is none
+pub fn active_version(ghcup_dir: &String, tool: &str) -> String {
+    let bin = url_build(vec![ghcup_dir, &tool.to_string()], false);
+    match Command::new(&bin).arg("--numeric-version").output() {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        _ => String::new(),
+    }
+}
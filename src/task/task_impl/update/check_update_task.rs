@@ -63,7 +63,7 @@ impl Task for CheckUpdateTask {
 }
 
 fn download_and_copy_version(version: &String) -> Result<Success, Message> {
-    let home_dir = Folder::get_home_dir()?;
+    let cvm_home = Folder::get_cvm_home()?;
 
     let mut version_map = HashMap::new();
     version_map.insert("version".to_string(), version);
@@ -79,14 +79,14 @@ fn download_and_copy_version(version: &String) -> Result<Success, Message> {
 
     let download_path = download(&url, format!("/{}", &FILE_NAME).as_str())?;
 
-    decompress(download_path, home_dir)
+    decompress(download_path, cvm_home)
 }
 
-fn decompress(file_uri: String, home_dir: String) -> Result<Success, Message> {
+fn decompress(file_uri: String, cvm_home: String) -> Result<Success, Message> {
     let file = File::open(file_uri)?;
     let tar = GzDecoder::new(file);
     let mut archive = Archive::new(tar);
-    archive.unpack(format!("{}/{}/", home_dir, ".cvm"))?;
+    archive.unpack(format!("{}/", cvm_home))?;
 
     Ok(Success {})
 }
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod schedule_upgrade_task;
@@ -0,0 +1,145 @@
+#![allow(dead_code, unused_variables)]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use crate::env::Env;
+use crate::{Success, Term};
+use crate::config::remote_config::RemoteConfig;
+use crate::config::settings_config::UpgradeSettings;
+use crate::message::message::{Message, MessageData};
+use crate::service_manager::{get_service_manager, SYSTEMD};
+use crate::service_manager::service_manager_impl::systemd::{build_systemctl_command, environment_value, exec_command_line, unit_path, write_unit};
+use crate::task::task::Task;
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandTask};
+use crate::task::task_type::TaskType;
+use crate::task_manager::task_manager::TaskManager;
+use crate::term::log_level::LogLevel::L2;
+use crate::utils::folders::Folder;
+use crate::utils::user::get_current_user;
+
+/// Switch to the new version inside the maintenance window
+pub const UPGRADE_UNIT: &str = "cvm-upgrade";
+/// Download or build the new version ahead of the window
+pub const PREPARE_UNIT: &str = "cvm-upgrade-prepare";
+/// Upgrade scheduled once with [cvm upgrade --at]
+pub const UPGRADE_AT_UNIT: &str = "cvm-upgrade-at";
+const SERVICE_TEMPLATE: &str = include_str!("../../../../config/systemd/cvm-upgrade.service");
+const TIMER_TEMPLATE: &str = include_str!("../../../../config/systemd/cvm-upgrade.timer");
+
+pub struct ScheduleUpgradeTask {
+    pub input_data: ScheduleUpgradeAction,
+}
+
+#[derive(Debug, Clone)]
+pub enum ScheduleUpgradeAction {
+    /// Timers of the upgrade policy in the settings
+    EnablePolicy(UpgradeSettings),
+    DisablePolicy,
+    /// systemd calendar and the arguments passed to [cvm upgrade]
+    At(String, Vec<String>),
+    CancelAt,
+}
+
+impl Task for ScheduleUpgradeTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        let service_manager = get_service_manager()?.name();
+        if service_manager != SYSTEMD {
+            return Err(Message::Upgrade(MessageData {
+                message: format!("Scheduled upgrades use systemd timers and the node is managed by {}, run [cvm upgrade] from cron instead", service_manager),
+                task: self.get_type(),
+                ..Default::default()
+            }));
+        }
+        Ok(true)
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        match &self.input_data {
+            ScheduleUpgradeAction::EnablePolicy(settings) => {
+                let build = if settings.build { vec!["--build".to_string()] } else { vec![] };
+                let prepare_args = [vec!["--policy".to_string(), "--download-only".to_string()], build.clone()].concat();
+                let upgrade_args = [vec!["--policy".to_string()], build].concat();
+
+                // a missed download runs at boot, a missed window waits for the next one
+                enable_timer(PREPARE_UNIT, "Download the next cardano node version", &settings.prepare, prepare_args, true, config, term)?;
+                enable_timer(UPGRADE_UNIT, "Upgrade cardano node in the maintenance window", &settings.window, upgrade_args, false, config, term)
+            }
+            ScheduleUpgradeAction::DisablePolicy => {
+                disable_timer(PREPARE_UNIT, config, term)?;
+                disable_timer(UPGRADE_UNIT, config, term)
+            }
+            ScheduleUpgradeAction::At(calendar, args) => {
+                enable_timer(UPGRADE_AT_UNIT, "Scheduled cardano node upgrade", calendar, args.clone(), false, config, term)
+            }
+            ScheduleUpgradeAction::CancelAt => disable_timer(UPGRADE_AT_UNIT, config, term),
+        }
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::ScheduleUpgrade
+    }
+}
+
+fn enable_timer(unit: &str, description: &str, calendar: &String, args: Vec<String>, persistent: bool, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let timer = format!("{}.timer", unit);
+    let cvm = env::current_exe()?.to_str().unwrap().to_string();
+    let context = UpgradeContext {
+        description: description.to_string(),
+        command: exec_command_line(&[vec![cvm, "upgrade".to_string()], args].concat()),
+        calendar: calendar.to_string(),
+        persistent,
+        user: environment_value(&get_current_user()?),
+        cvm_home: environment_value(&Folder::get_cvm_home()?),
+        workspace: environment_value(&Folder::get_workspaces_dir()),
+        rootless: Folder::is_rootless(),
+    };
+    fs::create_dir_all(Folder::get_systemd_dir()?)?;
+    write_unit(&format!("{}.service", unit), SERVICE_TEMPLATE, &context)?;
+    write_unit(&timer, TIMER_TEMPLATE, &context)?;
+
+    // restart picks up the new calendar when the timer was already enabled
+    TaskManager::default().start(vec![
+        Box::new(RunCommandTask { input_data: build_systemctl_command(vec![Cmd::DaemonReload.as_string()]), command_description: "Reset systemctl daemon".to_string() }),
+        Box::new(RunCommandTask { input_data: build_systemctl_command(vec![Cmd::Enable.as_string(), timer.clone()]), command_description: format!("Enable {}", timer) }),
+        Box::new(RunCommandTask { input_data: build_systemctl_command(vec!["restart".to_string(), timer.clone()]), command_description: format!("Start {}", timer) }),
+    ], config, term, L2)
+}
+
+fn disable_timer(unit: &str, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+    let timer = format!("{}.timer", unit);
+    if !Path::new(&unit_path(&timer)?).exists() {
+        return Ok(Success {});
+    }
+
+    TaskManager::default().start(vec![
+        Box::new(RunCommandTask { input_data: build_systemctl_command(vec![Cmd::Disable.as_string(), Cmd::Now.as_string(), timer.clone()]), command_description: format!("Disable {}", timer) }),
+    ], config, term, L2)?;
+
+    for extension in ["service", "timer"] {
+        let path = unit_path(&format!("{}.{}", unit, extension))?;
+        if Path::new(&path).exists() {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(Success {})
+}
+
+#[derive(Serialize)]
+struct UpgradeContext {
+    description: String,
+    /// cvm and its arguments quoted for ExecStart
+    command: String,
+    calendar: String,
+    persistent: bool,
+    user: String,
+    cvm_home: String,
+    workspace: String,
+    rootless: bool,
+}
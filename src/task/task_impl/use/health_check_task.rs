@@ -0,0 +1,87 @@
+#![allow(dead_code, unused_variables)]
+
+use std::time::{Duration, Instant};
+use crate::env::Env;
+use crate::{Success, Term};
+use crate::config::remote_config::RemoteConfig;
+use crate::message::message::{Message, MessageData};
+use crate::task::task::Task;
+use crate::task::task_type::TaskType;
+use crate::utils::node_process::{query_tip_slot, socket_accepts, socket_path, wait_until};
+
+/// Wait until the restarted node accepts connections on its socket and its tip advances,
+/// then report how long the node was down
+pub struct HealthCheckTask {
+    pub input_data: HealthCheckData,
+}
+
+pub struct HealthCheckData {
+    /// When the node was stopped
+    pub down_since: Instant,
+    pub timeout: Duration,
+}
+
+impl Task for HealthCheckTask {
+
+    fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        Ok(true)
+    }
+
+    fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let deadline = Instant::now() + self.input_data.timeout;
+
+        // the socket opens once the node has loaded the ledger, after a replay it can take long
+        if !wait_until(remaining(deadline), || socket_accepts(config)) {
+            return Err(self.error(format!("cardano-node did not open the socket {} in {} seconds", socket_path(config), self.input_data.timeout.as_secs())));
+        }
+        let socket_at = self.input_data.down_since.elapsed();
+
+        let mut first_slot = None;
+        let mut slot = None;
+        let advanced = wait_until(remaining(deadline), || {
+            slot = query_tip_slot(config);
+            if first_slot.is_none() {
+                first_slot = slot;
+            }
+            first_slot.is_some() && slot > first_slot
+        });
+        if !advanced {
+            return Err(self.error(format!("The tip of cardano-node did not advance in {} seconds", self.input_data.timeout.as_secs())));
+        }
+
+        println!("  The socket accepted connections after {} and the tip advanced to the slot {}, downtime {}",
+                 format_duration(socket_at), slot.unwrap_or_default(), format_duration(self.input_data.down_since.elapsed()));
+        Ok(Success {})
+    }
+
+    fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        Ok(Success {})
+    }
+
+    fn get_type(self: &Self) -> TaskType {
+        TaskType::HealthCheck
+    }
+}
+
+impl HealthCheckTask {
+    fn error(&self, message: String) -> Message {
+        Message::ServiceManager(MessageData {
+            message: format!("{}, follow the logs with [cvm logs -f]", message),
+            task: self.get_type(),
+            ..Default::default()
+        })
+    }
+}
+
+fn remaining(deadline: Instant) -> Duration {
+    deadline.saturating_duration_since(Instant::now())
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    }
+}
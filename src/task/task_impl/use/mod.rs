@@ -1,3 +1,4 @@
 pub mod deploy_system_task;
 pub mod service_manager_task;
-pub mod use_version_task;
\ No newline at end of file
+pub mod use_version_task;
+pub mod health_check_task;
\ No newline at end of file
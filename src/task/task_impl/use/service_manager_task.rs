@@ -1,27 +1,71 @@
 #![allow(dead_code, unused_variables)]
 
-use std::process::ExitStatus;
+use std::thread;
+use std::time::Duration;
 use crate::env::Env;
 use crate::{Success, Term};
 use crate::config::remote_config::RemoteConfig;
-use crate::message::message::Message;
+use crate::config::settings_config::get_settings;
+use crate::message::message::{Message, MessageData, MessageKind};
+use crate::service_manager::get_service_manager;
+use crate::utils::leader_schedule::{has_leader_schedule, scheduled_blocks};
 use crate::task::task::Task;
 use crate::task::task_type::TaskType;
+use crate::utils::folders::Folder;
+use crate::utils::node_process::wait_node_exit;
+use crate::utils::time_utils::{unix_now, utc_iso};
+
+/// Seconds after the leader slot until the block is adopted and sent to the relays
+const BLOCK_MARGIN: u64 = 30;
 
 pub struct ServicesManagerTask {
-    pub input_data: ServicesAction,
+    pub input_data: ServicesData,
+}
+
+pub struct ServicesData {
+    pub action: ServicesAction,
+    /// Wait for the blocks scheduled in the leader window before a running node is stopped or restarted
+    pub guard: bool,
 }
 
 impl Task for ServicesManagerTask {
 
     fn prepare(self: &mut Self, env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<bool, Message> {
+        // start restarts a running node, so both actions stop an active block producer
+        if self.input_data.guard && has_leader_schedule(config) {
+            let service_manager = get_service_manager()?;
+            if config.services_item.iter().any(|services| service_manager.is_active(&services.name)) {
+                wait_leader_window(config)?;
+            }
+        }
         Ok(true)
     }
 
     fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
+        let service_manager = get_service_manager()?;
 
         for services in &config.services_item {
-            exec_action(&self.input_data, services.name.as_str())?;
+            match self.input_data.action {
+                ServicesAction::START => { service_manager.start(&services.name)?; }
+                // the service may not be deployed yet, there is nothing to stop then
+                ServicesAction::STOP => {
+                    if service_manager.is_active(&services.name) {
+                        service_manager.stop(&services.name)?;
+                    }
+                }
+            }
+        }
+
+        // the service manager may return before the node closed the database
+        if let ServicesAction::STOP = self.input_data.action {
+            let stop_timeout = get_settings(&Folder::get_cvm_home()?)?.service.stop_timeout;
+            if !wait_node_exit(config, Duration::from_secs(stop_timeout)) {
+                Message::ServiceManager(MessageData {
+                    message: format!("cardano-node did not exit {} seconds after the stop, it was killed", stop_timeout),
+                    kind: MessageKind::Warning,
+                    ..Default::default()
+                }).print();
+            }
         }
         Ok(Success {})
     }
@@ -35,18 +79,34 @@ impl Task for ServicesManagerTask {
     }
 }
 
-fn exec_action(input_data: &ServicesAction, service_name: &str) -> Result<ExitStatus, Message> {
-    match input_data {
-        ServicesAction::START => {
-            Ok(systemctl::restart(service_name)?)
-        }
-        ServicesAction::STOP => {
-            Ok(systemctl::stop(service_name)?)
+/// Wait until the leader window after now has no scheduled blocks, a block in the window is waited for
+/// unless that takes longer than the leader wait of the settings
+fn wait_leader_window(config: &RemoteConfig) -> Result<(), Message> {
+    let settings = get_settings(&Folder::get_cvm_home()?)?.service;
+    let started = unix_now();
+
+    loop {
+        let now = unix_now();
+        // a block of the last seconds may not be adopted yet
+        let block = match scheduled_blocks(config, now.saturating_sub(BLOCK_MARGIN), now + settings.leader_window)?.into_iter().next() {
+            Some(block) => block,
+            None => return Ok(()),
+        };
+
+        let until = block.at + BLOCK_MARGIN;
+        if until - started > settings.leader_wait {
+            return Err(Message::ServiceManager(MessageData {
+                message: format!("The pool is leader in the slot {} at {}, the node can't be stopped before the block is minted. Try again later or use --force", block.slot, utc_iso(block.at)),
+                ..Default::default()
+            }));
         }
+
+        println!("  The pool is leader in the slot {} at {}, waiting {} seconds for the block before the node is stopped", block.slot, utc_iso(block.at), until.saturating_sub(now));
+        thread::sleep(Duration::from_secs(until.saturating_sub(now)));
     }
 }
 
 pub enum ServicesAction{
     START,
     STOP
-}
\ No newline at end of file
+}
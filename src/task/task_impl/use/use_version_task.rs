@@ -1,17 +1,20 @@
 #![allow(dead_code, unused_variables)]
 
+use std::cmp::Ordering;
 use std::fs;
 use std::fs::File;
 use std::path::Path;
 use file_diff::diff_files;
 use crate::env::Env;
 use crate::{Success, Term, url_build};
-use crate::config::remote_config::RemoteConfig;
-use crate::config::state_config::set_version_use;
-use crate::message::message::{Message, MessageData};
+use crate::config::remote_config::{DbCompatibility, get_component, is_node_component, RemoteConfig};
+use crate::config::state_config::{get_state, set_component_use, set_db_version, set_version_use};
+use crate::message::message::{Message, MessageData, MessageKind};
 use crate::utils::folders::Folder;
 use crate::task::task::Task;
 use crate::task::task_type::TaskType;
+use crate::task::task_impl::install::build::record_build_task::read_build_info;
+use crate::utils::version_utils::{compare_versions, verify_version};
 
 pub struct UserVersionTask {
     pub input_data: UserVersionData,
@@ -20,6 +23,8 @@ pub struct UserVersionTask {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct UserVersionData {
     pub version: String,
+    /// Component to switch, cardano-node when empty
+    pub component: String,
 }
 
 impl Task for UserVersionTask {
@@ -30,10 +35,10 @@ impl Task for UserVersionTask {
 
     fn run(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
 
-        let bin_folder = Folder::get_path(Folder::BIN, &config);
+        let bin_folder = Folder::get_component_bin_dir(&self.input_data.component, &config);
         let version_folder = url_build(vec![&bin_folder, &self.input_data.version], false);
         let version_folder_path = Path::new(version_folder.as_str());
-        let current_folder = Folder::get_path(Folder::CURRENT, &config);
+        let current_folder = Folder::get_component_current_dir(&self.input_data.component, &config);
         let current_folder_path = Path::new(current_folder.as_str());
 
         if !version_folder_path.exists() {
@@ -57,16 +62,24 @@ impl Task for UserVersionTask {
             }
         };
 
-        copy_file_version(&version_folder, &current_folder, &config.binaries.required_files, self)?;
-        set_version_use(self.input_data.version.clone())?;
+        if is_node_component(&self.input_data.component) {
+            copy_file_version(&version_folder, &current_folder, &config.binaries.required_files, self)?;
+            set_version_use(self.input_data.version.clone())?;
+            // the node started after the switch writes the db with this version
+            set_db_version(self.input_data.version.clone())?;
+        } else {
+            let component = get_component(config, &self.input_data.component)?;
+            copy_file_version(&version_folder, &current_folder, &component.binaries, self)?;
+            set_component_use(&self.input_data.component, self.input_data.version.clone())?;
+        }
         Ok(Success {})
     }
 
     fn check(self: &Self, _env: &mut Env, config: &RemoteConfig, term: &mut Term) -> Result<Success, Message> {
 
-        let bin_folder = Folder::get_path(Folder::BIN, &config);
+        let bin_folder = Folder::get_component_bin_dir(&self.input_data.component, &config);
         let version_folder = url_build(vec![&bin_folder, &self.input_data.version], false);
-        let current_folder = Folder::get_path(Folder::CURRENT, &config);
+        let current_folder = Folder::get_component_current_dir(&self.input_data.component, &config);
 
         for entity in fs::read_dir(Path::new(&current_folder))? {
             if entity.as_ref().unwrap().path().is_file() {
@@ -81,7 +94,7 @@ impl Task for UserVersionTask {
                 if !diff_files(&mut version_file, &mut current_file) {
                     return Err(Message::UseVersion(MessageData {
                         message: "version could not be used".to_string(),
-                        task: TaskType::UseVersion(UserVersionData{ version: self.input_data.version.clone() }),
+                        task: TaskType::UseVersion(self.input_data.clone()),
                         ..Default::default()
                     }))
                 }
@@ -106,4 +119,108 @@ fn copy_file_version(version_folder: &String, current_folder: &String, files_nam
 
     }
     Ok(Success {})
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DbSwitch {
+    Safe,
+    /// The new version replays the ledger, with the notes of the versions that cause it
+    Replay(Vec<String>),
+    /// The new version is older than the db format
+    Unsafe(Vec<String>),
+    /// The release of one of the versions is not known, with the versions that could not be resolved
+    Unknown(Vec<String>),
+}
+
+/// Compare the version that wrote the db with the new one using the compatibility metadata of the remote config.
+/// Both have to be release numbers, a custom build is resolved with resolve_release first
+pub fn check_db_switch(from: &String, to: &String, items: &[DbCompatibility]) -> DbSwitch {
+    let unknown: Vec<String> = [from, to].iter().filter(|version| verify_version(version).is_err()).map(|version| version.to_string()).collect();
+    if !unknown.is_empty() {
+        return DbSwitch::Unknown(unknown);
+    }
+    if compare_versions(from, to) == Ordering::Equal {
+        return DbSwitch::Safe;
+    }
+
+    let upgrade = compare_versions(from, to) == Ordering::Less;
+    let (low, high) = if upgrade { (from, to) } else { (to, from) };
+
+    let crossed: Vec<&DbCompatibility> = items.iter()
+        .filter(|item| compare_versions(&item.version, low) == Ordering::Greater && compare_versions(&item.version, high) != Ordering::Greater)
+        .collect();
+
+    let notes = |items: Vec<&&DbCompatibility>| -> Vec<String> {
+        items.iter().map(|item| format!("{}: {}", item.version, item.note)).collect()
+    };
+
+    if upgrade {
+        let replay: Vec<&&DbCompatibility> = crossed.iter().filter(|item| item.ledger_replay).collect();
+        if !replay.is_empty() {
+            return DbSwitch::Replay(notes(replay));
+        }
+    } else {
+        let unsafe_items: Vec<&&DbCompatibility> = crossed.iter().filter(|item| !item.downgrade_safe).collect();
+        if !unsafe_items.is_empty() {
+            return DbSwitch::Unsafe(notes(unsafe_items));
+        }
+    }
+    DbSwitch::Safe
+}
+
+/// Release number of an installed version, a build installed under its own name is resolved
+/// with the ref recorded in its build.toml, like `1.35.4` or `v1.35.4`
+fn resolve_release(version: &String, config: &RemoteConfig) -> String {
+    if verify_version(version).is_ok() {
+        return version.to_string();
+    }
+    let version_folder = url_build(vec![&Folder::get_path(Folder::BIN, config), version], false);
+    read_build_info(&version_folder)
+        .map(|info| info.git_ref.trim_start_matches('v').to_string())
+        .filter(|git_ref| verify_version(git_ref).is_ok())
+        .unwrap_or(version.to_string())
+}
+
+/// Stop the switch to `version` when it replays the ledger or can not read the db, unless it was accepted
+pub fn verify_db_switch(version: &String, accept_replay: bool, config: &RemoteConfig) -> Result<Success, Message> {
+    let db_folder = Folder::get_path(Folder::DB, config);
+    let db_is_empty = fs::read_dir(&db_folder).map(|mut entries| entries.next().is_none()).unwrap_or(true);
+    if db_is_empty {
+        return Ok(Success {});
+    }
+
+    let state = get_state()?;
+    let db_version = if state.r#use.db_version.is_empty() { state.r#use.version } else { state.r#use.db_version };
+    if db_version == *version {
+        return Ok(Success {});
+    }
+
+    let from = resolve_release(&db_version, config);
+    let to = resolve_release(version, config);
+    let (message, notes) = match check_db_switch(&from, &to, &config.db_compatibility) {
+        DbSwitch::Safe => return Ok(Success {}),
+        DbSwitch::Replay(notes) => {
+            (format!("Switching the db written by {} to {} replays the ledger, the node will take hours to start", db_version, version), notes)
+        }
+        DbSwitch::Unsafe(notes) => {
+            (format!("The db was written by {} and {} can not read it, restore a backup with [cvm db restore] or bootstrap the db again after the switch", db_version, version), notes)
+        }
+        DbSwitch::Unknown(versions) => {
+            let notes = versions.iter().map(|version| format!("{} is not a release and its build.toml has no release ref", version)).collect();
+            (format!("The release of the db written by {} or of {} is not known, the switch may replay the ledger or not read the db", db_version, version), notes)
+        }
+    };
+
+    let details = notes.iter().map(|note| format!("\n  - {}", note)).collect::<String>();
+
+    if accept_replay {
+        Message::UseVersion(MessageData { message: format!("{}{}", message, details), kind: MessageKind::Warning, ..Default::default() }).print();
+        return Ok(Success {});
+    }
+
+    Err(Message::UseVersion(MessageData {
+        message: format!("{}, run the command again with --accept-replay to continue{}", message, details),
+        kind: MessageKind::Warning,
+        ..Default::default()
+    }))
+}
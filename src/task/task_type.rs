@@ -15,8 +15,7 @@ pub enum TaskType {
     CreateFolderStructure,
     DownloadConfigFiles,
     UpdateConfigFiles,
-    InstallLibsodium,
-    Libsecp256k1,
+    InstallLibrary(String),
     BuildCardanoNode,
     CopyBinFiles(CopyBinInputData),
     UseVersion(UserVersionData),
@@ -28,6 +27,17 @@ pub enum TaskType {
     FileManager(String),
     Permission(String),
     DownloadInstall(String),
+    BackupDb,
+    RestoreDb,
+    ScheduleBackup,
+    BootstrapDb,
+    RecordBuild,
+    CollectCabalBin,
+    BuildComponent(String),
+    Toolchain(String),
+    HealthCheck,
+    ScheduleUpgrade,
+    EditFile(String),
 }
 
 impl Default for TaskType {
@@ -44,7 +54,7 @@ impl TaskType {
             TaskType::InstallGhcup => { "Install ghcup".to_string() }
             TaskType::CreateFolderStructure => { "Creating folder structure".to_string() }
             TaskType::DownloadConfigFiles => { "Downloading scripts and configuration files".to_string() }
-            TaskType::InstallLibsodium => { "Installing libsodium".to_string() }
+            TaskType::InstallLibrary(name) => { format!("Installing {}", name) }
             TaskType::BuildCardanoNode => { "Compiling cardano node".to_string() }
             TaskType::CopyBinFiles(_) => { "Copying generated binary files".to_string() }
             TaskType::UseVersion(_) => { "Switching to the version".to_string() }
@@ -56,8 +66,18 @@ impl TaskType {
             TaskType::Permission(text) => { format!("{} permission", text.to_string()) }
             TaskType::FileManager(text) => { text.to_string() }
             TaskType::UpdateConfigFiles => {"Updating configuration files".to_string() }
-            TaskType::Libsecp256k1 => {"Installing libsecp256k1".to_string()}
             TaskType::DownloadInstall(version) => {format!("Downloading version {}", version)}
+            TaskType::BackupDb => { "Backing up the chain database".to_string() }
+            TaskType::RestoreDb => { "Restoring the chain database".to_string() }
+            TaskType::ScheduleBackup => { "Scheduling the chain database backups".to_string() }
+            TaskType::BootstrapDb => { "Bootstrapping the chain database from the snapshot".to_string() }
+            TaskType::RecordBuild => { "Recording the built commit".to_string() }
+            TaskType::CollectCabalBin => { "Collecting the built binaries".to_string() }
+            TaskType::BuildComponent(name) => { format!("Compiling {}", name) }
+            TaskType::Toolchain(description) => { description.to_string() }
+            TaskType::HealthCheck => { "Waiting for cardano node to follow the chain".to_string() }
+            TaskType::ScheduleUpgrade => { "Scheduling the cardano node upgrade".to_string() }
+            TaskType::EditFile(text) => { text.to_string() }
         }
     }
 }
@@ -72,7 +92,7 @@ impl fmt::Display for TaskType {
             TaskType::InstallGhcup => write!(f, "Task: Install_Haskell_Gsh_Task"),
             TaskType::CreateFolderStructure => write!(f, "Task: Create_Folder_Structure_Task"),
             TaskType::DownloadConfigFiles => write!(f, "Task: Download_Config_File_Task"),
-            TaskType::InstallLibsodium => write!(f, "Task: Install_Libsodium_Task"),
+            TaskType::InstallLibrary(name) => write!(f, "Task: Install_Library | library: {}", name),
             TaskType::BuildCardanoNode => write!(f, "Task: Build_Cardano_Node_Task"),
             TaskType::CopyBinFiles(data) => {
                 write!(f, "Task: Copy_Bin_File_Task Path: {} | FileName: {:?} | Version: {}", data.origin_path, data.files_names, data.version)
@@ -92,8 +112,18 @@ impl fmt::Display for TaskType {
             TaskType::Permission(_) =>  write!(f, "Task: Permission"),
             TaskType::FileManager(_) =>  write!(f, "Task: File_Manager"),
             TaskType::UpdateConfigFiles => write!(f, "Task: Update_Config_File"),
-            TaskType::Libsecp256k1 => write!(f, "Task: Libsecp256k1"),
             TaskType::DownloadInstall(_) => write!(f, "Task: Donwload installer"),
+            TaskType::BackupDb => write!(f, "Task: Backup_Db"),
+            TaskType::RestoreDb => write!(f, "Task: Restore_Db"),
+            TaskType::ScheduleBackup => write!(f, "Task: Schedule_Backup"),
+            TaskType::BootstrapDb => write!(f, "Task: Bootstrap_Db"),
+            TaskType::RecordBuild => write!(f, "Task: Record_Build"),
+            TaskType::CollectCabalBin => write!(f, "Task: Collect_Cabal_Bin"),
+            TaskType::BuildComponent(name) => write!(f, "Task: Build_Component | component: {}", name),
+            TaskType::Toolchain(description) => write!(f, "Task: Toolchain | {}", description),
+            TaskType::HealthCheck => write!(f, "Task: Health_Check"),
+            TaskType::ScheduleUpgrade => write!(f, "Task: Schedule_Upgrade"),
+            TaskType::EditFile(_) => write!(f, "Task: Edit_File"),
         }
     }
 }
\ No newline at end of file
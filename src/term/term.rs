@@ -3,8 +3,10 @@
 use std::io::{Stdout, Write};
 use crossterm::{cursor, ExecutableCommand, QueueableCommand};
 use crossterm::style::Stylize;
+use crate::doctor::check::{CheckResult, CheckStatus};
 use crate::task::task_type::TaskType;
 use crate::term::log_level::LogLevel;
+use crate::utils::node_log::{LogRecord, Severity};
 
 pub struct Term {
     pub stdout: Stdout,
@@ -35,6 +37,40 @@ impl Term {
         let _ = &self.stdout.flush().unwrap();
     }
 
+    pub fn print_check_result(&mut self, name: &String, result: &CheckResult) {
+        let status = match result.status {
+            CheckStatus::Pass => "[PASS]".green(),
+            CheckStatus::Warn => "[WARN]".yellow(),
+            CheckStatus::Fail => "[FAIL]".red(),
+        };
+        let _ = &self.stdout.write_all(format!("{} {}: {}", status, name.as_str().bold(), result.message).as_bytes());
+        let _ = &self.stdout.queue(cursor::MoveToNextLine(1)).unwrap();
+        if !result.hint.is_empty() {
+            let _ = &self.stdout.write_all(format!("       {}", result.hint.as_str().dark_grey()).as_bytes());
+            let _ = &self.stdout.queue(cursor::MoveToNextLine(1)).unwrap();
+        }
+        let _ = &self.stdout.flush().unwrap();
+    }
+
+    /// Log lines end with a plain new line, the output of [cvm logs] is often piped to other tools
+    pub fn print_log_record(&mut self, record: &LogRecord) {
+        let severity = format!("{:<9}", record.severity.as_str());
+        let severity = match record.severity {
+            Severity::Debug => severity.dark_grey(),
+            Severity::Info => severity.green(),
+            Severity::Notice => severity.cyan(),
+            Severity::Warning => severity.yellow(),
+            _ => severity.red().bold(),
+        };
+        let _ = &self.stdout.write_all(format!("{} {} {} {}\n", record.short_time().dark_grey(), severity, record.namespace.as_str().dark_grey(), record.message).as_bytes());
+        let _ = &self.stdout.flush();
+    }
+
+    pub fn print_log_line(&mut self, line: &str) {
+        let _ = &self.stdout.write_all(format!("{}\n", line).as_bytes());
+        let _ = &self.stdout.flush();
+    }
+
     pub fn exit(&mut self) {
         let _ = &self.stdout.execute(cursor::Show).unwrap();
     }
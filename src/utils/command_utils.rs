@@ -0,0 +1,10 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+
+const COMMAND_FOLDERS: [&str; 5] = ["/usr/bin", "/bin", "/usr/sbin", "/sbin", "/usr/local/bin"];
+
+/// The command is installed in one of the system folders, the PATH of a sudo session can't be trusted to find it
+pub fn has_command(command: &str) -> bool {
+    COMMAND_FOLDERS.iter().any(|folder| Path::new(&format!("{}/{}", folder, command)).exists())
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code, unused_variables)]
 
-use std::io::Write;
+use std::io::{Read, Write};
 use std::fs;
 use std::fs::File;
 use std::os::unix::fs::PermissionsExt;
@@ -9,9 +9,9 @@ use crate::url_build;
 use crate::utils::folders::Folder;
 
 pub fn download(url: &String, name: &str) -> Result<String, Message> {
-    let home_dir = Folder::get_home_dir()?;
+    let cvm_home = Folder::get_cvm_home()?;
 
-    let dir_tmp_main = format!("{}/.cvm/tmp/", home_dir);
+    let dir_tmp_main = format!("{}/tmp/", cvm_home);
 
     let mut path = String::new();
     path.push_str(&dir_tmp_main);
@@ -43,4 +43,13 @@ pub fn download_in_path(url: &String, path: String, name: String) -> Result<Stri
     file.write_all(&content.unwrap())?;
 
     Ok(file_path)
-}
\ No newline at end of file
+}
+/// Open the url as a reader, the body is consumed while it is downloaded without keeping it in memory
+pub fn download_stream(url: &String) -> Result<Box<dyn Read>, Message> {
+    let response = reqwest::blocking::get(url)?.error_for_status()?;
+    Ok(Box::new(response))
+}
+
+pub fn download_text(url: &String) -> Result<String, Message> {
+    Ok(reqwest::blocking::get(url)?.error_for_status()?.text()?)
+}
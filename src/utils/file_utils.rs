@@ -0,0 +1,95 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{Read, Write};
+use std::path::Path;
+use ring::digest::{Context, SHA256};
+use crate::{Message, Success};
+
+const TMP_EXTENSION: &str = "tmp";
+const BACKUP_EXTENSION: &str = "bak";
+
+/// Write the file in a temporal file next to it and rename it over the original,
+/// a crash in the middle leaves the previous content untouched.
+/// The previous content is kept as `<file>.bak`
+pub fn write_file_atomic(file_path: &String, data: &[u8]) -> Result<Success, Message> {
+    write_file_atomic_with(file_path, data, true)
+}
+
+/// Same as write_file_atomic, the backup is left as it is when the current file is not worth keeping
+pub fn write_file_atomic_with(file_path: &String, data: &[u8], keep_backup: bool) -> Result<Success, Message> {
+    let tmp_path = format!("{}.{}", file_path, TMP_EXTENSION);
+
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+
+    if keep_backup && Path::new(file_path).exists() {
+        fs::copy(file_path, backup_path(file_path))?;
+    }
+
+    fs::rename(&tmp_path, file_path)?;
+    sync_parent(file_path);
+    Ok(Success {})
+}
+
+pub fn backup_path(file_path: &String) -> String {
+    format!("{}.{}", file_path, BACKUP_EXTENSION)
+}
+
+fn sync_parent(file_path: &String) {
+    if let Some(parent) = Path::new(file_path).parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+/// Sha256 of the file as lowercase hex, read in chunks so big chain files are not loaded in memory
+pub fn sha256_file(file_path: &String) -> Result<String, Message> {
+    let mut file = File::open(file_path)?;
+    let mut context = Context::new(&SHA256);
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let count = file.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        context.update(&buffer[..count]);
+    }
+
+    Ok(to_hex(context.finish().as_ref()))
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Reader that computes the sha256 of everything read through it
+pub struct HashReader<R: Read> {
+    inner: R,
+    context: Context,
+}
+
+impl<R: Read> HashReader<R> {
+    pub fn new(inner: R) -> Self {
+        HashReader { inner, context: Context::new(&SHA256) }
+    }
+
+    /// Read what is left so the digest covers the whole source and return it as lowercase hex
+    pub fn finish(mut self) -> Result<String, Message> {
+        io::copy(&mut self, &mut io::sink())?;
+        Ok(to_hex(self.context.finish().as_ref()))
+    }
+}
+
+impl<R: Read> Read for HashReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.context.update(&buf[..count]);
+        Ok(count)
+    }
+}
@@ -1,10 +1,38 @@
 #![allow(dead_code, unused_variables)]
 
+use std::env;
 use std::str::FromStr;
-use crate::config::remote_config::{RemoteConfig, StructureFolderItem};
+use std::sync::OnceLock;
+use crate::config::remote_config::{is_node_component, RemoteConfig, StructureFolderItem};
+use crate::config::settings_config::get_settings;
 use crate::utils::folders::Folder::*;
 use crate::{Message, url_build};
-use crate::utils::user::get_current_user;
+use users::get_current_uid;
+use crate::utils::user::get_user_home;
+
+pub const CVM_HOME_ENV: &str = "CVM_HOME";
+pub const CVM_WORKSPACE_ENV: &str = "CVM_WORKSPACE";
+pub const GHCUP_PREFIX_ENV: &str = "GHCUP_INSTALL_BASE_PREFIX";
+pub const CVM_ROOTLESS_ENV: &str = "CVM_ROOTLESS";
+const XDG_RUNTIME_DIR_ENV: &str = "XDG_RUNTIME_DIR";
+const DEFAULT_WORKSPACE: &str = "/opt";
+const SYSTEMD_FOLDER: &str = "/etc/systemd/system";
+const USER_SYSTEMD_FOLDER: &str = ".config/systemd/user";
+const CVM_FOLDER: &str = ".cvm";
+const BACKUP_FOLDER: &str = "backups";
+
+static PATHS: OnceLock<Paths> = OnceLock::new();
+
+/// Base locations used by every task, resolved once per execution
+/// with the priority: cli flag / environment variable > settings file > default
+#[derive(Debug, Clone)]
+pub struct Paths {
+    pub home: String,
+    pub cvm_home: String,
+    pub workspace: String,
+    pub ghcup_prefix: String,
+    pub rootless: bool,
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Folder {
@@ -58,13 +86,105 @@ impl Folder {
         &config.structure_folder_item.get(0).unwrap()
     }
 
-    pub fn get_workspaces_dir() -> &'static str {
-        "/opt"
+    pub fn resolve_paths() -> Result<&'static Paths, Message> {
+        if let Some(paths) = PATHS.get() {
+            return Ok(paths);
+        }
+
+        let home = get_user_home()?;
+        let default_cvm_home = url_build(vec![&home, &CVM_FOLDER.to_string()], false);
+
+        // cvm_home can be moved from the settings file in the default location
+        let cvm_home = match env_value(CVM_HOME_ENV) {
+            Some(value) => value,
+            None => non_empty(get_settings(&default_cvm_home)?.cvm_home).unwrap_or(default_cvm_home),
+        };
+
+        let settings = get_settings(&cvm_home)?;
+
+        let rootless = env_value(CVM_ROOTLESS_ENV).map(|value| value != "0" && value != "false").unwrap_or(settings.rootless);
+
+        // without root the workspace has to be somewhere the user can write
+        let default_workspace = if rootless { home.clone() } else { DEFAULT_WORKSPACE.to_string() };
+        let workspace = env_value(CVM_WORKSPACE_ENV)
+            .or(non_empty(settings.workspace))
+            .unwrap_or(default_workspace);
+
+        let ghcup_prefix = env_value(GHCUP_PREFIX_ENV)
+            .or(non_empty(settings.ghcup_prefix))
+            .unwrap_or_else(|| home.clone());
+
+        // ghcup reads the same variable, so the installer and ghcup commands use this location too
+        env::set_var(GHCUP_PREFIX_ENV, &ghcup_prefix);
+
+        // systemctl --user needs the runtime folder of the user manager, missing when cvm runs outside a login session
+        if rootless && env_value(XDG_RUNTIME_DIR_ENV).is_none() {
+            env::set_var(XDG_RUNTIME_DIR_ENV, format!("/run/user/{}", get_current_uid()));
+        }
+
+        let _ = PATHS.set(Paths { home, cvm_home, workspace, ghcup_prefix, rootless });
+        Ok(PATHS.get().unwrap())
+    }
+
+    pub fn get_workspaces_dir() -> String {
+        Folder::resolve_paths().map(|paths| paths.workspace.clone()).unwrap_or_else(|_| DEFAULT_WORKSPACE.to_string())
+    }
+
+    /// True when cvm runs without root and manages the node with user level systemd units
+    pub fn is_rootless() -> bool {
+        Folder::resolve_paths().map(|paths| paths.rootless).unwrap_or(false)
     }
 
+    /// Folder where the systemd units are written, ~/.config/systemd/user in rootless mode
+    pub fn get_systemd_dir() -> Result<String, Message> {
+        let paths = Folder::resolve_paths()?;
+        if paths.rootless {
+            return Ok(url_build(vec![&paths.home, &USER_SYSTEMD_FOLDER.to_string()], false));
+        }
+        Ok(SYSTEMD_FOLDER.to_string())
+    }
+
+    /// Home folder of the user running cvm, taken from the system user database
     pub fn get_home_dir() -> Result<String, Message> {
-        let user = get_current_user()?;
-        Ok(String::from(format!("/home/{}", user)))
+        Ok(Folder::resolve_paths()?.home.clone())
+    }
+
+    /// Folder with the cvm state, remote config, settings and temporal files
+    pub fn get_cvm_home() -> Result<String, Message> {
+        Ok(Folder::resolve_paths()?.cvm_home.clone())
+    }
+
+    pub fn get_ghcup_bin_dir(config: &RemoteConfig) -> Result<String, Message> {
+        Ok(url_build(vec![&Folder::resolve_paths()?.ghcup_prefix, &config.init.ghcup_bin_path], false))
+    }
+
+    /// Folder with the chain database backups, set in the settings file or {root}/backups
+    pub fn get_backup_dir(config: &RemoteConfig) -> Result<String, Message> {
+        let settings = get_settings(&Folder::get_cvm_home()?)?;
+        Ok(non_empty(settings.backup_dir).unwrap_or_else(|| url_build(vec![&Folder::get_path(ROOT, config), &BACKUP_FOLDER.to_string()], false)))
+    }
+
+    /// Folder with the installed versions of a component, cardano-node keeps its versions directly in bin
+    pub fn get_component_bin_dir(component: &String, config: &RemoteConfig) -> String {
+        let bin_folder = Folder::get_path(BIN, config);
+        if is_node_component(component) {
+            return bin_folder;
+        }
+        url_build(vec![&bin_folder, component], false)
+    }
+
+    /// Folder with the binaries of the version in use of a component
+    pub fn get_component_current_dir(component: &String, config: &RemoteConfig) -> String {
+        if is_node_component(component) {
+            return Folder::get_path(CURRENT, config);
+        }
+        let current_name = &Folder::get_folder_item(&CURRENT, config).name;
+        url_build(vec![&Folder::get_component_bin_dir(component, config), current_name], false)
+    }
+
+    /// Replace the {root} placeholder used in remote config patterns with the workspace root folder
+    pub fn fill_root(text: &String, config: &RemoteConfig) -> String {
+        text.replace("{root}", &Folder::get_path(ROOT, config))
     }
 
     pub fn to_string(&self) -> String {
@@ -95,4 +215,12 @@ impl FromStr for Folder {
             _ => Ok(ROOT) // this case never execute
         }
     }
-}
\ No newline at end of file
+}
+
+fn env_value(key: &str) -> Option<String> {
+    env::var(key).ok().and_then(non_empty)
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.trim().is_empty() { None } else { Some(value.trim_end_matches('/').to_string()) }
+}
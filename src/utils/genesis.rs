@@ -0,0 +1,40 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use serde_json::Value;
+use crate::config::remote_config::RemoteConfig;
+use crate::url_build;
+use crate::utils::folders::Folder;
+use crate::utils::time_utils::{parse_utc_iso, unix_now};
+
+const SHELLEY_GENESIS: &str = "shelley-genesis.json";
+
+/// Shelley genesis of the workspace, Null when it is not downloaded yet
+pub fn shelley_genesis(config: &RemoteConfig) -> Value {
+    let genesis = url_build(vec![&Folder::get_path(Folder::FILES, config), &SHELLEY_GENESIS.to_string()], false);
+    fs::read_to_string(genesis).ok().and_then(|content| serde_json::from_str(&content).ok()).unwrap_or(Value::Null)
+}
+
+/// --mainnet or --testnet-magic for cardano-cli
+pub fn network_args(config: &RemoteConfig) -> Vec<String> {
+    let genesis = shelley_genesis(config);
+    match (genesis.get("networkId").and_then(|id| id.as_str()), genesis.get("networkMagic").and_then(|magic| magic.as_u64())) {
+        (Some("Testnet"), Some(magic)) => vec!["--testnet-magic".to_string(), magic.to_string()],
+        _ => vec!["--mainnet".to_string()],
+    }
+}
+
+/// Unix time of the start of the next epoch. The byron epochs of mainnet and preprod last as long
+/// as the shelley ones, so the boundaries are every epoch length since the system start
+pub fn next_epoch_start(config: &RemoteConfig) -> Option<u64> {
+    let genesis = shelley_genesis(config);
+    let system_start = parse_utc_iso(genesis.get("systemStart")?.as_str()?)?;
+    let epoch_length = genesis.get("epochLength")?.as_u64()? as f64 * genesis.get("slotLength")?.as_f64()?;
+    let epoch_length = epoch_length as u64;
+    if epoch_length == 0 {
+        return None;
+    }
+
+    let elapsed = unix_now().saturating_sub(system_start);
+    Some(system_start + (elapsed / epoch_length + 1) * epoch_length)
+}
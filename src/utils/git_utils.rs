@@ -0,0 +1,29 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use std::process::Command;
+use crate::{Message, MessageData};
+
+/// Run git inside the folder and return its trimmed stdout
+pub fn git_output(folder: &String, args: Vec<&str>) -> Result<String, Message> {
+    let output = Command::new("git").args(&args).current_dir(folder).output()?;
+    if !output.status.success() {
+        return Err(Message::CommandOutputError(MessageData {
+            message: format!("Error running git {} in {}", args.join(" "), folder),
+            stack: vec![String::from_utf8_lossy(&output.stderr).to_string()],
+            ..Default::default()
+        }));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// True when the folder is a clone of the repository
+pub fn is_clone_of(folder: &String, repository: &String) -> bool {
+    if !Path::new(folder).join(".git").exists() {
+        return false;
+    }
+    match git_output(folder, vec!["remote", "get-url", "origin"]) {
+        Ok(url) => url.trim_end_matches('/').trim_end_matches(".git") == repository.trim_end_matches('/').trim_end_matches(".git"),
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,323 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::path::Path;
+use serde_json::Value;
+use crate::config::remote_config::{JsonPatch, RemoteConfig};
+use crate::{Message, MessageData, Success};
+use crate::utils::file_utils::write_file_atomic;
+use crate::utils::folders::Folder;
+
+const ADD: &str = "add";
+const REMOVE: &str = "remove";
+const REPLACE: &str = "replace";
+const MOVE: &str = "move";
+const COPY: &str = "copy";
+const TEST: &str = "test";
+/// Root folder of the guild operators files, replaced by the workspace of cvm
+const GUILD_ROOT: &str = "/opt/cardano/cnode";
+
+/// Apply the patches of the remote config to a downloaded json file. `{root}` is replaced in the string values.
+/// The file is only written when every operation applies, an operation whose path is missing means the
+/// upstream file changed and it is reported instead of leaving the file half patched
+pub fn apply_json_patch_file(file_path: &String, patches: &[JsonPatch], config: &RemoteConfig) -> Result<Success, Message> {
+    if patches.is_empty() {
+        return Ok(Success {});
+    }
+
+    let mut json: Value = serde_json::from_str(&fs::read_to_string(file_path)?)
+        .map_err(|error| patch_error(format!("{} is not a json file, it can't be patched: {}", file_path, error)))?;
+
+    let patches: Vec<JsonPatch> = patches.iter().map(|patch| JsonPatch {
+        value: patch.value.as_ref().map(|value| fill_root(value, config)),
+        ..patch.clone()
+    }).collect();
+    let name = Path::new(file_path).file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    patch_upstream(&mut json, &patches).map_err(|error| patch_error(format!("The upstream {} changed, {}", name, error)))?;
+
+    // the file was just downloaded, there is no previous content to keep
+    fs::write(file_path, json_text(&json)?)?;
+    Ok(Success {})
+}
+
+/// Patch a file of the guild operators, every path in their root folder has to be patched.
+/// A path the patches don't know, like the genesis file of a new era, would point to a folder that doesn't exist
+fn patch_upstream(json: &mut Value, patches: &[JsonPatch]) -> Result<(), String> {
+    apply_patches(json, patches).map_err(|error| format!("its patch no longer matches: {}", error))?;
+
+    let unpatched = paths_containing(json, GUILD_ROOT, "");
+    if !unpatched.is_empty() {
+        return Err(format!("it has paths in {} that the remote config doesn't patch: {}", GUILD_ROOT, unpatched.join(", ")));
+    }
+    Ok(())
+}
+
+/// Write the json of a file edited by the operator, the previous content is kept as `<file>.bak`
+pub fn write_json(file_path: &String, json: &Value) -> Result<Success, Message> {
+    write_file_atomic(file_path, json_text(json)?.as_bytes())
+}
+
+fn json_text(json: &Value) -> Result<String, Message> {
+    let mut text = serde_json::to_string_pretty(json).map_err(|error| patch_error(error.to_string()))?;
+    text.push('\n');
+    Ok(text)
+}
+
+/// RFC 6902 patch, the operations are applied in order and none of them is kept if one fails
+pub fn apply_patches(json: &mut Value, patches: &[JsonPatch]) -> Result<(), String> {
+    let mut patched = json.clone();
+    for patch in patches {
+        apply(&mut patched, patch).map_err(|error| format!("{} {}: {}", patch.op, patch.path, error))?;
+    }
+    *json = patched;
+    Ok(())
+}
+
+fn apply(json: &mut Value, patch: &JsonPatch) -> Result<(), String> {
+    let value = || patch.value.clone().ok_or("the operation has no value".to_string());
+    match patch.op.as_str() {
+        ADD => add(json, &patch.path, value()?),
+        REMOVE => remove(json, &patch.path).map(|_| ()),
+        REPLACE => {
+            *get_mut(json, &patch.path)? = value()?;
+            Ok(())
+        }
+        MOVE => {
+            let moved = remove(json, &patch.from)?;
+            add(json, &patch.path, moved)
+        }
+        COPY => {
+            let copied = get_mut(json, &patch.from)?.clone();
+            add(json, &patch.path, copied)
+        }
+        TEST => {
+            let expected = value()?;
+            let actual = get_mut(json, &patch.path)?;
+            if *actual != expected {
+                return Err(format!("the value is {} instead of {}", actual, expected));
+            }
+            Ok(())
+        }
+        other => Err(format!("unknown operation {}, use {}, {}, {}, {}, {} or {}", other, ADD, REMOVE, REPLACE, MOVE, COPY, TEST)),
+    }
+}
+
+/// Key of [cvm node-config] as a json pointer, a dotted path like `setupScribes.0.scName` is converted
+pub fn to_pointer(key: &str) -> String {
+    if key.is_empty() || key.starts_with('/') {
+        return key.to_string();
+    }
+    key.split('.').map(|token| format!("/{}", escape(token))).collect()
+}
+
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn tokens(path: &str) -> Result<Vec<String>, String> {
+    if path.is_empty() {
+        return Ok(vec![]);
+    }
+    if !path.starts_with('/') {
+        return Err("the path must start with /".to_string());
+    }
+    Ok(path[1..].split('/').map(|token| token.replace("~1", "/").replace("~0", "~")).collect())
+}
+
+pub fn get_mut<'a>(json: &'a mut Value, path: &str) -> Result<&'a mut Value, String> {
+    tokens(path)?;
+    json.pointer_mut(path).ok_or("the path doesn't exist".to_string())
+}
+
+/// Parent of the path and the last token, the parent has to exist
+fn parent<'a>(json: &'a mut Value, path: &str) -> Result<(&'a mut Value, String), String> {
+    let mut tokens = tokens(path)?;
+    let last = tokens.pop().ok_or("the whole document can't be changed".to_string())?;
+    let parent_path: String = tokens.iter().map(|token| format!("/{}", escape(token))).collect();
+    let parent = json.pointer_mut(&parent_path).ok_or("the parent of the path doesn't exist".to_string())?;
+    Ok((parent, last))
+}
+
+fn add(json: &mut Value, path: &str, value: Value) -> Result<(), String> {
+    let (parent, last) = parent(json, path)?;
+    match parent {
+        Value::Object(object) => {
+            object.insert(last, value);
+            Ok(())
+        }
+        Value::Array(array) => {
+            let index = if last == "-" { array.len() } else { array_index(&last, array.len() + 1)? };
+            array.insert(index, value);
+            Ok(())
+        }
+        _ => Err("the parent of the path is not an object or an array".to_string()),
+    }
+}
+
+fn remove(json: &mut Value, path: &str) -> Result<Value, String> {
+    let (parent, last) = parent(json, path)?;
+    match parent {
+        Value::Object(object) => {
+            // serde_json is built with preserve_order, retain keeps the order of the other keys
+            // while remove would swap the last one in its place
+            let removed = object.get(&last).cloned().ok_or("the path doesn't exist".to_string())?;
+            object.retain(|key, _| key != &last);
+            Ok(removed)
+        }
+        Value::Array(array) => {
+            let index = array_index(&last, array.len())?;
+            Ok(array.remove(index))
+        }
+        _ => Err("the path doesn't exist".to_string()),
+    }
+}
+
+fn array_index(token: &str, len: usize) -> Result<usize, String> {
+    match token.parse::<usize>() {
+        Ok(index) if index < len => Ok(index),
+        _ => Err(format!("{} is not an index of the array", token)),
+    }
+}
+
+/// Pointers of the string values that contain the text
+fn paths_containing(json: &Value, text: &str, pointer: &str) -> Vec<String> {
+    match json {
+        Value::String(value) if value.contains(text) => vec![pointer.to_string()],
+        Value::Array(items) => items.iter().enumerate().flat_map(|(index, item)| paths_containing(item, text, &format!("{}/{}", pointer, index))).collect(),
+        Value::Object(object) => object.iter().flat_map(|(key, item)| paths_containing(item, text, &format!("{}/{}", pointer, escape(key)))).collect(),
+        _ => vec![],
+    }
+}
+
+fn fill_root(value: &Value, config: &RemoteConfig) -> Value {
+    match value {
+        Value::String(text) => Value::String(Folder::fill_root(text, config)),
+        Value::Array(items) => Value::Array(items.iter().map(|item| fill_root(item, config)).collect()),
+        Value::Object(object) => Value::Object(object.iter().map(|(key, item)| (key.to_string(), fill_root(item, config))).collect()),
+        other => other.clone(),
+    }
+}
+
+fn patch_error(message: String) -> Message {
+    Message::UpdateConfigFile(MessageData {
+        message,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(op: &str, path: &str, value: Option<Value>) -> JsonPatch {
+        JsonPatch { op: op.to_string(), path: path.to_string(), value, ..Default::default() }
+    }
+
+    fn patch_from(op: &str, from: &str, path: &str) -> JsonPatch {
+        JsonPatch { op: op.to_string(), path: path.to_string(), from: from.to_string(), ..Default::default() }
+    }
+
+    fn config() -> Value {
+        json!({
+            "ByronGenesisFile": "/opt/cardano/cnode/files/byron-genesis.json",
+            "EnableP2P": false,
+            "defaultScribes": [["FileSK", "/opt/cardano/cnode/logs/node0.json"], ["StdoutSK", "stdout"]],
+            "a/b": 1,
+            "m~n": 2
+        })
+    }
+
+    #[test]
+    fn add_and_replace() {
+        let mut json = config();
+        apply_patches(&mut json, &[
+            patch(ADD, "/TraceMempool", Some(json!(true))),
+            patch(REPLACE, "/EnableP2P", Some(json!(true))),
+            patch(ADD, "/defaultScribes/-", Some(json!(["JournalSK", "cnode"]))),
+            patch(ADD, "/defaultScribes/0", Some(json!(["FileSK", "first"]))),
+        ]).unwrap();
+        assert_eq!(json["TraceMempool"], json!(true));
+        assert_eq!(json["EnableP2P"], json!(true));
+        assert_eq!(json["defaultScribes"].as_array().unwrap().len(), 4);
+        assert_eq!(json["defaultScribes"][0][1], json!("first"));
+        assert_eq!(json["defaultScribes"][3][0], json!("JournalSK"));
+
+        // replace needs the path, add past the end of an array is not an index
+        assert!(apply_patches(&mut json, &[patch(REPLACE, "/Missing", Some(json!(1)))]).is_err());
+        assert!(apply_patches(&mut json, &[patch(ADD, "/defaultScribes/9", Some(json!(1)))]).is_err());
+        assert!(apply_patches(&mut json, &[patch(ADD, "/Missing/key", Some(json!(1)))]).is_err());
+    }
+
+    #[test]
+    fn remove_keeps_the_key_order() {
+        let mut json = config();
+        apply_patches(&mut json, &[patch(REMOVE, "/ByronGenesisFile", None), patch(REMOVE, "/defaultScribes/1", None)]).unwrap();
+        let keys: Vec<&String> = json.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["EnableP2P", "defaultScribes", "a/b", "m~n"]);
+        assert_eq!(json["defaultScribes"].as_array().unwrap().len(), 1);
+        assert!(apply_patches(&mut json, &[patch(REMOVE, "/ByronGenesisFile", None)]).is_err());
+    }
+
+    #[test]
+    fn move_copy_and_test() {
+        let mut json = config();
+        apply_patches(&mut json, &[
+            patch(TEST, "/EnableP2P", Some(json!(false))),
+            patch_from(COPY, "/EnableP2P", "/EnableP2PCopy"),
+            patch_from(MOVE, "/defaultScribes/1", "/stdoutScribe"),
+        ]).unwrap();
+        assert_eq!(json["EnableP2PCopy"], json!(false));
+        assert_eq!(json["stdoutScribe"], json!(["StdoutSK", "stdout"]));
+        assert_eq!(json["defaultScribes"].as_array().unwrap().len(), 1);
+
+        let error = apply_patches(&mut json, &[patch(TEST, "/EnableP2P", Some(json!(true)))]).unwrap_err();
+        assert!(error.contains("instead of"), "{}", error);
+        assert!(apply_patches(&mut json, &[patch("merge", "/EnableP2P", None)]).is_err());
+    }
+
+    #[test]
+    fn escaped_tokens() {
+        assert_eq!(to_pointer("a/b"), "/a~1b");
+        assert_eq!(to_pointer("m~n"), "/m~0n");
+        assert_eq!(to_pointer("setupScribes.0.scName"), "/setupScribes/0/scName");
+        assert_eq!(to_pointer("/already/a/pointer"), "/already/a/pointer");
+
+        let mut json = config();
+        apply_patches(&mut json, &[
+            patch(REPLACE, "/a~1b", Some(json!(10))),
+            patch(REPLACE, "/m~0n", Some(json!(20))),
+        ]).unwrap();
+        assert_eq!(json["a/b"], json!(10));
+        assert_eq!(json["m~n"], json!(20));
+        assert!(get_mut(&mut json, "no-slash").is_err());
+    }
+
+    #[test]
+    fn failed_patch_changes_nothing() {
+        let mut json = config();
+        let result = apply_patches(&mut json, &[
+            patch(REPLACE, "/EnableP2P", Some(json!(true))),
+            patch(REMOVE, "/ByronGenesisFile", None),
+            patch(TEST, "/defaultScribes/0/0", Some(json!("JournalSK"))),
+        ]);
+        assert!(result.is_err());
+        assert_eq!(json, config());
+    }
+
+    #[test]
+    fn upstream_paths_must_be_patched() {
+        let patches = [
+            patch(REPLACE, "/ByronGenesisFile", Some(json!("/home/cvm/files/byron-genesis.json"))),
+            patch(REPLACE, "/defaultScribes/0/1", Some(json!("/home/cvm/logs/node0.json"))),
+        ];
+        let mut json = config();
+        patch_upstream(&mut json, &patches).unwrap();
+
+        // a new era adds a genesis file the remote config doesn't know yet
+        let mut json = config();
+        json["ConwayGenesisFile"] = json!("/opt/cardano/cnode/files/conway-genesis.json");
+        let error = patch_upstream(&mut json, &patches).unwrap_err();
+        assert!(error.ends_with("/ConwayGenesisFile"), "{}", error);
+    }
+}
@@ -0,0 +1,56 @@
+#![allow(dead_code, unused_variables)]
+
+use std::path::Path;
+use rusqlite::{Connection, OpenFlags};
+use crate::config::remote_config::RemoteConfig;
+use crate::{Message, MessageData, url_build};
+use crate::utils::folders::Folder;
+use crate::utils::time_utils::parse_rfc3339;
+
+/// Database where cncli.sh saves the leaderlog of the pool, inside the guild-db folder
+const BLOCKLOG_DB: &str = "blocklog/blocklog.db";
+/// Status of the slots of the leaderlog that are not minted yet
+const LEADER_STATUS: &str = "leader";
+
+/// Slot where the pool is leader, `at` is the unix time of the slot
+pub struct ScheduledBlock {
+    pub slot: u64,
+    pub epoch: u64,
+    pub at: u64,
+}
+
+pub fn blocklog_db(config: &RemoteConfig) -> String {
+    url_build(vec![&Folder::get_path(Folder::GUILDDB, config), &BLOCKLOG_DB.to_string()], false)
+}
+
+/// Only the block producers run the cncli leaderlog, there is no database on a relay
+pub fn has_leader_schedule(config: &RemoteConfig) -> bool {
+    Path::new(&blocklog_db(config)).exists()
+}
+
+/// Blocks of the leaderlog scheduled after from and until to, sorted by time
+pub fn scheduled_blocks(config: &RemoteConfig, from: u64, to: u64) -> Result<Vec<ScheduledBlock>, Message> {
+    let db = blocklog_db(config);
+    let error = |error: rusqlite::Error| Message::ServiceManager(MessageData {
+        message: format!("Error reading the leader schedule in {}: {}", db, error),
+        ..Default::default()
+    });
+
+    // read only, cncli.sh may be writing the leaderlog at the same time
+    let connection = Connection::open_with_flags(&db, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(error)?;
+    let mut statement = connection.prepare("SELECT slot, epoch, at FROM blocklog WHERE status = ?1").map_err(error)?;
+    let rows = statement.query_map([LEADER_STATUS], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?))).map_err(error)?;
+
+    // the time is saved with the zone of the pool, it is compared after the parse
+    let mut blocks = vec![];
+    for row in rows {
+        let (slot, epoch, at) = row.map_err(error)?;
+        if let Some(at) = parse_rfc3339(&at) {
+            if at > from && at <= to {
+                blocks.push(ScheduledBlock { slot: slot as u64, epoch: epoch as u64, at });
+            }
+        }
+    }
+    blocks.sort_by_key(|block| block.at);
+    Ok(blocks)
+}
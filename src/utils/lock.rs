@@ -0,0 +1,66 @@
+#![allow(dead_code, unused_variables)]
+
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::os::unix::io::AsRawFd;
+use crossterm::style::Stylize;
+use crate::{Message, MessageData, url_build};
+use crate::utils::folders::Folder;
+
+const LOCK_FILE: &str = "cvm.lock";
+/// Pid of the process that holds the lock, so the same command escalated with sudo does not wait for itself.
+/// The variable is inherited by anything started later, it is only trusted when that pid is an ancestor
+const LOCK_ENV: &str = "CVM_LOCK_HELD";
+
+/// Exclusive advisory lock over the cvm home, held while the returned file is alive.
+/// Another cvm process waits until the current operation finishes
+pub fn lock_cvm_home() -> Result<Option<File>, Message> {
+    let holder = env::var(LOCK_ENV).ok().and_then(|pid| pid.parse::<u32>().ok());
+    if holder.map(is_ancestor).unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let cvm_home = Folder::get_cvm_home()?;
+    fs::create_dir_all(&cvm_home)?;
+    let lock_path = url_build(vec![&cvm_home, &LOCK_FILE.to_string()], false);
+    let file = File::options().create(true).write(true).open(&lock_path)?;
+
+    if !flock(&file, libc::LOCK_EX | libc::LOCK_NB) {
+        println!("{}", "Another cvm process is running, waiting for it to finish...".yellow());
+        if !flock(&file, libc::LOCK_EX) {
+            return Err(Message::LockFile(MessageData {
+                message: format!("Could not lock the file {}", lock_path),
+                ..Default::default()
+            }));
+        }
+    }
+
+    env::set_var(LOCK_ENV, std::process::id().to_string());
+    Ok(Some(file))
+}
+
+fn flock(file: &File, operation: libc::c_int) -> bool {
+    unsafe { libc::flock(file.as_raw_fd(), operation) == 0 }
+}
+
+/// Walk up the parents of this process, sudo sits between the holder and the escalated cvm
+fn is_ancestor(pid: u32) -> bool {
+    let mut current = std::process::id();
+    while current > 1 {
+        current = match parent_pid(current) {
+            Some(parent) => parent,
+            None => return false,
+        };
+        if current == pid {
+            return true;
+        }
+    }
+    false
+}
+
+/// The fourth field of /proc/<pid>/stat, read after the command name that can contain spaces
+fn parent_pid(pid: u32) -> Option<u32> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    stat[stat.rfind(')')? + 1..].split_whitespace().nth(1)?.parse().ok()
+}
@@ -3,3 +3,15 @@ pub mod version_utils;
 pub mod url_build;
 pub mod folders;
 pub mod user;
+pub mod file_utils;
+pub mod lock;
+pub mod time_utils;
+pub mod git_utils;
+pub mod node_log;
+pub mod node_process;
+pub mod genesis;
+pub mod leader_schedule;
+pub mod topology;
+pub mod json_patch;
+pub mod sed;
+pub mod command_utils;
@@ -0,0 +1,169 @@
+#![allow(dead_code, unused_variables)]
+
+use regex::Regex;
+use serde_json::{Map, Value};
+use crate::{Message, MessageData};
+use crate::utils::time_utils::{unix_now, utc_iso};
+
+/// Severities of the cardano-node log records, from the least to the most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Debug,
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+impl Severity {
+    pub fn parse(value: &str) -> Option<Severity> {
+        match value.to_lowercase().as_str() {
+            "debug" => Some(Severity::Debug),
+            "info" => Some(Severity::Info),
+            "notice" => Some(Severity::Notice),
+            "warning" | "warn" => Some(Severity::Warning),
+            "error" | "err" => Some(Severity::Error),
+            "critical" | "crit" => Some(Severity::Critical),
+            "alert" => Some(Severity::Alert),
+            "emergency" | "emerg" => Some(Severity::Emergency),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Debug => "Debug",
+            Severity::Info => "Info",
+            Severity::Notice => "Notice",
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+            Severity::Critical => "Critical",
+            Severity::Alert => "Alert",
+            Severity::Emergency => "Emergency",
+        }
+    }
+}
+
+/// Structured record written by the node, both the legacy (iohk-monitoring) and the new tracing system
+/// use the `at`, `sev`, `ns` and `data` fields
+pub struct LogRecord {
+    pub at: String,
+    pub severity: Severity,
+    pub namespace: String,
+    pub message: String,
+}
+
+impl LogRecord {
+    /// `2022-08-01T12:00:00.12Z` is shown as `2022-08-01 12:00:00`
+    pub fn short_time(&self) -> String {
+        self.at.chars().take(19).collect::<String>().replacen('T', " ", 1)
+    }
+}
+
+/// None when the line is not a json record, like the output of cnode.sh before the node starts.
+/// Prefixes added by the log collectors, like the svlogd timestamp, are skipped
+pub fn parse_record(line: &str) -> Option<LogRecord> {
+    let json: Value = serde_json::from_str(line[line.find('{')?..].trim()).ok()?;
+    let object = json.as_object()?;
+
+    let at = object.get("at")?.as_str()?.to_string();
+    let severity = Severity::parse(object.get("sev")?.as_str()?)?;
+    let namespace = match object.get("ns") {
+        Some(Value::String(namespace)) => namespace.to_string(),
+        Some(Value::Array(namespaces)) => namespaces.iter().filter_map(|namespace| namespace.as_str()).collect::<Vec<&str>>().join(","),
+        _ => String::new(),
+    };
+
+    let msg = object.get("msg").and_then(|msg| msg.as_str()).unwrap_or("");
+    let message = if !msg.is_empty() {
+        msg.to_string()
+    } else {
+        match object.get("data") {
+            Some(Value::String(data)) => data.to_string(),
+            Some(Value::Object(data)) => format_data(data),
+            Some(data) => data.to_string(),
+            None => String::new(),
+        }
+    };
+
+    Some(LogRecord { at, severity, namespace, message })
+}
+
+/// The kind of the trace first and then the rest of the fields as key=value
+fn format_data(data: &Map<String, Value>) -> String {
+    let mut fields = vec![];
+    if let Some(kind) = data.get("kind").and_then(|kind| kind.as_str()) {
+        fields.push(kind.to_string());
+    }
+    for (key, value) in data.iter().filter(|(key, _)| key.as_str() != "kind") {
+        match value {
+            Value::String(value) => fields.push(format!("{}={}", key, value)),
+            value => fields.push(format!("{}={}", key, value)),
+        }
+    }
+    fields.join(" ")
+}
+
+/// Filters of [cvm logs], the lines that are not records only pass when there is no severity,
+/// namespace or time filter
+#[derive(Default)]
+pub struct LogFilter {
+    pub level: Option<Severity>,
+    pub namespace: Option<String>,
+    pub grep: Option<Regex>,
+    /// Oldest time shown, as `YYYY-MM-DDTHH:MM:SS`
+    pub since: Option<String>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, line: &str, record: &Option<LogRecord>) -> bool {
+        if let Some(grep) = &self.grep {
+            if !grep.is_match(line) {
+                return false;
+            }
+        }
+
+        match record {
+            Some(record) => {
+                self.level.map(|level| record.severity >= level).unwrap_or(true)
+                    && self.namespace.as_ref().map(|namespace| record.namespace.to_lowercase().contains(&namespace.to_lowercase())).unwrap_or(true)
+                    && self.since.as_ref().map(|since| record.at.chars().take(19).collect::<String>() >= *since).unwrap_or(true)
+            }
+            None => self.level.is_none() && self.namespace.is_none() && self.since.is_none(),
+        }
+    }
+}
+
+/// Parse the value of --since, a duration back from now (30s, 15m, 2h, 1d, 1w) or a UTC date (2022-08-01, 2022-08-01 12:00[:00])
+/// and return it as `YYYY-MM-DDTHH:MM:SS`
+pub fn parse_since(value: &str) -> Result<String, Message> {
+    let duration = Regex::new(r"^(\d+)([smhdw])$").unwrap();
+    if let Some(captures) = duration.captures(value.trim()) {
+        let amount = captures[1].parse::<u64>().unwrap_or(0);
+        let unit = match &captures[2] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            "d" => 86400,
+            _ => 604800,
+        };
+        let since = utc_iso(unix_now().saturating_sub(amount * unit));
+        return Ok(since.trim_end_matches('Z').to_string());
+    }
+
+    let date = Regex::new(r"^(\d{4}-\d{2}-\d{2})(?:[ T](\d{2}:\d{2})(:\d{2})?)?Z?$").unwrap();
+    match date.captures(value.trim()) {
+        Some(captures) => {
+            let time = captures.get(2).map(|time| time.as_str()).unwrap_or("00:00");
+            let seconds = captures.get(3).map(|seconds| seconds.as_str()).unwrap_or(":00");
+            Ok(format!("{}T{}{}", &captures[1], time, seconds))
+        }
+        None => Err(Message::ParseArg(MessageData {
+            message: format!("The value {} of --since must be a duration like 30m, 2h or 1d, or a UTC date like 2022-08-01 12:00", value),
+            ..Default::default()
+        }))
+    }
+}
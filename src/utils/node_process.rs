@@ -0,0 +1,92 @@
+#![allow(dead_code, unused_variables)]
+
+use std::fs;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::Value;
+use crate::config::remote_config::RemoteConfig;
+use crate::url_build;
+use crate::utils::folders::Folder;
+use crate::utils::genesis::network_args;
+
+const NODE_BIN: &str = "cardano-node";
+const CLI_BIN: &str = "cardano-cli";
+/// Socket set in the env file of the guild scripts
+const NODE_SOCKET: &str = "node0.socket";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Pids of the cardano-node processes running with this workspace, the service manager
+/// only knows the script that started them
+pub fn node_pids(config: &RemoteConfig) -> Vec<i32> {
+    let root = Folder::get_path(Folder::ROOT, config);
+    let entries = match fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    entries.filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_string_lossy().parse::<i32>().ok())
+        .filter(|pid| {
+            let cmdline = fs::read(format!("/proc/{}/cmdline", pid)).unwrap_or_default();
+            let args: Vec<String> = cmdline.split(|byte| *byte == 0).map(|arg| String::from_utf8_lossy(arg).to_string()).collect();
+            let is_node = args.first().map(|bin| Path::new(bin).file_name().map(|name| name == NODE_BIN).unwrap_or(false)).unwrap_or(false);
+            is_node && args.iter().any(|arg| arg.starts_with(&root))
+        })
+        .collect()
+}
+
+/// Wait until the node closes the database and exits, it is killed when the timeout expires.
+/// Returns false when the node had to be killed
+pub fn wait_node_exit(config: &RemoteConfig, timeout: Duration) -> bool {
+    if wait_until(timeout, || node_pids(config).is_empty()) {
+        return true;
+    }
+    for pid in node_pids(config) {
+        unsafe { libc::kill(pid, libc::SIGKILL); }
+    }
+    wait_until(Duration::from_secs(5), || node_pids(config).is_empty());
+    false
+}
+
+/// SIGHUP makes the node read the topology again, a P2P node applies it without a restart.
+/// Returns the number of nodes signaled
+pub fn reload_node(config: &RemoteConfig) -> usize {
+    node_pids(config).into_iter().filter(|pid| unsafe { libc::kill(*pid, libc::SIGHUP) } == 0).count()
+}
+
+pub fn socket_path(config: &RemoteConfig) -> String {
+    url_build(vec![&Folder::get_path(Folder::SOCKETS, config), &NODE_SOCKET.to_string()], false)
+}
+
+/// The socket file is left behind when the node is killed, only a connection tells that it is listening
+pub fn socket_accepts(config: &RemoteConfig) -> bool {
+    UnixStream::connect(socket_path(config)).is_ok()
+}
+
+/// Slot of the tip of the node, with the cardano-cli of the version in use
+pub fn query_tip_slot(config: &RemoteConfig) -> Option<u64> {
+    let cli = url_build(vec![&Folder::get_path(Folder::CURRENT, config), &CLI_BIN.to_string()], false);
+    let mut args = vec!["query".to_string(), "tip".to_string()];
+    args.extend(network_args(config));
+
+    let output = Command::new(cli).args(&args).env("CARDANO_NODE_SOCKET_PATH", socket_path(config)).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let tip: Value = serde_json::from_slice(&output.stdout).ok()?;
+    tip.get("slot")?.as_u64()
+}
+
+pub fn wait_until<F: FnMut() -> bool>(timeout: Duration, mut condition: F) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    condition()
+}
@@ -0,0 +1,511 @@
+#![allow(dead_code, unused_variables)]
+
+use regex::{Regex, RegexBuilder};
+
+/// The subset of sed used by the remote config: `s` commands with the g, i and number flags, separated by `;`,
+/// addressed by a line (`2`, `$`), a basic regular expression (`/^#CONFIG=/`) or a range of them (`2,80`).
+/// The expressions are applied to every line in order, like `sed -i -e <script>`
+pub struct SedScript {
+    expressions: Vec<SedExpression>,
+}
+
+/// Lines of the address of an expression and the substitutions it made
+pub struct SedReport {
+    pub expression: String,
+    pub lines: usize,
+    pub substitutions: usize,
+    /// The expression is addressed by a regular expression, it only needs to find its lines
+    pub by_pattern: bool,
+}
+
+impl SedReport {
+    /// An expression that finds nothing means the file changed upstream.
+    /// A pattern address that finds its lines is enough, the lines may already have the change
+    pub fn matched(&self) -> bool {
+        if self.by_pattern { self.lines > 0 } else { self.substitutions > 0 }
+    }
+}
+
+struct SedExpression {
+    text: String,
+    start: Option<Address>,
+    end: Option<Address>,
+    regex: Regex,
+    replacement: String,
+    global: bool,
+    /// Only the nth match is replaced, 0 when the flag is not set
+    occurrence: usize,
+}
+
+enum Address {
+    Line(usize),
+    Last,
+    Pattern(Regex),
+}
+
+impl Address {
+    fn matches(&self, number: usize, line: &str, last: bool) -> bool {
+        match self {
+            Address::Line(line_number) => number == *line_number,
+            Address::Last => last,
+            Address::Pattern(regex) => regex.is_match(line),
+        }
+    }
+}
+
+impl SedScript {
+    pub fn parse(script: &str) -> Result<SedScript, String> {
+        let chars: Vec<char> = script.chars().collect();
+        let mut position = 0;
+        let mut expressions = vec![];
+
+        while position < chars.len() {
+            if chars[position] == ';' || chars[position].is_whitespace() {
+                position += 1;
+                continue;
+            }
+            let begin = position;
+            let start = parse_address(&chars, &mut position)?;
+            let end = match (&start, chars.get(position)) {
+                (Some(_), Some(',')) => {
+                    position += 1;
+                    Some(parse_address(&chars, &mut position)?.ok_or(format!("the range at {} has no end", begin + 1))?)
+                }
+                _ => None,
+            };
+
+            match chars.get(position) {
+                Some('s') => position += 1,
+                Some(command) => return Err(format!("the command {} at {} is not supported, only s is", command, position + 1)),
+                None => return Err("the script ends without a command".to_string()),
+            }
+
+            let delimiter = *chars.get(position).ok_or("the s command has no delimiter".to_string())?;
+            position += 1;
+            let pattern = read_until(&chars, &mut position, delimiter, true)?;
+            let replacement = read_until(&chars, &mut position, delimiter, false)?;
+
+            let mut global = false;
+            let mut insensitive = false;
+            let mut occurrence = String::new();
+            while let Some(flag) = chars.get(position).filter(|flag| **flag != ';') {
+                match flag {
+                    'g' => global = true,
+                    'i' | 'I' => insensitive = true,
+                    digit if digit.is_ascii_digit() => occurrence.push(*digit),
+                    other => return Err(format!("the flag {} at {} is not supported", other, position + 1)),
+                }
+                position += 1;
+            }
+
+            let text: String = chars[begin..position].iter().collect();
+            let regex = RegexBuilder::new(&basic_to_regex(&pattern)).case_insensitive(insensitive).build()
+                .map_err(|error| format!("the expression {} has an invalid regular expression: {}", text, error))?;
+            expressions.push(SedExpression {
+                text,
+                start,
+                end,
+                regex,
+                replacement: translate_replacement(&replacement, delimiter),
+                global,
+                occurrence: occurrence.parse().unwrap_or(0),
+            });
+        }
+        Ok(SedScript { expressions })
+    }
+
+    /// The edited text and the report of every expression
+    pub fn apply(&self, text: &str) -> (String, Vec<SedReport>) {
+        let trailing_newline = text.ends_with('\n');
+        let mut lines: Vec<String> = text.split('\n').map(|line| line.to_string()).collect();
+        if trailing_newline {
+            lines.pop();
+        }
+
+        let mut reports: Vec<SedReport> = self.expressions.iter().map(|expression| SedReport {
+            expression: expression.text.to_string(),
+            lines: 0,
+            substitutions: 0,
+            by_pattern: matches!(expression.start, Some(Address::Pattern(_))) || matches!(expression.end, Some(Address::Pattern(_))),
+        }).collect();
+        let mut in_range = vec![false; self.expressions.len()];
+
+        let count = lines.len();
+        for (index, line) in lines.iter_mut().enumerate() {
+            let number = index + 1;
+            let last = number == count;
+            for (position, expression) in self.expressions.iter().enumerate() {
+                if !expression.selects(&mut in_range[position], number, line, last) {
+                    continue;
+                }
+                let (edited, substitutions) = expression.substitute(line);
+                // without an address every line is selected, only the edited ones are worth counting
+                if expression.start.is_some() || substitutions > 0 {
+                    reports[position].lines += 1;
+                }
+                reports[position].substitutions += substitutions;
+                *line = edited;
+            }
+        }
+
+        let mut result = lines.join("\n");
+        if trailing_newline {
+            result.push('\n');
+        }
+        (result, reports)
+    }
+}
+
+impl SedExpression {
+    /// A range starts on the line of its first address and ends on the line of the second one,
+    /// a line number that is already behind closes it on the same line
+    fn selects(&self, in_range: &mut bool, number: usize, line: &str, last: bool) -> bool {
+        let start = match &self.start {
+            Some(start) => start,
+            None => return true,
+        };
+        let end = match &self.end {
+            Some(end) => end,
+            None => return start.matches(number, line, last),
+        };
+
+        if !*in_range {
+            if !start.matches(number, line, last) {
+                return false;
+            }
+            *in_range = match end {
+                Address::Line(end_number) => *end_number > number,
+                Address::Last => !last,
+                Address::Pattern(_) => true,
+            };
+            return true;
+        }
+
+        *in_range = match end {
+            Address::Line(end_number) => *end_number > number,
+            Address::Last => !last,
+            Address::Pattern(regex) => !regex.is_match(line),
+        };
+        true
+    }
+
+    fn substitute(&self, line: &str) -> (String, usize) {
+        let mut matches = 0;
+        let mut replaced = 0;
+        let result = self.regex.replace_all(line, |captures: &regex::Captures| {
+            matches += 1;
+            let selected = if self.occurrence > 0 {
+                matches == self.occurrence || (self.global && matches > self.occurrence)
+            } else {
+                self.global || matches == 1
+            };
+            if selected {
+                replaced += 1;
+                let mut replacement = String::new();
+                captures.expand(&self.replacement, &mut replacement);
+                replacement
+            } else {
+                captures[0].to_string()
+            }
+        });
+        (result.to_string(), replaced)
+    }
+}
+
+fn parse_address(chars: &[char], position: &mut usize) -> Result<Option<Address>, String> {
+    match chars.get(*position) {
+        Some('$') => {
+            *position += 1;
+            Ok(Some(Address::Last))
+        }
+        Some(digit) if digit.is_ascii_digit() => {
+            let mut number = String::new();
+            while let Some(digit) = chars.get(*position).filter(|digit| digit.is_ascii_digit()) {
+                number.push(*digit);
+                *position += 1;
+            }
+            Ok(Some(Address::Line(number.parse().map_err(|_| format!("the line {} is not a number", number))?)))
+        }
+        Some('/') => {
+            *position += 1;
+            let pattern = read_until(chars, position, '/', true)?;
+            let regex = Regex::new(&basic_to_regex(&pattern)).map_err(|error| format!("the address /{}/ is not a valid regular expression: {}", pattern, error))?;
+            Ok(Some(Address::Pattern(regex)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Text until the unescaped delimiter, the delimiter is left out. An escaped delimiter is a literal delimiter,
+/// in a pattern it stays escaped when it is special in a regular expression
+fn read_until(chars: &[char], position: &mut usize, delimiter: char, pattern: bool) -> Result<String, String> {
+    let mut text = String::new();
+    while let Some(current) = chars.get(*position) {
+        *position += 1;
+        match current {
+            '\\' => {
+                let next = *chars.get(*position).ok_or("the script ends with \\".to_string())?;
+                *position += 1;
+                if next == delimiter && (!pattern || !".*[]^$\\".contains(next)) {
+                    text.push(next);
+                } else {
+                    text.push('\\');
+                    text.push(next);
+                }
+            }
+            current if *current == delimiter => return Ok(text),
+            current => text.push(*current),
+        }
+    }
+    Err(format!("the delimiter {} is not closed", delimiter))
+}
+
+/// POSIX basic regular expression to the syntax of the regex crate: `\(`, `\)`, `\{`, `\}`, `\|`, `\+` and `\?`
+/// are the operators and the plain characters are literals, `^` and `$` are anchors only at the ends
+fn basic_to_regex(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut regex = String::new();
+    let mut index = 0;
+    // an anchor or a * at this position starts the expression or a group
+    let mut at_start = true;
+
+    while index < chars.len() {
+        let current = chars[index];
+        let starting = at_start;
+        at_start = false;
+        match current {
+            '\\' if index + 1 < chars.len() => {
+                index += 1;
+                match chars[index] {
+                    '(' => { regex.push('('); at_start = true; }
+                    '|' => { regex.push('|'); at_start = true; }
+                    ')' | '{' | '}' | '+' | '?' => regex.push(chars[index]),
+                    'n' => regex.push_str("\\n"),
+                    't' => regex.push_str("\\t"),
+                    digit if digit.is_ascii_digit() => regex.push_str(&format!("\\{}", digit)),
+                    'w' | 'W' | 's' | 'S' | 'b' | 'B' => { regex.push('\\'); regex.push(chars[index]); }
+                    '<' | '>' => regex.push_str("\\b"),
+                    other => regex.push_str(&regex::escape(&other.to_string())),
+                }
+            }
+            '^' if starting => { regex.push('^'); at_start = true; }
+            '$' if index + 1 == chars.len() || chars[index + 1..].starts_with(&['\\', ')']) || chars[index + 1..].starts_with(&['\\', '|']) => regex.push('$'),
+            '*' if starting => regex.push_str("\\*"),
+            '[' => {
+                let (class, next) = bracket_to_regex(&chars, index);
+                regex.push_str(&class);
+                index = next;
+                continue;
+            }
+            '.' | '*' => regex.push(current),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+        index += 1;
+    }
+    regex
+}
+
+/// Bracket expression starting at index and the index after it. A `]` first in the list is a literal,
+/// the backslash is a literal too and the set operators of the regex crate are escaped
+fn bracket_to_regex(chars: &[char], index: usize) -> (String, usize) {
+    let mut class = String::from("[");
+    let mut position = index + 1;
+    if chars.get(position) == Some(&'^') {
+        class.push('^');
+        position += 1;
+    }
+    if chars.get(position) == Some(&']') {
+        class.push_str("\\]");
+        position += 1;
+    }
+    while let Some(current) = chars.get(position) {
+        match current {
+            ']' => return (format!("{}]", class), position + 1),
+            '[' if chars.get(position + 1) == Some(&':') => {
+                let end = (position + 2..chars.len().saturating_sub(1)).find(|end| chars[*end] == ':' && chars[*end + 1] == ']');
+                match end {
+                    Some(end) => {
+                        class.extend(&chars[position..end + 2]);
+                        position = end + 2;
+                        continue;
+                    }
+                    None => class.push_str("\\["),
+                }
+            }
+            '\\' | '[' | '&' | '~' => { class.push('\\'); class.push(*current); }
+            other => class.push(*other),
+        }
+        position += 1;
+    }
+    // not closed, sed reads it as a literal [
+    (format!("\\[{}", regex::escape(&chars[index + 1..].iter().collect::<String>())), chars.len())
+}
+
+/// `\1` and `&` of sed to the `${1}` and `${0}` of the regex crate, a `$` is a literal
+fn translate_replacement(replacement: &str, delimiter: char) -> String {
+    let chars: Vec<char> = replacement.chars().collect();
+    let mut result = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        match chars[index] {
+            '\\' if index + 1 < chars.len() => {
+                index += 1;
+                match chars[index] {
+                    digit if digit.is_ascii_digit() => result.push_str(&format!("${{{}}}", digit)),
+                    'n' => result.push('\n'),
+                    't' => result.push('\t'),
+                    '$' => result.push_str("$$"),
+                    other => result.push(other),
+                }
+            }
+            '&' => result.push_str("${0}"),
+            '$' => result.push_str("$$"),
+            other => result.push(other),
+        }
+        index += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::remote_config::RemoteConfig;
+
+    const ENV: &str = r#"#!/usr/bin/env bash
+
+#CCLI="${HOME}/.cabal/bin/cardano-cli"     # Override automatic detection of path to cardano-cli executable
+#CNODEBIN="${HOME}/.cabal/bin/cardano-node" # Override automatic detection of path to cardano-node executable
+#CNODE_HOME="/opt/cardano/cnode"           # Override default CNODE_HOME path (defaults to /opt/cardano/cnode)
+#CNODE_PORT=6000                           # Set node port
+#CONFIG="${CNODE_HOME}/files/config.json"  # Override automatic detection of node config path
+#SOCKET="${CNODE_HOME}/sockets/node0.socket" # Override automatic detection of path to socket
+#TOPOLOGY="${CNODE_HOME}/files/topology.json" # Override default topology.json path
+#LOG_DIR="${CNODE_HOME}/logs"              # Folder where your logs will be sent to (must pre-exist)
+#DB_DIR="${CNODE_HOME}/db"                 # Folder to store the cardano-node blockchain db
+"#;
+
+    /// Output of GNU sed 4.9 with the same script
+    const ENV_EDITED: &str = r#"#!/usr/bin/env bash
+
+CCLI="/home/cvm/.cvm/bin/current/cardano-cli"     # Override automatic detection of path to cardano-cli executable
+CNODEBIN="/home/cvm/.cvm/bin/current/cardano-node" # Override automatic detection of path to cardano-node executable
+CNODE_HOME="/home/cvm/.cvm"           # Override default CNODE_HOME path (defaults to /home/cvm/.cvm)
+CNODE_PORT=${CNODE_PORT:-6000}                           # Set node port
+CONFIG="${CNODE_HOME}/files/config.json"  # Override automatic detection of node config path
+SOCKET="${CNODE_HOME}/sockets/node0.socket" # Override automatic detection of path to socket
+TOPOLOGY="${CNODE_HOME}/files/topology.json" # Override default topology.json path
+LOG_DIR="${CNODE_HOME}/logs"              # Folder where your logs will be sent to (must pre-exist)
+DB_DIR="${CNODE_HOME}/db"                 # Folder to store the cardano-node blockchain db
+"#;
+
+    const GHCUP: &str = r#"ask() {
+    read -r answer </dev/tty
+}
+warn "GHCup will be installed"
+yellow "Press ENTER to proceed"
+green "Done"
+echo "warn not at the start"
+"#;
+
+    fn remote_config() -> RemoteConfig {
+        toml::from_str(include_str!("../../config/config_remote.toml")).unwrap()
+    }
+
+    fn run(script: &str, text: &str) -> (String, Vec<SedReport>) {
+        SedScript::parse(script).unwrap().apply(text)
+    }
+
+    #[test]
+    fn env_script_of_the_remote_config() {
+        let config = remote_config();
+        let item = config.config_file_item.iter().find(|item| item.name == "env").unwrap();
+        let script = item.get_pattern_sed().replace("{root}", "/home/cvm/.cvm");
+
+        let (text, reports) = run(&script, ENV);
+        assert_eq!(text, ENV_EDITED);
+        assert!(reports.iter().all(|report| report.matched()));
+
+        // an edited file is not the upstream one anymore, only the addresses without the # still find their lines
+        let (_, reports) = run(&script, ENV_EDITED);
+        let matched: Vec<&String> = reports.iter().filter(|report| report.matched()).map(|report| &report.expression).collect();
+        assert_eq!(matched, vec!["/DB_DIR=/s/^#//g", "/CNODEBIN=/s/^#//g", "/CCLI=/s/^#//g"]);
+    }
+
+    #[test]
+    fn legacy_scripts_have_no_root() {
+        // released versions run pattern_sed as it is, {root} would be written to the files
+        for item in remote_config().config_file_item {
+            assert!(!item.pattern_sed.contains("{root}"), "{}", item.name);
+            if !item.json_patch.is_empty() {
+                assert!(item.get_pattern_sed().is_empty(), "{}", item.name);
+            }
+        }
+    }
+
+    #[test]
+    fn ghcup_script_of_the_remote_config() {
+        let (text, reports) = run(&remote_config().init.ghcup_pattern_sed, GHCUP);
+        assert_eq!(text, "ask() {\n    #-r answer </dev/tty\n}\n#warn \"GHCup will be installed\"\n#yellow \"Press ENTER to proceed\"\n#green \"Done\"\necho \"warn not at the start\"\n");
+        assert!(reports.iter().all(|report| report.matched()));
+    }
+
+    #[test]
+    fn groups_and_matched_text() {
+        assert_eq!(run(r"s/\([a-z]\)\([0-9]*\)/\2\1/g", "x1y22").0, "1x22y");
+        assert_eq!(run("1s/a/[&]/", "a=1").0, "[a]=1");
+        assert_eq!(run(r"s/\$5/$6 \&/", "cost $5").0, "cost $6 &");
+        assert_eq!(run("s|^#CNODE_PORT=|$PORT|", "#CNODE_PORT=6000").0, "$PORT6000");
+    }
+
+    #[test]
+    fn occurrence_flags() {
+        assert_eq!(run("s/a/b/", "aaaa").0, "baaa");
+        assert_eq!(run("s/a/b/g", "aaaa").0, "bbbb");
+        assert_eq!(run("s/a/b/2", "aaaa").0, "abaa");
+        assert_eq!(run("s/a/b/2g", "aaaa").0, "abbb");
+        assert_eq!(run("s/A/b/i", "aaaa").0, "baaa");
+    }
+
+    #[test]
+    fn line_addresses() {
+        assert_eq!(run("2,$s/=/ := /;1s/a/[&]/;$s/$/;/", "a=1\nb=2\nc=3\n").0, "[a]=1\nb := 2\nc := 3;\n");
+
+        // the env script only edits the cabal paths in the first 80 lines
+        let mut text = String::new();
+        for _ in 0..81 {
+            text.push_str("${HOME}/.cabal/bin\n");
+        }
+        let (edited, reports) = run("2,80s|.{HOME}/.cabal/bin|/cvm|g", &text);
+        let lines: Vec<&str> = edited.lines().collect();
+        assert_eq!(lines[0], "${HOME}/.cabal/bin");
+        assert_eq!(lines[1], "/cvm");
+        assert_eq!(lines[79], "/cvm");
+        assert_eq!(lines[80], "${HOME}/.cabal/bin");
+        assert_eq!(reports[0].substitutions, 79);
+    }
+
+    #[test]
+    fn pattern_addresses() {
+        let text = "a\n[start]\nb\n[end]\nc\n";
+        assert_eq!(run(r"/^\[start\]/,/^\[end\]/s/^/  /", text).0, "a\n  [start]\n  b\n  [end]\nc\n");
+        assert_eq!(run("/^b/s/^/#/", text).0, "a\n[start]\n#b\n[end]\nc\n");
+    }
+
+    #[test]
+    fn zero_match_is_reported() {
+        let (text, reports) = run("s/absent/x/;/absent/s/^/#/;s/a/b/", "a\n");
+        assert_eq!(text, "b\n");
+        assert!(!reports[0].matched());
+        assert!(!reports[1].matched());
+        assert!(reports[2].matched());
+    }
+
+    #[test]
+    fn invalid_scripts() {
+        assert!(SedScript::parse("y/a/b/").is_err());
+        assert!(SedScript::parse("s/a/b").is_err());
+        assert!(SedScript::parse("s/a/b/x").is_err());
+        assert!(SedScript::parse("2,s/a/b/").is_err());
+    }
+}
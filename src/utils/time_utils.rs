@@ -0,0 +1,131 @@
+#![allow(dead_code, unused_variables)]
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+}
+
+/// Current UTC time as `YYYYMMDDTHHMMSSZ`, sortable and safe to use in file names
+pub fn utc_timestamp() -> String {
+    let secs = unix_now();
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let rest = secs % 86400;
+    format!("{:04}{:02}{:02}T{:02}{:02}{:02}Z", year, month, day, rest / 3600, rest % 3600 / 60, rest % 60)
+}
+
+/// Unix time as `YYYY-MM-DDTHH:MM:SSZ`, the format of the cardano-node log records
+pub fn utc_iso(secs: u64) -> String {
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let rest = secs % 86400;
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, rest / 3600, rest % 3600 / 60, rest % 60)
+}
+
+/// Days since 1970-01-01 to a (year, month, day) date in the proleptic gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Unix time of a `YYYY-MM-DDTHH:MM:SS` UTC date, the fraction of the seconds and the zone are ignored
+pub fn parse_utc_iso(date: &str) -> Option<u64> {
+    let number = |range: std::ops::Range<usize>| date.get(range).and_then(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    let seconds = days_from_civil(year, month as u32, day as u32) * 86400 + hour * 3600 + minute * 60 + second;
+    u64::try_from(seconds).ok()
+}
+
+/// Inverse of civil_from_days
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Unix time of a RFC 3339 date like `2022-08-01T12:00:00.5+02:00`, the zone offset is applied
+pub fn parse_rfc3339(date: &str) -> Option<u64> {
+    let secs = parse_utc_iso(date)? as i64;
+    let zone = date.get(19..)?.trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = match zone {
+        "" | "Z" | "z" => 0,
+        _ => {
+            let sign = match zone.get(0..1)? { "+" => 1, "-" => -1, _ => return None };
+            let hours = zone.get(1..3)?.parse::<i64>().ok()?;
+            let minutes = zone.get(3..).map(|rest| rest.trim_start_matches(':')).filter(|rest| !rest.is_empty()).map(|rest| rest.parse::<i64>().ok()).unwrap_or(Some(0))?;
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+    u64::try_from(secs - offset).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_round_trip() {
+        // 1600-01-01 to 2400-12-31, four centuries each side of the epoch
+        for days in -135140..157000 {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days, "{}-{}-{}", year, month, day);
+        }
+    }
+
+    #[test]
+    fn known_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(utc_iso(951782400), "2000-02-29T00:00:00Z");
+        assert_eq!(utc_iso(1704067199), "2023-12-31T23:59:59Z");
+        assert_eq!(utc_iso(1704067200), "2024-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn leap_years() {
+        let february = |year| days_from_civil(year, 3, 1) - days_from_civil(year, 2, 1);
+        assert_eq!(february(2024), 29);
+        assert_eq!(february(2023), 28);
+        assert_eq!(february(2000), 29);
+        assert_eq!(february(1900), 28);
+        assert_eq!(february(2100), 28);
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 28) + 1), (2024, 2, 29));
+        assert_eq!(civil_from_days(days_from_civil(2023, 2, 28) + 1), (2023, 3, 1));
+    }
+
+    #[test]
+    fn month_boundaries() {
+        for (year, month, last) in [(2023, 1, 31), (2023, 4, 30), (2024, 2, 29), (2023, 6, 30), (2023, 12, 31)] {
+            let end = format!("{:04}-{:02}-{:02}T23:59:59Z", year, month, last);
+            let secs = parse_utc_iso(&end).unwrap();
+            assert_eq!(utc_iso(secs), end);
+            let next = if month == 12 { format!("{:04}-01-01T00:00:00Z", year + 1) } else { format!("{:04}-{:02}-01T00:00:00Z", year, month + 1) };
+            assert_eq!(utc_iso(secs + 1), next);
+        }
+    }
+
+    #[test]
+    fn rfc3339_offsets() {
+        let utc = parse_rfc3339("2022-08-01T12:00:00Z").unwrap();
+        assert_eq!(parse_utc_iso("2022-08-01T12:00:00").unwrap(), utc);
+        assert_eq!(parse_rfc3339("2022-08-01T12:00:00.123456Z").unwrap(), utc);
+        assert_eq!(parse_rfc3339("2022-08-01T14:00:00+02:00").unwrap(), utc);
+        assert_eq!(parse_rfc3339("2022-08-01T05:00:00-07:00").unwrap(), utc);
+        assert_eq!(parse_rfc3339("2022-08-01T17:30:00.5+0530").unwrap(), utc);
+        // the offset crosses midnight and the end of a month
+        assert_eq!(utc_iso(parse_rfc3339("2022-08-01T01:00:00+02:00").unwrap()), "2022-07-31T23:00:00Z");
+        assert_eq!(parse_rfc3339("2022-08-01T12:00:00 UTC"), None);
+    }
+}
@@ -0,0 +1,513 @@
+#![allow(dead_code, unused_variables)]
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use serde_json::{json, Map, Value};
+use crate::{Message, MessageData, Success};
+use crate::utils::file_utils::write_file_atomic;
+
+pub const TOPOLOGY_FILE: &str = "topology.json";
+const PRODUCERS: &str = "Producers";
+const LOCAL_ROOTS: &str = "localRoots";
+const PUBLIC_ROOTS: &str = "publicRoots";
+const ACCESS_POINTS: &str = "accessPoints";
+const VALENCY: &str = "valency";
+/// Valency of the local roots since the node 8.x, the older ones use valency
+const HOT_VALENCY: &str = "hotValency";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TopologyFormat {
+    /// `Producers` list read once when the node starts
+    LEGACY,
+    /// `localRoots` and `publicRoots` groups, reloaded by the node on SIGHUP
+    P2P,
+}
+
+impl TopologyFormat {
+    pub fn parse(value: &str) -> Option<TopologyFormat> {
+        match value.to_lowercase().as_str() {
+            "legacy" => Some(TopologyFormat::LEGACY),
+            "p2p" => Some(TopologyFormat::P2P),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TopologyFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TopologyFormat::LEGACY => write!(f, "legacy"),
+            TopologyFormat::P2P => write!(f, "P2P"),
+        }
+    }
+}
+
+/// Peer of the topology, the group is the index of the producer or of the root group it belongs to
+pub struct Peer {
+    pub list: String,
+    pub group: usize,
+    pub address: String,
+    pub port: u64,
+    pub valency: Option<u64>,
+}
+
+/// topology.json kept as json, the fields cvm doesn't know like advertise, trustable or useLedgerAfterSlot
+/// are written back untouched
+pub struct NodeTopology {
+    pub format: TopologyFormat,
+    pub json: Value,
+}
+
+impl NodeTopology {
+    pub fn read(path: &String) -> Result<NodeTopology, Message> {
+        let text = fs::read_to_string(path)?;
+        NodeTopology::parse(&text).map_err(|message| topology_error(format!("{} is not a valid topology: {}", path, message)))
+    }
+
+    pub fn parse(text: &str) -> Result<NodeTopology, String> {
+        let json: Value = serde_json::from_str(text).map_err(|error| error.to_string())?;
+        let object = json.as_object().ok_or("the topology must be a json object".to_string())?;
+
+        let format = if object.contains_key(PRODUCERS) {
+            TopologyFormat::LEGACY
+        } else if object.contains_key(LOCAL_ROOTS) || object.contains_key(PUBLIC_ROOTS) {
+            TopologyFormat::P2P
+        } else {
+            return Err(format!("there is no {} list or {} and {} groups", PRODUCERS, LOCAL_ROOTS, PUBLIC_ROOTS));
+        };
+        Ok(NodeTopology { format, json })
+    }
+
+    pub fn write(&self, path: &String) -> Result<Success, Message> {
+        let mut text = serde_json::to_string_pretty(&self.json).map_err(|error| topology_error(error.to_string()))?;
+        text.push('\n');
+        write_file_atomic(path, text.as_bytes())
+    }
+
+    pub fn peers(&self) -> Vec<Peer> {
+        let mut peers = vec![];
+        match self.format {
+            TopologyFormat::LEGACY => {
+                for (index, producer) in self.list(PRODUCERS).iter().enumerate() {
+                    peers.push(Peer {
+                        list: PRODUCERS.to_string(),
+                        group: index,
+                        address: producer.get("addr").and_then(|addr| addr.as_str()).unwrap_or("").to_string(),
+                        port: producer.get("port").and_then(|port| port.as_u64()).unwrap_or(0),
+                        valency: producer.get(VALENCY).and_then(|valency| valency.as_u64()),
+                    });
+                }
+            }
+            TopologyFormat::P2P => {
+                for list in [LOCAL_ROOTS, PUBLIC_ROOTS] {
+                    for (index, group) in self.list(list).iter().enumerate() {
+                        let valency = group_valency(group);
+                        for point in group.get(ACCESS_POINTS).and_then(|points| points.as_array()).cloned().unwrap_or_default() {
+                            peers.push(Peer {
+                                list: list.to_string(),
+                                group: index,
+                                address: point.get("address").and_then(|address| address.as_str()).unwrap_or("").to_string(),
+                                port: point.get("port").and_then(|port| port.as_u64()).unwrap_or(0),
+                                valency,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        peers
+    }
+
+    /// Problems that make the node reject the topology or ignore some of its peers, empty when it is valid
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = vec![];
+        let mut seen = HashSet::new();
+        let mut check_peer = |problems: &mut Vec<String>, point: &Value, address_key: &str, name: String| {
+            let address = point.get(address_key).and_then(|address| address.as_str()).unwrap_or("");
+            let port = point.get("port").and_then(|port| port.as_u64());
+            if address.trim().is_empty() {
+                problems.push(format!("{} has no {}", name, address_key));
+            }
+            match port {
+                Some(port) if port > 0 && port <= 65535 => {
+                    if !seen.insert(format!("{}:{}", address, port)) {
+                        problems.push(format!("{} {}:{} is repeated", name, address, port));
+                    }
+                }
+                _ => problems.push(format!("{} has no valid port, it must be a number between 1 and 65535", name)),
+            }
+        };
+
+        match self.format {
+            TopologyFormat::LEGACY => {
+                if !self.json[PRODUCERS].is_array() {
+                    problems.push(format!("{} must be a list", PRODUCERS));
+                }
+                for (index, producer) in self.list(PRODUCERS).iter().enumerate() {
+                    let name = format!("{} #{}", PRODUCERS, index + 1);
+                    check_peer(&mut problems, producer, "addr", name.to_string());
+                    if let Some(valency) = producer.get(VALENCY) {
+                        if valency.as_u64().map(|valency| valency == 0).unwrap_or(true) {
+                            problems.push(format!("{} has a valency of {}, it must be a number greater than 0", name, valency));
+                        }
+                    }
+                }
+            }
+            TopologyFormat::P2P => {
+                for list in [LOCAL_ROOTS, PUBLIC_ROOTS] {
+                    if self.json.get(list).map(|value| !value.is_array()).unwrap_or(false) {
+                        problems.push(format!("{} must be a list", list));
+                    }
+                    for (index, group) in self.list(list).iter().enumerate() {
+                        let name = format!("{} #{}", list, index + 1);
+                        let points = group.get(ACCESS_POINTS).and_then(|points| points.as_array()).cloned().unwrap_or_default();
+                        if points.is_empty() {
+                            problems.push(format!("{} has no {}", name, ACCESS_POINTS));
+                        }
+                        for point in points.iter() {
+                            check_peer(&mut problems, point, "address", name.to_string());
+                        }
+                        if list == LOCAL_ROOTS {
+                            match group_valency(group) {
+                                Some(valency) if valency as usize > points.len() =>
+                                    problems.push(format!("{} has a valency of {} but only {} access points", name, valency, points.len())),
+                                Some(_) => {}
+                                None => problems.push(format!("{} has no valency", name)),
+                            }
+                        }
+                    }
+                }
+                if let Some(slot) = self.json.get("useLedgerAfterSlot") {
+                    if !slot.is_i64() {
+                        problems.push(format!("useLedgerAfterSlot must be a slot number or -1, not {}", slot));
+                    }
+                }
+            }
+        }
+        problems
+    }
+
+    /// New legacy producer, or new group with a single access point in the local roots or in the public roots
+    pub fn add(&mut self, address: &String, port: u64, valency: u64, public: bool) -> Result<Success, Message> {
+        if self.peers().iter().any(|peer| &peer.address == address && peer.port == port) {
+            return Err(topology_error(format!("{}:{} is already in the topology", address, port)));
+        }
+
+        match self.format {
+            TopologyFormat::LEGACY => {
+                self.list_mut(PRODUCERS).push(json!({ "addr": address, "port": port, VALENCY: valency }));
+            }
+            TopologyFormat::P2P => {
+                let mut group = Map::new();
+                group.insert(ACCESS_POINTS.to_string(), json!([{ "address": address, "port": port }]));
+                group.insert("advertise".to_string(), Value::Bool(false));
+                if public {
+                    self.list_mut(PUBLIC_ROOTS).push(Value::Object(group));
+                } else {
+                    // the new group follows the valency of the existing ones
+                    let key = if self.list(LOCAL_ROOTS).iter().any(|group| group.get(HOT_VALENCY).is_some()) { HOT_VALENCY } else { VALENCY };
+                    group.insert(key.to_string(), json!(valency));
+                    self.list_mut(LOCAL_ROOTS).push(Value::Object(group));
+                }
+            }
+        }
+        Ok(Success {})
+    }
+
+    /// Remove the peers with the address, only the one with the port when it is given.
+    /// The root groups left without access points are removed too, returns the number of peers removed
+    pub fn remove(&mut self, address: &String, port: Option<u64>) -> Result<usize, Message> {
+        let matches = |point: &Value, address_key: &str| {
+            point.get(address_key).and_then(|value| value.as_str()) == Some(address.as_str())
+                && port.map(|port| point.get("port").and_then(|value| value.as_u64()) == Some(port)).unwrap_or(true)
+        };
+
+        let before = self.peers().len();
+        match self.format {
+            TopologyFormat::LEGACY => {
+                self.list_mut(PRODUCERS).retain(|producer| !matches(producer, "addr"));
+            }
+            TopologyFormat::P2P => {
+                for list in [LOCAL_ROOTS, PUBLIC_ROOTS] {
+                    for group in self.list_mut(list).iter_mut() {
+                        if let Some(points) = group.get_mut(ACCESS_POINTS).and_then(|points| points.as_array_mut()) {
+                            points.retain(|point| !matches(point, "address"));
+                        }
+                        // the valency can't be larger than the peers left in the group
+                        let left = group.get(ACCESS_POINTS).and_then(|points| points.as_array()).map(|points| points.len()).unwrap_or(0) as u64;
+                        if let Some(key) = valency_key(group) {
+                            if group_valency(group).map(|valency| valency > left).unwrap_or(false) {
+                                group[key] = json!(left);
+                            }
+                        }
+                    }
+                    self.list_mut(list).retain(|group| group.get(ACCESS_POINTS).and_then(|points| points.as_array()).map(|points| !points.is_empty()).unwrap_or(false));
+                }
+            }
+        }
+
+        let removed = before - self.peers().len();
+        if removed == 0 {
+            return Err(topology_error(format!("There is no peer {} in the topology", display_peer(address, port))));
+        }
+        Ok(removed)
+    }
+
+    /// Valency of the legacy producer, or of the local root group, with the address
+    pub fn set_valency(&mut self, address: &String, port: Option<u64>, valency: u64) -> Result<Success, Message> {
+        let peers: Vec<Peer> = self.peers().into_iter()
+            .filter(|peer| &peer.address == address && port.map(|port| peer.port == port).unwrap_or(true))
+            .collect();
+        if peers.is_empty() {
+            return Err(topology_error(format!("There is no peer {} in the topology", display_peer(address, port))));
+        }
+        if peers.iter().all(|peer| peer.list == PUBLIC_ROOTS) {
+            return Err(topology_error(format!("{} is in the {}, only the {} have a valency", display_peer(address, port), PUBLIC_ROOTS, LOCAL_ROOTS)));
+        }
+
+        for peer in peers.iter().filter(|peer| peer.list != PUBLIC_ROOTS) {
+            let entry = &mut self.list_mut(&peer.list)[peer.group];
+            let key = valency_key(entry).unwrap_or(VALENCY);
+            entry[key] = json!(valency);
+        }
+        Ok(Success {})
+    }
+
+    /// Legacy producers become local root groups of one peer with the ledger peers disabled, as the legacy node only
+    /// connects to the listed peers. The root groups become producers of valency 1, the fields of P2P are lost,
+    /// lost_valencies tells which valencies change
+    pub fn convert(&self, format: TopologyFormat) -> NodeTopology {
+        if format == self.format {
+            return NodeTopology { format, json: self.json.clone() };
+        }
+
+        let json = match format {
+            TopologyFormat::P2P => {
+                let groups: Vec<Value> = self.peers().iter().map(|peer| json!({
+                    ACCESS_POINTS: [{ "address": peer.address, "port": peer.port }],
+                    "advertise": false,
+                    VALENCY: 1,
+                })).collect();
+                json!({ LOCAL_ROOTS: groups, PUBLIC_ROOTS: [], "useLedgerAfterSlot": -1 })
+            }
+            TopologyFormat::LEGACY => {
+                let producers: Vec<Value> = self.peers().iter().map(|peer| json!({
+                    "addr": peer.address,
+                    "port": peer.port,
+                    VALENCY: 1,
+                })).collect();
+                json!({ PRODUCERS: producers })
+            }
+        };
+        NodeTopology { format, json }
+    }
+
+    /// Valencies the conversion can't keep: a legacy valency above 1 is a dns name with several addresses
+    /// and a root group keeps some of its peers hot, while the legacy node connects to every producer
+    pub fn lost_valencies(&self, format: TopologyFormat) -> Vec<String> {
+        if format == self.format {
+            return vec![];
+        }
+        let mut lost = vec![];
+        match self.format {
+            TopologyFormat::LEGACY => {
+                for peer in self.peers().iter().filter(|peer| peer.valency.unwrap_or(1) > 1) {
+                    lost.push(format!("{} #{} {}:{} has a valency of {}, its group gets a valency of 1",
+                                      PRODUCERS, peer.group + 1, peer.address, peer.port, peer.valency.unwrap_or(1)));
+                }
+            }
+            TopologyFormat::P2P => {
+                for (index, group) in self.list(LOCAL_ROOTS).iter().enumerate() {
+                    let points = group.get(ACCESS_POINTS).and_then(|points| points.as_array()).map(|points| points.len()).unwrap_or(0) as u64;
+                    if let Some(valency) = group_valency(group).filter(|valency| *valency != points) {
+                        lost.push(format!("{} #{} has a valency of {} for {} peers, each of them becomes a producer of valency 1",
+                                          LOCAL_ROOTS, index + 1, valency, points));
+                    }
+                }
+            }
+        }
+        lost
+    }
+
+    fn list(&self, name: &str) -> Vec<Value> {
+        self.json.get(name).and_then(|list| list.as_array()).cloned().unwrap_or_default()
+    }
+
+    fn list_mut(&mut self, name: &str) -> &mut Vec<Value> {
+        let object = self.json.as_object_mut().expect("the topology is a json object");
+        let list = object.entry(name.to_string()).or_insert(json!([]));
+        if !list.is_array() {
+            *list = json!([]);
+        }
+        list.as_array_mut().unwrap()
+    }
+}
+
+fn valency_key(group: &Value) -> Option<&'static str> {
+    if group.get(HOT_VALENCY).is_some() {
+        Some(HOT_VALENCY)
+    } else if group.get(VALENCY).is_some() {
+        Some(VALENCY)
+    } else {
+        None
+    }
+}
+
+fn group_valency(group: &Value) -> Option<u64> {
+    valency_key(group).and_then(|key| group[key].as_u64())
+}
+
+pub fn display_peer(address: &String, port: Option<u64>) -> String {
+    match port {
+        Some(port) => format!("{}:{}", address, port),
+        None => address.to_string(),
+    }
+}
+
+pub fn topology_error(message: String) -> Message {
+    Message::Topology(MessageData {
+        message,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY: &str = r#"{"Producers": [
+        {"addr": "relay1.example.com", "port": 3001, "valency": 2},
+        {"addr": "10.0.0.2", "port": 3002, "valency": 1}
+    ]}"#;
+
+    const P2P: &str = r#"{
+        "localRoots": [
+            {"accessPoints": [{"address": "10.0.0.1", "port": 3001}, {"address": "10.0.0.2", "port": 3002}], "advertise": false, "hotValency": 2, "trustable": true}
+        ],
+        "publicRoots": [
+            {"accessPoints": [{"address": "relays.example.com", "port": 3001}], "advertise": false}
+        ],
+        "useLedgerAfterSlot": 128908821
+    }"#;
+
+    fn peer_names(topology: &NodeTopology) -> Vec<String> {
+        topology.peers().iter().map(|peer| format!("{}:{}", peer.address, peer.port)).collect()
+    }
+
+    #[test]
+    fn parse_formats() {
+        assert_eq!(NodeTopology::parse(LEGACY).unwrap().format, TopologyFormat::LEGACY);
+        assert_eq!(NodeTopology::parse(P2P).unwrap().format, TopologyFormat::P2P);
+        assert!(NodeTopology::parse(r#"{"peers": []}"#).is_err());
+        assert!(NodeTopology::parse("[]").is_err());
+    }
+
+    #[test]
+    fn hot_valency_and_valency() {
+        let topology = NodeTopology::parse(P2P).unwrap();
+        assert_eq!(topology.peers()[0].valency, Some(2));
+        assert_eq!(topology.peers()[2].valency, None);
+
+        let old = NodeTopology::parse(&P2P.replace("hotValency", "valency")).unwrap();
+        assert_eq!(old.peers()[0].valency, Some(2));
+        assert!(old.validate().is_empty());
+    }
+
+    #[test]
+    fn add_follows_the_valency_key() {
+        let mut topology = NodeTopology::parse(P2P).unwrap();
+        topology.add(&"10.0.0.3".to_string(), 3003, 1, false).unwrap();
+        assert_eq!(topology.json[LOCAL_ROOTS][1][HOT_VALENCY], json!(1));
+        assert!(topology.json[LOCAL_ROOTS][1].get(VALENCY).is_none());
+
+        let mut old = NodeTopology::parse(&P2P.replace("hotValency", "valency")).unwrap();
+        old.add(&"10.0.0.3".to_string(), 3003, 1, false).unwrap();
+        assert_eq!(old.json[LOCAL_ROOTS][1][VALENCY], json!(1));
+
+        assert!(topology.add(&"10.0.0.3".to_string(), 3003, 1, false).is_err());
+    }
+
+    #[test]
+    fn set_valency_keeps_the_key() {
+        let mut topology = NodeTopology::parse(P2P).unwrap();
+        topology.set_valency(&"10.0.0.2".to_string(), None, 1).unwrap();
+        assert_eq!(topology.json[LOCAL_ROOTS][0][HOT_VALENCY], json!(1));
+        assert!(topology.json[LOCAL_ROOTS][0].get(VALENCY).is_none());
+        assert!(topology.set_valency(&"relays.example.com".to_string(), None, 2).is_err());
+    }
+
+    #[test]
+    fn remove_shrinks_the_valency() {
+        let mut topology = NodeTopology::parse(P2P).unwrap();
+        assert_eq!(topology.remove(&"10.0.0.1".to_string(), Some(3001)).unwrap(), 1);
+        assert_eq!(topology.json[LOCAL_ROOTS][0][HOT_VALENCY], json!(1));
+        assert!(topology.validate().is_empty());
+
+        // the last peer of a group takes the group with it
+        topology.remove(&"10.0.0.2".to_string(), None).unwrap();
+        assert_eq!(topology.json[LOCAL_ROOTS], json!([]));
+        // the fields cvm doesn't know are kept
+        assert_eq!(topology.json["useLedgerAfterSlot"], json!(128908821));
+
+        assert!(topology.remove(&"10.0.0.9".to_string(), None).is_err());
+    }
+
+    #[test]
+    fn validate_problems() {
+        let mut topology = NodeTopology::parse(P2P).unwrap();
+        topology.json[LOCAL_ROOTS][0][HOT_VALENCY] = json!(3);
+        topology.json[PUBLIC_ROOTS][0][ACCESS_POINTS][0]["port"] = json!(70000);
+        topology.json["useLedgerAfterSlot"] = json!("never");
+        let problems = topology.validate();
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+
+        let repeated = NodeTopology::parse(&LEGACY.replace("10.0.0.2\", \"port\": 3002", "relay1.example.com\", \"port\": 3001")).unwrap();
+        assert_eq!(repeated.validate(), vec!["Producers #2 relay1.example.com:3001 is repeated".to_string()]);
+
+        let zero = NodeTopology::parse(&LEGACY.replace("\"valency\": 2", "\"valency\": 0")).unwrap();
+        assert_eq!(zero.validate().len(), 1);
+    }
+
+    #[test]
+    fn legacy_to_p2p_and_back() {
+        let legacy = NodeTopology::parse(LEGACY).unwrap();
+        let p2p = legacy.convert(TopologyFormat::P2P);
+        assert_eq!(p2p.format, TopologyFormat::P2P);
+        assert!(p2p.validate().is_empty());
+        assert_eq!(peer_names(&p2p), peer_names(&legacy));
+        assert_eq!(p2p.json["useLedgerAfterSlot"], json!(-1));
+        assert_eq!(p2p.json[LOCAL_ROOTS].as_array().unwrap().len(), 2);
+
+        let back = p2p.convert(TopologyFormat::LEGACY);
+        assert!(back.validate().is_empty());
+        assert_eq!(peer_names(&back), peer_names(&legacy));
+    }
+
+    #[test]
+    fn p2p_to_legacy() {
+        let p2p = NodeTopology::parse(P2P).unwrap();
+        let legacy = p2p.convert(TopologyFormat::LEGACY);
+        assert_eq!(legacy.format, TopologyFormat::LEGACY);
+        assert!(legacy.validate().is_empty());
+        assert_eq!(peer_names(&legacy), vec!["10.0.0.1:3001", "10.0.0.2:3002", "relays.example.com:3001"]);
+        assert!(legacy.json.get(LOCAL_ROOTS).is_none());
+    }
+
+    #[test]
+    fn lost_valencies() {
+        let legacy = NodeTopology::parse(LEGACY).unwrap();
+        let lost = legacy.lost_valencies(TopologyFormat::P2P);
+        assert_eq!(lost.len(), 1);
+        assert!(lost[0].contains("relay1.example.com:3001"), "{}", lost[0]);
+        assert!(legacy.lost_valencies(TopologyFormat::LEGACY).is_empty());
+
+        // every peer of the group is hot, the legacy producers keep it
+        let p2p = NodeTopology::parse(P2P).unwrap();
+        assert!(p2p.lost_valencies(TopologyFormat::LEGACY).is_empty());
+
+        let mut partial = NodeTopology::parse(P2P).unwrap();
+        partial.json[LOCAL_ROOTS][0][HOT_VALENCY] = json!(1);
+        assert_eq!(partial.lost_valencies(TopologyFormat::LEGACY).len(), 1);
+    }
+}
@@ -1,6 +1,11 @@
 use std::env;
-use users::{get_current_uid, get_user_by_uid};
+use std::os::unix::fs::lchown;
+use walkdir::WalkDir;
+use users::{get_current_uid, get_user_by_name, get_user_by_uid};
+use users::os::unix::UserExt;
 use crate::{MessageData, Message};
+use crate::task::task_impl::commons::run_command_task::{Cmd, RunCommandInputData};
+use crate::utils::folders::Folder;
 
 pub fn get_current_user() -> Result<String, Message> {
     let user = get_user_by_uid(get_current_uid()).unwrap();
@@ -13,8 +18,65 @@ pub fn get_current_user() -> Result<String, Message> {
         return Ok(String::from(sudo_user.to_str().unwrap()));
     }
 
-    Err(Message::UserNotFound(MessageData {
-        message: "User not found".to_string(),
-        ..Default::default()
-    }))
-}
\ No newline at end of file
+    Ok(String::from(user.name().to_str().unwrap()))
+}
+
+pub fn get_user_home() -> Result<String, Message> {
+    let user_name = get_current_user()?;
+    match get_user_by_name(&user_name) {
+        Some(user) => Ok(user.home_dir().to_str().unwrap().to_string()),
+        None => Err(Message::UserNotFound(MessageData {
+            message: format!("User {} not found", user_name),
+            ..Default::default()
+        }))
+    }
+}
+
+/// Escalate to root keeping the variables cvm uses to resolve its folders and the editor,
+/// in rootless mode everything runs as the current user
+pub fn escalate_if_needed() -> Result<(), Message> {
+    if Folder::is_rootless() {
+        return Ok(());
+    }
+    match sudo::with_env(&["CVM_", "GHCUP_", "EDITOR", "VISUAL"]) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Message::PermissionDenied(MessageData {
+            message: format!("Super user permissions are required, {}", error),
+            ..Default::default()
+        })),
+    }
+}
+
+/// Run the command with sudo keeping the given variables, or directly in rootless mode
+pub fn as_root(input_data: RunCommandInputData, preserve_env: &[&str]) -> RunCommandInputData {
+    if Folder::is_rootless() {
+        return input_data;
+    }
+    let mut args = vec![];
+    if !preserve_env.is_empty() {
+        args.push(format!("--preserve-env={}", preserve_env.join(",")));
+    }
+    args.push(input_data.command);
+    args.extend(input_data.args);
+    RunCommandInputData { command: Cmd::Sudo.as_string(), args, current_dir: input_data.current_dir }
+}
+/// Give back to the real user the files created while running as root with sudo
+pub fn chown_to_user(path: &String) -> Result<(), Message> {
+    if get_current_uid() != 0 {
+        return Ok(());
+    }
+
+    let user_name = get_current_user()?;
+    let user = match get_user_by_name(&user_name) {
+        Some(user) => user,
+        None => return Err(Message::UserNotFound(MessageData {
+            message: format!("User {} not found", user_name),
+            ..Default::default()
+        }))
+    };
+
+    for entry in WalkDir::new(path).into_iter().flatten() {
+        lchown(entry.path(), Some(user.uid()), Some(user.primary_group_id()))?;
+    }
+    Ok(())
+}
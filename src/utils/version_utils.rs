@@ -1,16 +1,19 @@
 #![allow(dead_code, unused_variables)]
 
+use std::cmp::Ordering;
 use std::fs;
 use std::fs::File;
 use std::path::Path;
 use regex::Regex;
 use serde::{Serialize, Deserialize};
 use crate::message::message::{Message, MessageData};
+use crate::config::remote_config::Component;
 use crate::url_build;
 
 pub const LATEST: &str = "latest";
 const USER_AGENT: &str = "cvm";
 const VERSION_FILE: &str = "version";
+const CURRENT_FOLDER: &str = "current";
 const CVM_RELEASES: &str = "https://api.github.com/repos/orelvis15/cvm/releases/latest";
 
 pub fn verify_version(version: &str) -> Result<&str, Message> {
@@ -26,6 +29,35 @@ pub fn verify_version(version: &str) -> Result<&str, Message> {
     }
 }
 
+/// Installed versions are named by their number or by the label passed to [cvm install --name]
+pub fn verify_label(label: &str) -> Result<&str, Message> {
+    let regex = Regex::new(r"^[A-Za-z0-9][A-Za-z0-9._-]*$").unwrap();
+    if regex.is_match(label) && label != CURRENT_FOLDER {
+        Ok(label)
+    } else {
+        return Err(Message::VersionBadFormed(MessageData {
+            message: format!("The name {} is not valid, use letters, numbers, '.', '_' and '-'", label),
+            ..Default::default()
+        }));
+    }
+}
+
+/// Compare two x.y.z versions number by number, missing parts count as 0
+pub fn compare_versions(left: &str, right: &str) -> Ordering {
+    let parse = |version: &str| -> Vec<u64> {
+        version.split('.').map(|part| part.parse::<u64>().unwrap_or(0)).collect()
+    };
+    let (left, right) = (parse(left), parse(right));
+
+    for index in 0..left.len().max(right.len()) {
+        let ordering = left.get(index).unwrap_or(&0).cmp(right.get(index).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
 pub fn get_last_tag(url: &String) -> Result<String, Message> {
     let client = reqwest::blocking::Client::builder().user_agent(USER_AGENT).build();
     if let Ok(client) = client {
@@ -42,6 +74,33 @@ pub fn get_last_tag(url: &String) -> Result<String, Message> {
     }));
 }
 
+/// Versions of the published releases, without drafts and pre-releases.
+/// The url is the one of the latest release, like the cnode_release of the remote config
+pub fn get_release_versions(latest_url: &String) -> Result<Vec<String>, Message> {
+    let url = format!("{}?per_page=100", latest_url.trim_end_matches('/').trim_end_matches("/latest"));
+    let list_error = |stack: String| Message::CheckCardanoVersion(MessageData {
+        message: "Error listing the cardano node releases".to_string(),
+        stack: vec![stack],
+        ..Default::default()
+    });
+
+    let client = reqwest::blocking::Client::builder().user_agent(USER_AGENT).build().map_err(|error| list_error(error.to_string()))?;
+    let text = client.get(&url).send().and_then(|response| response.text()).map_err(|error| list_error(error.to_string()))?;
+    let releases: Vec<Release> = serde_json::from_str(&text).map_err(|error| list_error(error.to_string()))?;
+
+    Ok(releases.into_iter()
+        .filter(|release| !release.draft && !release.prerelease)
+        .map(|release| release.tag_name)
+        .filter(|tag| verify_version(tag).is_ok())
+        .collect())
+}
+
+/// Latest released version of a component, without the tag prefix
+pub fn get_last_component_version(component: &Component) -> Result<String, Message> {
+    let tag = get_last_tag(&component.release)?;
+    Ok(tag.strip_prefix(component.tag_prefix.as_str()).unwrap_or(&tag).to_string())
+}
+
 pub fn get_last_cvm_version() -> Result<String, Message> {
     let client = reqwest::blocking::Client::builder().user_agent(USER_AGENT).build();
     if let Ok(client) = client {
@@ -82,6 +141,15 @@ pub fn read_version(current_folder: &String) -> String {
     value
 }
 
+#[derive(Serialize, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Tag {
     #[serde(rename = "tag_name")]